@@ -0,0 +1,65 @@
+//! Compares the general sand outline with the straight-sided fast path.
+//!
+//! Run with `cargo bench -p hourglass-geometry`. Each iteration regenerates the sand of both bulbs,
+//! as the plugin does for every hourglass whose timer advanced, so a scene with hundreds of auto
+//! flipping hourglasses pays this cost hundreds of times per frame.
+
+use hourglass_geometry::*;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 100_000;
+
+fn params(step: u32, bulb: SandBulb) -> SandOutlineParams {
+    SandOutlineParams {
+        fill_percent: (step % 101) as f32 / 100.0,
+        wall_offset: 6.0,
+        bulb,
+        neck_height: 6.0,
+        min_y: -100.0,
+        max_y: 100.0,
+        tilt: 0.0,
+        solid_stream: true,
+        surface_wave: None,
+        funnel_fill: 0.0,
+        robust_geometry: false,
+    }
+}
+
+fn time(name: &str, mut generate: impl FnMut(&SandOutlineParams) -> Vec<Point2D>) -> Duration {
+    let start = Instant::now();
+    for step in 0..ITERATIONS {
+        for bulb in [SandBulb::Top, SandBulb::Bottom] {
+            black_box(generate(black_box(&params(step, bulb))));
+        }
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<8} {:>8.1} ns per hourglass",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+    elapsed
+}
+
+fn main() {
+    let shape = HourglassShapeBuilder {
+        total_height: 200.0,
+        bulb_style: BulbStyle::Straight { width_factor: 0.6 },
+        neck_style: NeckStyle::Straight {
+            width: 10.0,
+            height: 6.0,
+        },
+    };
+    let outline = shape.generate_outline_with_wall_offset(6.0);
+
+    let general = time("general", |params| {
+        generate_sand_outline_with_params(&outline, params)
+    });
+    let fast = time("fast", |params| {
+        shape.generate_sand_outline(&outline, params)
+    });
+    println!(
+        "speedup  {:>8.2}x",
+        general.as_secs_f64() / fast.as_secs_f64()
+    );
+}
@@ -0,0 +1,245 @@
+//! Writes SVG or PNG previews of an hourglass shape at several fill levels.
+//!
+//! Usage: `hourglass-preview <style.ron> <output.svg|output.png>`
+//!
+//! The style file describes the shape and how the preview is drawn, every field is optional:
+//!
+//! ```ron
+//! (
+//!     shape: (
+//!         total_height: 200.0,
+//!         bulb_style: Straight(width_factor: 0.6),
+//!         neck_style: Straight(width: 10.0, height: 6.0),
+//!     ),
+//!     wall_offset: 6.0,
+//!     fills: [1.0, 0.5, 0.1],
+//! )
+//! ```
+
+use hourglass_geometry::*;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::{env, process};
+
+/// Space between the previews and around them (in pixels)
+const MARGIN: f32 = 20.0;
+
+/// Contents of a style file
+#[derive(Deserialize)]
+#[serde(default)]
+struct PreviewStyle {
+    shape: HourglassShapeBuilder,
+    /// Distance of the sand from the glass walls
+    wall_offset: f32,
+    /// Fill levels of the top bulb to preview, from left to right
+    fills: Vec<f32>,
+    /// Top bulb fill below which the sand sinks into a funnel
+    funnel_fill: f32,
+    /// RGBA color of the glass
+    glass_color: [u8; 4],
+    /// RGBA color of the sand
+    sand_color: [u8; 4],
+}
+
+impl Default for PreviewStyle {
+    fn default() -> Self {
+        Self {
+            shape: HourglassShapeBuilder::default(),
+            wall_offset: 8.0,
+            fills: vec![1.0, 0.75, 0.5, 0.25, 0.0],
+            funnel_fill: 0.1,
+            glass_color: [217, 242, 255, 80],
+            sand_color: [222, 170, 80, 255],
+        }
+    }
+}
+
+/// Polygons of one preview, in image coordinates (y pointing down)
+struct Frame {
+    glass: Vec<Point2D>,
+    sand: Vec<Vec<Point2D>>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let [_, style_path, output_path] = args.as_slice() else {
+        eprintln!("usage: hourglass-preview <style.ron> <output.svg|output.png>");
+        process::exit(2);
+    };
+
+    if let Err(error) = run(Path::new(style_path), Path::new(output_path)) {
+        eprintln!("hourglass-preview: {error}");
+        process::exit(1);
+    }
+}
+
+fn run(style_path: &Path, output_path: &Path) -> Result<(), Box<dyn Error>> {
+    let style: PreviewStyle = ron::from_str(&fs::read_to_string(style_path)?)?;
+    let (frames, size) = layout(&style);
+
+    match output_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("svg") => fs::write(output_path, svg(&style, &frames, size))?,
+        Some("png") => write_png(output_path, &style, &frames, size)?,
+        _ => return Err("the output must be an .svg or .png file".into()),
+    }
+    Ok(())
+}
+
+/// Glass and sand polygons for every fill level side by side, along with the image size
+fn layout(style: &PreviewStyle) -> (Vec<Frame>, [f32; 2]) {
+    let shape = &style.shape;
+    let glass = shape.generate_outline();
+    let sand_outline = shape.generate_outline_with_wall_offset(style.wall_offset);
+
+    let min_x = glass.iter().map(|point| point[0]).fold(f32::MAX, f32::min);
+    let max_x = glass.iter().map(|point| point[0]).fold(f32::MIN, f32::max);
+    let width = max_x - min_x;
+    let half_height = shape.total_height / 2.0;
+
+    let frames = style
+        .fills
+        .iter()
+        .enumerate()
+        .map(|(index, &fill_percent)| {
+            let origin = [
+                MARGIN + index as f32 * (width + MARGIN) - min_x,
+                MARGIN + half_height,
+            ];
+            let place = |points: &[Point2D]| -> Vec<Point2D> {
+                points
+                    .iter()
+                    .map(|point| [origin[0] + point[0], origin[1] - point[1]])
+                    .collect()
+            };
+
+            let sand = [SandBulb::Top, SandBulb::Bottom]
+                .into_iter()
+                .map(|bulb| {
+                    let params = SandOutlineParams {
+                        fill_percent,
+                        wall_offset: style.wall_offset,
+                        bulb,
+                        neck_height: shape.neck_style.height(),
+                        min_y: -half_height,
+                        max_y: half_height,
+                        tilt: 0.0,
+                        solid_stream: false,
+                        surface_wave: None,
+                        funnel_fill: style.funnel_fill,
+                        robust_geometry: true,
+                    };
+                    place(&generate_sand_outline_with_params(&sand_outline, &params))
+                })
+                .filter(|points| points.len() >= 3)
+                .collect();
+
+            Frame {
+                glass: place(&glass),
+                sand,
+            }
+        })
+        .collect();
+
+    let size = [
+        MARGIN + style.fills.len() as f32 * (width + MARGIN),
+        shape.total_height + 2.0 * MARGIN,
+    ];
+    (frames, size)
+}
+
+/// Preview as an SVG document, with the sand in front of the glass like the plugin draws it
+fn svg(style: &PreviewStyle, frames: &[Frame], size: [f32; 2]) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        size[0].ceil(),
+        size[1].ceil()
+    );
+    for frame in frames {
+        svg += &svg_polygon(&frame.glass, style.glass_color);
+        for sand in &frame.sand {
+            svg += &svg_polygon(sand, style.sand_color);
+        }
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+fn svg_polygon(points: &[Point2D], color: [u8; 4]) -> String {
+    let points: Vec<String> = points
+        .iter()
+        .map(|point| format!("{:.2},{:.2}", point[0], point[1]))
+        .collect();
+    format!(
+        "  <polygon points=\"{}\" fill=\"rgb({},{},{})\" fill-opacity=\"{:.3}\"/>\n",
+        points.join(" "),
+        color[0],
+        color[1],
+        color[2],
+        color[3] as f32 / 255.0
+    )
+}
+
+/// Preview rasterized into a PNG image with a transparent background
+fn write_png(
+    path: &Path,
+    style: &PreviewStyle,
+    frames: &[Frame],
+    size: [f32; 2],
+) -> Result<(), Box<dyn Error>> {
+    let (width, height) = (size[0].ceil() as u32, size[1].ceil() as u32);
+    let mut pixels = vec![0; (width * height * 4) as usize];
+    for frame in frames {
+        fill_polygon(&mut pixels, width, &frame.glass, style.glass_color);
+        for sand in &frame.sand {
+            fill_polygon(&mut pixels, width, sand, style.sand_color);
+        }
+    }
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels)?;
+    Ok(())
+}
+
+/// Blend a polygon over the pixels, sampled at the pixel centers
+fn fill_polygon(pixels: &mut [u8], width: u32, polygon: &[Point2D], color: [u8; 4]) {
+    let height = pixels.len() as u32 / (width * 4);
+    let min = polygon
+        .iter()
+        .fold([f32::MAX; 2], |min, p| [min[0].min(p[0]), min[1].min(p[1])]);
+    let max = polygon
+        .iter()
+        .fold([f32::MIN; 2], |max, p| [max[0].max(p[0]), max[1].max(p[1])]);
+
+    for y in (min[1].max(0.0) as u32)..(max[1].ceil() as u32).min(height) {
+        for x in (min[0].max(0.0) as u32)..(max[0].ceil() as u32).min(width) {
+            if point_in_polygon([x as f32 + 0.5, y as f32 + 0.5], polygon) {
+                let index = ((y * width + x) * 4) as usize;
+                blend(&mut pixels[index..index + 4], color);
+            }
+        }
+    }
+}
+
+/// Composite a color over a pixel, both with straight alpha
+fn blend(pixel: &mut [u8], color: [u8; 4]) {
+    let src_alpha = color[3] as f32 / 255.0;
+    let dst_alpha = pixel[3] as f32 / 255.0 * (1.0 - src_alpha);
+    let alpha = src_alpha + dst_alpha;
+    if alpha <= 0.0 {
+        return;
+    }
+    for channel in 0..3 {
+        pixel[channel] = ((color[channel] as f32 * src_alpha + pixel[channel] as f32 * dst_alpha)
+            / alpha)
+            .round() as u8;
+    }
+    pixel[3] = (alpha * 255.0).round() as u8;
+}
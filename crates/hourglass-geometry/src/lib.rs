@@ -0,0 +1,1860 @@
+//! Composable curve generation system for hourglass shapes.
+//!
+//! Pure geometry on `[f32; 2]` points without any Bevy types, shared by `bevy_hourglass` and
+//! tools that only need the shapes, e.g. previews.
+
+use std::borrow::Cow;
+use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
+
+/// A 2D point
+pub type Point2D = [f32; 2];
+
+/// Distance (on each axis) below which two points are treated as the same point
+pub const POINT_EPSILON: f32 = 1e-4;
+
+/// Area below which a polygon is treated as empty, as it can't be triangulated reliably
+pub const MIN_POLYGON_AREA: f32 = 1e-3;
+
+/// Hash a float consistently with `==`, for hashing styles and configurations
+///
+/// `0.0` and `-0.0` hash alike. NaN never equals anything, so its hash doesn't matter.
+pub fn hash_f32<H: Hasher>(value: f32, state: &mut H) {
+    // Adding zero turns -0.0 into 0.0
+    (value + 0.0).to_bits().hash(state);
+}
+
+/// Key comparing styles and configurations by their [`Hash`] impls, for use in `HashMap`s
+///
+/// Floats compare by their bits (with `-0.0` equal to `0.0`), so unlike the configurations
+/// themselves keys are `Eq`, even for NaN values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConfigKey(Vec<u8>);
+
+impl ConfigKey {
+    /// Key of a style or configuration
+    pub fn new(config: &impl Hash) -> Self {
+        Self::default().with(config)
+    }
+
+    /// Add another value to the key
+    pub fn with(mut self, value: &impl Hash) -> Self {
+        value.hash(&mut KeyWriter(&mut self.0));
+        self
+    }
+
+    /// Add floats to the key, which don't implement [`Hash`] themselves
+    pub fn with_f32s(mut self, values: impl IntoIterator<Item = f32>) -> Self {
+        for value in values {
+            hash_f32(value, &mut KeyWriter(&mut self.0));
+        }
+        self
+    }
+}
+
+/// Hasher recording the bytes written to it, which make up a [`ConfigKey`]
+struct KeyWriter<'a>(&'a mut Vec<u8>);
+
+impl Hasher for KeyWriter<'_> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hasher = std::hash::DefaultHasher::new();
+        hasher.write(self.0);
+        hasher.finish()
+    }
+}
+
+/// Trait for generating curve segments
+pub trait CurveGenerator: Send + Sync {
+    /// Generate points along the curve with the specified resolution
+    fn generate_points(&self, resolution: usize) -> Vec<Point2D>;
+
+    /// Get the start point of the curve
+    fn start_point(&self) -> Point2D;
+
+    /// Get the end point of the curve
+    fn end_point(&self) -> Point2D;
+
+    /// Approximate length of the curve, used to share resolution between curves
+    fn length(&self) -> f32 {
+        polyline_length(&self.generate_points(32))
+    }
+}
+
+/// Total length of the segments joining consecutive points
+pub fn polyline_length(points: &[Point2D]) -> f32 {
+    points
+        .windows(2)
+        .map(|pair| (pair[1][0] - pair[0][0]).hypot(pair[1][1] - pair[0][1]))
+        .sum()
+}
+
+/// Configuration for a circular arc curve
+#[derive(Debug, Clone)]
+pub struct CircularArc {
+    pub center: Point2D,
+    pub radius: f32,
+    pub start_angle: f32, // in radians
+    pub end_angle: f32,   // in radians
+    pub clockwise: bool,
+}
+
+impl CircularArc {
+    /// Create a new circular arc
+    pub fn new(
+        center: Point2D,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        clockwise: bool,
+    ) -> Self {
+        Self {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            clockwise,
+        }
+    }
+
+    /// Create a quarter circle arc (90 degrees)
+    pub fn quarter_circle(center: Point2D, radius: f32, quadrant: CircleQuadrant) -> Self {
+        let (start_angle, end_angle) = match quadrant {
+            CircleQuadrant::TopRight => (0.0, PI / 2.0),
+            CircleQuadrant::TopLeft => (PI / 2.0, PI),
+            CircleQuadrant::BottomLeft => (PI, 3.0 * PI / 2.0),
+            CircleQuadrant::BottomRight => (3.0 * PI / 2.0, 2.0 * PI),
+        };
+
+        Self::new(center, radius, start_angle, end_angle, false)
+    }
+
+    /// Angle swept by the arc in its direction (in radians)
+    fn angle_diff(&self) -> f32 {
+        if self.clockwise {
+            if self.end_angle <= self.start_angle {
+                self.end_angle + 2.0 * PI - self.start_angle
+            } else {
+                self.end_angle - self.start_angle
+            }
+        } else if self.end_angle >= self.start_angle {
+            self.end_angle - self.start_angle
+        } else {
+            self.end_angle + 2.0 * PI - self.start_angle
+        }
+    }
+}
+
+impl CurveGenerator for CircularArc {
+    fn generate_points(&self, resolution: usize) -> Vec<Point2D> {
+        if resolution == 0 {
+            return vec![self.start_point(), self.end_point()];
+        }
+
+        let mut points = Vec::with_capacity(resolution + 1);
+        let angle_diff = self.angle_diff();
+
+        for i in 0..=resolution {
+            let t = i as f32 / resolution as f32;
+            let angle = if self.clockwise {
+                self.start_angle - t * angle_diff
+            } else {
+                self.start_angle + t * angle_diff
+            };
+
+            let x = self.center[0] + self.radius * angle.cos();
+            let y = self.center[1] + self.radius * angle.sin();
+            points.push([x, y]);
+        }
+
+        points
+    }
+
+    fn start_point(&self) -> Point2D {
+        [
+            self.center[0] + self.radius * self.start_angle.cos(),
+            self.center[1] + self.radius * self.start_angle.sin(),
+        ]
+    }
+
+    fn end_point(&self) -> Point2D {
+        [
+            self.center[0] + self.radius * self.end_angle.cos(),
+            self.center[1] + self.radius * self.end_angle.sin(),
+        ]
+    }
+
+    fn length(&self) -> f32 {
+        self.radius.abs() * self.angle_diff()
+    }
+}
+
+/// Quadrants for quarter circle generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircleQuadrant {
+    TopRight,
+    TopLeft,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Configuration for a smooth transition curve between two points
+#[derive(Debug, Clone)]
+pub struct SmoothTransition {
+    pub start: Point2D,
+    pub end: Point2D,
+    pub curvature: f32, // 0.0 = straight line, 1.0 = maximum curve
+    pub curve_direction: CurveDirection,
+}
+
+impl SmoothTransition {
+    /// Create a new smooth transition curve
+    pub fn new(
+        start: Point2D,
+        end: Point2D,
+        curvature: f32,
+        curve_direction: CurveDirection,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            curvature: curvature.max(0.0), // Allow values > 1.0 for more extreme curves
+            curve_direction,
+        }
+    }
+
+    /// Create a straight line (no curvature)
+    pub fn straight_line(start: Point2D, end: Point2D) -> Self {
+        Self::new(start, end, 0.0, CurveDirection::None)
+    }
+}
+
+impl CurveGenerator for SmoothTransition {
+    fn generate_points(&self, resolution: usize) -> Vec<Point2D> {
+        if resolution == 0 {
+            return vec![self.start, self.end];
+        }
+
+        let mut points = Vec::with_capacity(resolution + 1);
+
+        for i in 0..=resolution {
+            let t = i as f32 / resolution as f32;
+
+            if self.curvature == 0.0 {
+                // Straight line interpolation
+                let x = self.start[0] * (1.0 - t) + self.end[0] * t;
+                let y = self.start[1] * (1.0 - t) + self.end[1] * t;
+                points.push([x, y]);
+            } else {
+                // Curved interpolation using sine wave for smooth transitions
+                let curve_offset = match self.curve_direction {
+                    CurveDirection::None => 0.0,
+                    CurveDirection::Inward => -self.curvature * (t * PI).sin(),
+                    CurveDirection::Outward => self.curvature * (t * PI).sin(),
+                };
+
+                // Base linear interpolation
+                let base_x = self.start[0] * (1.0 - t) + self.end[0] * t;
+                let base_y = self.start[1] * (1.0 - t) + self.end[1] * t;
+
+                // Apply curve offset perpendicular to the line direction
+                let dx = self.end[0] - self.start[0];
+                let dy = self.end[1] - self.start[1];
+                let length = (dx * dx + dy * dy).sqrt();
+
+                if length > 0.0 {
+                    // Perpendicular direction (rotated 90 degrees)
+                    let perp_x = -dy / length;
+                    let perp_y = dx / length;
+
+                    let x = base_x + perp_x * curve_offset * length * 0.1; // Scale factor for reasonable curve
+                    let y = base_y + perp_y * curve_offset * length * 0.1;
+                    points.push([x, y]);
+                } else {
+                    points.push([base_x, base_y]);
+                }
+            }
+        }
+
+        points
+    }
+
+    fn start_point(&self) -> Point2D {
+        self.start
+    }
+
+    fn end_point(&self) -> Point2D {
+        self.end
+    }
+}
+
+/// Direction of curve bending
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CurveDirection {
+    None,
+    Inward,
+    Outward,
+}
+
+/// How a [`CompositeCurve`] shares its resolution between its segments
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResolutionDistribution {
+    /// Every segment gets the same resolution
+    #[default]
+    Equal,
+    /// Segments get a resolution proportional to their length, for evenly spaced points
+    ByLength,
+}
+
+/// A composite curve made up of multiple curve segments
+pub struct CompositeCurve {
+    pub segments: Vec<Box<dyn CurveGenerator>>,
+    /// Fixed resolution of each segment, by index, instead of a share of the total
+    pub resolution_overrides: Vec<Option<usize>>,
+    pub distribution: ResolutionDistribution,
+}
+
+impl CompositeCurve {
+    /// Create a new composite curve
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            resolution_overrides: Vec::new(),
+            distribution: ResolutionDistribution::default(),
+        }
+    }
+
+    /// Set how the resolution is shared between the segments
+    pub fn with_distribution(mut self, distribution: ResolutionDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Add a curve segment to the composite
+    pub fn add_segment(mut self, segment: Box<dyn CurveGenerator>) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    /// Add a curve segment always generated with the given resolution, which is taken out of the
+    /// resolution shared by the other segments
+    pub fn add_segment_with_resolution(
+        mut self,
+        segment: Box<dyn CurveGenerator>,
+        resolution: usize,
+    ) -> Self {
+        self.resolution_overrides.resize(self.segments.len(), None);
+        self.resolution_overrides.push(Some(resolution));
+        self.segments.push(segment);
+        self
+    }
+
+    /// Resolution of each segment when generating the whole curve with `resolution`
+    pub fn segment_resolutions(&self, resolution: usize) -> Vec<usize> {
+        let fixed = |i: usize| self.resolution_overrides.get(i).copied().flatten();
+        let shared: Vec<usize> = (0..self.segments.len())
+            .filter(|&i| fixed(i).is_none())
+            .collect();
+        let fixed_total: usize = (0..self.segments.len()).filter_map(fixed).sum();
+        let remaining = resolution.saturating_sub(fixed_total);
+
+        // Measuring segments samples them, so only do it when the lengths are needed
+        let lengths: Vec<f32> = match self.distribution {
+            ResolutionDistribution::Equal => vec![0.0; shared.len()],
+            ResolutionDistribution::ByLength => shared
+                .iter()
+                .map(|&i| self.segments[i].length().max(0.0))
+                .collect(),
+        };
+        let total_length: f32 = lengths.iter().sum();
+
+        let mut resolutions: Vec<usize> = (0..self.segments.len())
+            .map(|i| fixed(i).unwrap_or(0))
+            .collect();
+        for (&i, length) in shared.iter().zip(&lengths) {
+            resolutions[i] = match self.distribution {
+                ResolutionDistribution::ByLength if total_length > 0.0 => {
+                    // Every segment keeps at least one step so it still reaches its end point
+                    ((remaining as f32 * length / total_length).round() as usize).max(1)
+                }
+                _ => remaining / shared.len(),
+            };
+        }
+        resolutions
+    }
+
+    /// Add a circular arc segment
+    pub fn add_arc(self, arc: CircularArc) -> Self {
+        self.add_segment(Box::new(arc))
+    }
+
+    /// Add a smooth transition segment
+    pub fn add_transition(self, transition: SmoothTransition) -> Self {
+        self.add_segment(Box::new(transition))
+    }
+}
+
+impl CurveGenerator for CompositeCurve {
+    fn generate_points(&self, resolution: usize) -> Vec<Point2D> {
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+
+        let mut all_points = Vec::new();
+        let resolutions = self.segment_resolutions(resolution);
+
+        for (i, (segment, segment_resolution)) in self.segments.iter().zip(resolutions).enumerate()
+        {
+            let mut segment_points = segment.generate_points(segment_resolution);
+
+            // Skip the first point of subsequent segments to avoid duplication
+            if i > 0 && !segment_points.is_empty() {
+                segment_points.remove(0);
+            }
+
+            all_points.extend(segment_points);
+        }
+
+        all_points
+    }
+
+    fn start_point(&self) -> Point2D {
+        self.segments
+            .first()
+            .map(|s| s.start_point())
+            .unwrap_or([0.0, 0.0])
+    }
+
+    fn end_point(&self) -> Point2D {
+        self.segments
+            .last()
+            .map(|s| s.end_point())
+            .unwrap_or([0.0, 0.0])
+    }
+
+    fn length(&self) -> f32 {
+        self.segments.iter().map(|segment| segment.length()).sum()
+    }
+}
+
+impl Default for CompositeCurve {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Different styles for hourglass bulbs
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum BulbStyle {
+    /// Circular bulbs with adjustable curvature
+    Circular {
+        curvature: f32,
+        width_factor: f32,
+        curve_resolution: usize,
+    },
+    /// Straight-sided bulbs (triangular shape)
+    Straight { width_factor: f32 },
+}
+
+impl BulbStyle {
+    /// Get the width factor for this bulb style
+    pub fn width_factor(&self) -> f32 {
+        match self {
+            BulbStyle::Circular { width_factor, .. } => *width_factor,
+            BulbStyle::Straight { width_factor } => *width_factor,
+        }
+    }
+
+    /// Get the curve resolution for this bulb style
+    pub fn curve_resolution(&self) -> usize {
+        match self {
+            BulbStyle::Circular {
+                curve_resolution, ..
+            } => *curve_resolution,
+            BulbStyle::Straight { .. } => 2, // Minimal resolution for straight lines
+        }
+    }
+
+    /// Copy of this bulb style with the given curve resolution, straight bulbs being unchanged
+    pub fn with_curve_resolution(&self, resolution: usize) -> Self {
+        match self.clone() {
+            BulbStyle::Circular {
+                curvature,
+                width_factor,
+                ..
+            } => BulbStyle::Circular {
+                curvature,
+                width_factor,
+                curve_resolution: resolution.max(1),
+            },
+            straight => straight,
+        }
+    }
+
+    /// Copy of this bulb style with its curve resolution limited to `max_resolution`
+    pub fn capped_resolution(&self, max_resolution: usize) -> Self {
+        self.with_curve_resolution(self.curve_resolution().min(max_resolution))
+    }
+
+    /// Bulb style at `t` (0.0 - 1.0) between this style and `other`
+    ///
+    /// `t` at or beyond the ends gives a copy of that end. Straight bulbs are blended as circular
+    /// bulbs without curvature, at the other style's curve resolution, so the shape changes
+    /// smoothly even between variants.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        if t <= 0.0 {
+            return self.clone();
+        }
+        if t >= 1.0 {
+            return other.clone();
+        }
+        let as_circular = |style: &Self, fallback_resolution: usize| match *style {
+            BulbStyle::Circular {
+                curvature,
+                width_factor,
+                curve_resolution,
+            } => (curvature, width_factor, curve_resolution),
+            BulbStyle::Straight { width_factor } => (0.0, width_factor, fallback_resolution),
+        };
+        match (self, other) {
+            (BulbStyle::Straight { width_factor: a }, BulbStyle::Straight { width_factor: b }) => {
+                BulbStyle::Straight {
+                    width_factor: lerp(*a, *b, t),
+                }
+            }
+            _ => {
+                let (curvature_a, width_a, resolution_a) =
+                    as_circular(self, other.curve_resolution());
+                let (curvature_b, width_b, resolution_b) = as_circular(other, resolution_a);
+                BulbStyle::Circular {
+                    curvature: lerp(curvature_a, curvature_b, t),
+                    width_factor: lerp(width_a, width_b, t),
+                    curve_resolution: lerp_resolution(resolution_a, resolution_b, t),
+                }
+            }
+        }
+    }
+}
+
+impl Hash for BulbStyle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            BulbStyle::Circular {
+                curvature,
+                width_factor,
+                curve_resolution,
+            } => {
+                hash_f32(curvature, state);
+                hash_f32(width_factor, state);
+                curve_resolution.hash(state);
+            }
+            BulbStyle::Straight { width_factor } => hash_f32(width_factor, state),
+        }
+    }
+}
+
+impl Default for BulbStyle {
+    fn default() -> Self {
+        Self::Circular {
+            curvature: 1.0,
+            width_factor: 0.75,
+            curve_resolution: 20,
+        }
+    }
+}
+
+/// Different styles for hourglass necks
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum NeckStyle {
+    /// Straight neck
+    Straight { width: f32, height: f32 },
+    /// Curved neck that bends inward
+    Curved {
+        curvature: f32,
+        width: f32,
+        height: f32,
+        curve_resolution: usize,
+    },
+}
+
+impl NeckStyle {
+    /// Get the width for this neck style
+    pub fn width(&self) -> f32 {
+        match self {
+            NeckStyle::Straight { width, .. } => width.max(3.0),
+            NeckStyle::Curved { width, .. } => width.max(3.0),
+        }
+    }
+
+    /// Copy of this neck style with its width and height multiplied by `factor`
+    pub fn scaled(&self, factor: f32) -> Self {
+        match self.clone() {
+            NeckStyle::Straight { width, height } => NeckStyle::Straight {
+                width: width * factor,
+                height: height * factor,
+            },
+            NeckStyle::Curved {
+                curvature,
+                width,
+                height,
+                curve_resolution,
+            } => NeckStyle::Curved {
+                curvature,
+                width: width * factor,
+                height: height * factor,
+                curve_resolution,
+            },
+        }
+    }
+
+    /// Get the width for this neck style with wall offset constraint
+    /// Ensures neck width is always at least 2 * wall_offset + minimum_gap
+    pub fn width_with_wall_offset(&self, wall_offset: f32) -> f32 {
+        let minimum_gap = 2.0; // Minimum gap between sand on left and right sides of neck
+        let minimum_width = 2.0 * wall_offset + minimum_gap;
+        self.width().max(minimum_width)
+    }
+
+    /// Get the height for this neck style
+    pub fn height(&self) -> f32 {
+        match self {
+            NeckStyle::Straight { height, .. } => *height,
+            NeckStyle::Curved { height, .. } => *height,
+        }
+    }
+
+    /// Get the curve resolution for this neck style
+    pub fn curve_resolution(&self) -> usize {
+        match self {
+            NeckStyle::Straight { .. } => 2, // Minimal resolution for straight lines
+            NeckStyle::Curved {
+                curve_resolution, ..
+            } => *curve_resolution,
+        }
+    }
+
+    /// Copy of this neck style with the given curve resolution, straight necks being unchanged
+    pub fn with_curve_resolution(&self, resolution: usize) -> Self {
+        match self.clone() {
+            NeckStyle::Curved {
+                curvature,
+                width,
+                height,
+                ..
+            } => NeckStyle::Curved {
+                curvature,
+                width,
+                height,
+                curve_resolution: resolution.max(1),
+            },
+            straight => straight,
+        }
+    }
+
+    /// Copy of this neck style with its curve resolution limited to `max_resolution`
+    pub fn capped_resolution(&self, max_resolution: usize) -> Self {
+        self.with_curve_resolution(self.curve_resolution().min(max_resolution))
+    }
+
+    /// Neck style at `t` (0.0 - 1.0) between this style and `other`
+    ///
+    /// `t` at or beyond the ends gives a copy of that end. Straight necks are blended as curved
+    /// necks without curvature, at the other style's curve resolution, so the shape changes
+    /// smoothly even between variants.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        if t <= 0.0 {
+            return self.clone();
+        }
+        if t >= 1.0 {
+            return other.clone();
+        }
+        let as_curved = |style: &Self, fallback_resolution: usize| match *style {
+            NeckStyle::Curved {
+                curvature,
+                width,
+                height,
+                curve_resolution,
+            } => (curvature, width, height, curve_resolution),
+            NeckStyle::Straight { width, height } => (0.0, width, height, fallback_resolution),
+        };
+        match (self, other) {
+            (
+                NeckStyle::Straight {
+                    width: width_a,
+                    height: height_a,
+                },
+                NeckStyle::Straight {
+                    width: width_b,
+                    height: height_b,
+                },
+            ) => NeckStyle::Straight {
+                width: lerp(*width_a, *width_b, t),
+                height: lerp(*height_a, *height_b, t),
+            },
+            _ => {
+                let (curvature_a, width_a, height_a, resolution_a) =
+                    as_curved(self, other.curve_resolution());
+                let (curvature_b, width_b, height_b, resolution_b) = as_curved(other, resolution_a);
+                NeckStyle::Curved {
+                    curvature: lerp(curvature_a, curvature_b, t),
+                    width: lerp(width_a, width_b, t),
+                    height: lerp(height_a, height_b, t),
+                    curve_resolution: lerp_resolution(resolution_a, resolution_b, t),
+                }
+            }
+        }
+    }
+}
+
+/// Value at `t` between `a` and `b`
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Curve resolution at `t` between `a` and `b`, rounded and at least 1
+fn lerp_resolution(a: usize, b: usize, t: f32) -> usize {
+    (lerp(a as f32, b as f32, t).round() as usize).max(1)
+}
+
+impl Hash for NeckStyle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            NeckStyle::Straight { width, height } => {
+                hash_f32(width, state);
+                hash_f32(height, state);
+            }
+            NeckStyle::Curved {
+                curvature,
+                width,
+                height,
+                curve_resolution,
+            } => {
+                hash_f32(curvature, state);
+                hash_f32(width, state);
+                hash_f32(height, state);
+                curve_resolution.hash(state);
+            }
+        }
+    }
+}
+
+impl Default for NeckStyle {
+    fn default() -> Self {
+        Self::Curved {
+            curvature: 0.2,
+            width: 12.0,
+            height: 8.0,
+            curve_resolution: 5,
+        }
+    }
+}
+
+/// Builder for creating hourglass shape outlines using curves
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(default))]
+pub struct HourglassShapeBuilder {
+    pub total_height: f32,
+    pub bulb_style: BulbStyle,
+    pub neck_style: NeckStyle,
+}
+
+impl Hash for HourglassShapeBuilder {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_f32(self.total_height, state);
+        self.bulb_style.hash(state);
+        self.neck_style.hash(state);
+    }
+}
+
+impl HourglassShapeBuilder {
+    /// Create a new hourglass shape builder
+    pub fn new() -> Self {
+        Self {
+            total_height: 200.0,
+            bulb_style: BulbStyle::default(),
+            neck_style: NeckStyle::default(),
+        }
+    }
+
+    /// Set the bulb style
+    pub fn with_bulb_style(mut self, style: BulbStyle) -> Self {
+        self.bulb_style = style;
+        self
+    }
+
+    /// Set the neck style
+    pub fn with_neck_style(mut self, style: NeckStyle) -> Self {
+        self.neck_style = style;
+        self
+    }
+
+    /// Generate the complete hourglass outline
+    pub fn generate_outline(&self) -> Vec<Point2D> {
+        self.generate_outline_with_wall_offset(0.0)
+    }
+
+    /// Generate the complete hourglass outline with vertices about `spacing` apart
+    ///
+    /// See [`resample_by_arc_length`], the corners of the shape are kept.
+    pub fn generate_outline_with_spacing(&self, spacing: f32) -> Vec<Point2D> {
+        resample_by_arc_length(&self.generate_outline(), spacing)
+    }
+
+    /// Generate the complete hourglass outline with wall offset constraint for sand generation
+    pub fn generate_outline_with_wall_offset(&self, wall_offset: f32) -> Vec<Point2D> {
+        let half_height = self.total_height / 2.0;
+        let neck_width = if wall_offset > 0.0 {
+            self.neck_style.width_with_wall_offset(wall_offset)
+        } else {
+            self.neck_style.width()
+        };
+        let neck_half_width = neck_width / 2.0;
+        let neck_half_height = self.neck_style.height() / 2.0;
+
+        // Calculate bulb dimensions based on total_height as the authoritative dimension
+        let bulb_height = (self.total_height - self.neck_style.height()) / 2.0;
+        let bulb_width = bulb_height * self.bulb_style.width_factor();
+
+        let mut outline = Vec::new();
+
+        // Bottom bulb (left side, bottom to neck)
+        let bottom_bulb_left = self.create_bulb_curve(
+            [-bulb_width, -half_height],
+            [-neck_half_width, -neck_half_height],
+            BulbSection::BottomLeft,
+        );
+        outline.extend(bottom_bulb_left.generate_points(self.bulb_style.curve_resolution()));
+
+        // Left neck curve (bottom to top)
+        let left_neck = self.create_neck_curve(
+            [-neck_half_width, -neck_half_height],
+            [-neck_half_width, neck_half_height],
+            NeckSection::Left,
+        );
+        let mut left_neck_points = left_neck.generate_points(self.neck_style.curve_resolution());
+        if !left_neck_points.is_empty() {
+            left_neck_points.remove(0); // Remove duplicate point
+        }
+        outline.extend(left_neck_points);
+
+        // Top bulb (left side, neck to top)
+        let top_bulb_left = self.create_bulb_curve(
+            [-neck_half_width, neck_half_height],
+            [-bulb_width, half_height],
+            BulbSection::TopLeft,
+        );
+        let mut top_bulb_left_points =
+            top_bulb_left.generate_points(self.bulb_style.curve_resolution());
+        if !top_bulb_left_points.is_empty() {
+            top_bulb_left_points.remove(0);
+        }
+        outline.extend(top_bulb_left_points);
+
+        // TODO: Allow for curved top cap
+        // Top cap
+        outline.push([bulb_width, half_height]);
+
+        // Top bulb (right side, top to neck)
+        let top_bulb_right = self.create_bulb_curve(
+            [bulb_width, half_height],
+            [neck_half_width, neck_half_height],
+            BulbSection::TopRight,
+        );
+        let mut top_bulb_right_points =
+            top_bulb_right.generate_points(self.bulb_style.curve_resolution());
+        if !top_bulb_right_points.is_empty() {
+            top_bulb_right_points.remove(0);
+        }
+        outline.extend(top_bulb_right_points);
+
+        // Right neck curve (top to bottom)
+        let right_neck = self.create_neck_curve(
+            [neck_half_width, neck_half_height],
+            [neck_half_width, -neck_half_height],
+            NeckSection::Right,
+        );
+        let mut right_neck_points = right_neck.generate_points(self.neck_style.curve_resolution());
+        if !right_neck_points.is_empty() {
+            right_neck_points.remove(0);
+        }
+        outline.extend(right_neck_points);
+
+        // Bottom bulb (right side, neck to bottom)
+        let bottom_bulb_right = self.create_bulb_curve(
+            [neck_half_width, -neck_half_height],
+            [bulb_width, -half_height],
+            BulbSection::BottomRight,
+        );
+        let mut bottom_bulb_right_points =
+            bottom_bulb_right.generate_points(self.bulb_style.curve_resolution());
+        if !bottom_bulb_right_points.is_empty() {
+            bottom_bulb_right_points.remove(0);
+        }
+        outline.extend(bottom_bulb_right_points);
+
+        // TODO: Allow for curved bottom cap
+        ensure_ccw(outline)
+    }
+
+    /// Create a bulb curve based on the bulb style
+    fn create_bulb_curve(
+        &self,
+        start: Point2D,
+        end: Point2D,
+        section: BulbSection,
+    ) -> Box<dyn CurveGenerator> {
+        match &self.bulb_style {
+            BulbStyle::Circular { curvature, .. } => {
+                let curve_direction = match section {
+                    BulbSection::BottomLeft | BulbSection::TopLeft => CurveDirection::Outward,
+                    BulbSection::BottomRight | BulbSection::TopRight => CurveDirection::Outward,
+                };
+
+                Box::new(SmoothTransition::new(
+                    start,
+                    end,
+                    *curvature,
+                    curve_direction,
+                ))
+            }
+            BulbStyle::Straight { .. } => Box::new(SmoothTransition::straight_line(start, end)),
+        }
+    }
+
+    /// Create a neck curve based on the neck style
+    fn create_neck_curve(
+        &self,
+        start: Point2D,
+        end: Point2D,
+        _section: NeckSection,
+    ) -> Box<dyn CurveGenerator> {
+        match &self.neck_style {
+            NeckStyle::Straight { .. } => Box::new(SmoothTransition::straight_line(start, end)),
+            NeckStyle::Curved { curvature, .. } => Box::new(SmoothTransition::new(
+                start,
+                end,
+                *curvature,
+                CurveDirection::Inward,
+            )),
+        }
+    }
+}
+
+impl HourglassShapeBuilder {
+    /// Corners of the outline generated with the given wall offset, if both the bulbs and the
+    /// neck are straight
+    pub fn straight_profile(&self, wall_offset: f32) -> Option<StraightProfile> {
+        let (BulbStyle::Straight { width_factor }, NeckStyle::Straight { height, .. }) =
+            (&self.bulb_style, &self.neck_style)
+        else {
+            return None;
+        };
+        let neck_width = if wall_offset > 0.0 {
+            self.neck_style.width_with_wall_offset(wall_offset)
+        } else {
+            self.neck_style.width()
+        };
+        let bulb_height = (self.total_height - height) / 2.0;
+
+        Some(StraightProfile {
+            half_height: self.total_height / 2.0,
+            bulb_half_width: bulb_height * width_factor,
+            neck_half_height: height / 2.0,
+            neck_half_width: neck_width / 2.0,
+        })
+    }
+
+    /// Generate the sand for the given parameters inside this shape
+    ///
+    /// `hourglass_outline` must come from `generate_outline_with_wall_offset` with the same wall
+    /// offset. Upright straight-sided hourglasses skip it and compute the sand analytically, with
+    /// the same result as [`generate_sand_outline_with_params`].
+    pub fn generate_sand_outline(
+        &self,
+        hourglass_outline: &[Point2D],
+        params: &SandOutlineParams,
+    ) -> Vec<Point2D> {
+        match self.straight_profile(params.wall_offset) {
+            Some(profile) if params.tilt.abs() <= f32::EPSILON => {
+                let fill_line = sand_fill_line(
+                    params.fill_percent,
+                    params.bulb,
+                    params.neck_height,
+                    params.min_y,
+                    params.max_y,
+                );
+                let upright = |fill_line: f32| profile.fill_points(fill_line, params.bulb);
+                finish_sand_outline(upright(fill_line), fill_line, params, Some(&upright))
+            }
+            _ => generate_sand_outline_with_params(hourglass_outline, params),
+        }
+    }
+}
+
+impl Default for HourglassShapeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Corners of a straight-sided hourglass, whose sand is made of trapezoids
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StraightProfile {
+    pub half_height: f32,
+    /// Half width of the bulbs at the top and bottom of the glass
+    pub bulb_half_width: f32,
+    pub neck_half_height: f32,
+    pub neck_half_width: f32,
+}
+
+impl StraightProfile {
+    /// Half width of the glass at the given height
+    pub fn half_width_at(&self, y: f32) -> f32 {
+        let y = y.abs().min(self.half_height);
+        if y <= self.neck_half_height {
+            return self.neck_half_width;
+        }
+        let t = (y - self.neck_half_height) / (self.half_height - self.neck_half_height);
+        self.neck_half_width + t * (self.bulb_half_width - self.neck_half_width)
+    }
+
+    /// Points of the sand below an upright fill line, before they are inset from the walls
+    ///
+    /// Follows the same order as the general outline intersection, from the left wall to the
+    /// right one.
+    fn fill_points(&self, fill_line: f32, bulb: SandBulb) -> Vec<Point2D> {
+        match bulb {
+            SandBulb::Top => {
+                let fill_line = fill_line.clamp(0.0, self.half_height);
+                let neck_top = fill_line.min(self.neck_half_height);
+                let mut left = vec![
+                    [-self.neck_half_width, 0.0],
+                    [-self.neck_half_width, neck_top],
+                ];
+                if fill_line > self.neck_half_height {
+                    left.push([-self.half_width_at(fill_line), fill_line]);
+                }
+                let right = left.iter().rev().map(|point| [-point[0], point[1]]);
+                let mut points = left.clone();
+                points.extend(right);
+                points
+            }
+            SandBulb::Bottom => {
+                let fill_line = fill_line.clamp(-self.half_height, 0.0);
+                let surface_half_width = self.half_width_at(fill_line);
+                vec![
+                    [-self.bulb_half_width, -self.half_height],
+                    [-surface_half_width, fill_line],
+                    [surface_half_width, fill_line],
+                    [self.bulb_half_width, -self.half_height],
+                ]
+            }
+        }
+    }
+}
+
+/// Sections of the bulb for curve generation
+#[derive(Debug, Clone, Copy)]
+enum BulbSection {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Sections of the neck for curve generation
+#[derive(Debug, Clone, Copy)]
+enum NeckSection {
+    Left,
+    Right,
+}
+
+/// Parameters describing the sand to generate inside an hourglass outline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SandOutlineParams {
+    /// How full the top bulb is (0.0 to 1.0)
+    pub fill_percent: f32,
+    /// Distance from the glass walls
+    pub wall_offset: f32,
+    /// Which bulb to generate sand for
+    pub bulb: SandBulb,
+    /// Height of the hourglass neck
+    pub neck_height: f32,
+    /// Bottom of the glass
+    pub min_y: f32,
+    /// Top of the glass
+    pub max_y: f32,
+    /// Rotation of the hourglass relative to gravity in radians (counter-clockwise)
+    ///
+    /// Sand surfaces stay level with gravity while keeping the same amount of sand.
+    pub tilt: f32,
+    /// Whether the top bulb outline includes the solid stream of falling sand
+    pub solid_stream: bool,
+    /// Wave applied to the surface, e.g. for liquids (only applied while upright)
+    pub surface_wave: Option<SurfaceWave>,
+    /// Top bulb fill below which the surface sinks into a funnel draining through the neck, so
+    /// the last of the sand tapers away instead of thinning into a flat sliver (only applied
+    /// while upright and without a surface wave, 0.0 keeps the surface flat)
+    pub funnel_fill: f32,
+    /// Clean the outline with [`clean_polygon`], slower but safe against degenerate edges
+    pub robust_geometry: bool,
+}
+
+/// Sine wave running along the surface of the contents of a bulb
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceWave {
+    /// Height of the wave crests
+    pub amplitude: f32,
+    /// Distance between wave crests
+    pub wavelength: f32,
+    /// Phase offset of the wave in radians
+    pub phase: f32,
+}
+
+/// Generate sand shape points using the same curve system with smooth fill line interpolation
+pub fn generate_sand_outline(
+    hourglass_outline: &[Point2D],
+    fill_percent: f32,
+    wall_offset: f32,
+    bulb: SandBulb,
+    neck_height: f32,
+    min_y: f32,
+    max_y: f32,
+) -> Vec<Point2D> {
+    generate_sand_outline_with_params(
+        hourglass_outline,
+        &SandOutlineParams {
+            fill_percent,
+            wall_offset,
+            bulb,
+            neck_height,
+            min_y,
+            max_y,
+            tilt: 0.0,
+            solid_stream: true,
+            surface_wave: None,
+            funnel_fill: 0.0,
+            robust_geometry: false,
+        },
+    )
+}
+
+/// Generate sand shape points for the given parameters, keeping sand surfaces level when tilted
+pub fn generate_sand_outline_with_params(
+    hourglass_outline: &[Point2D],
+    params: &SandOutlineParams,
+) -> Vec<Point2D> {
+    let SandOutlineParams {
+        fill_percent,
+        bulb,
+        neck_height,
+        min_y,
+        max_y,
+        tilt,
+        ..
+    } = *params;
+
+    if hourglass_outline.is_empty() {
+        return Vec::new();
+    }
+
+    // The sand is traced clockwise from the bottom left, the reverse of the shape builder outlines
+    let hourglass_outline = &*clockwise(hourglass_outline);
+
+    // The hourglass is always centered at y=0
+    let center_y = 0.0;
+
+    // Calculate fill line based on which bulb and fill percentage
+    let fill_line = sand_fill_line(fill_percent, bulb, neck_height, min_y, max_y);
+
+    // Generate points with smooth fill line interpolation
+    if tilt.abs() <= f32::EPSILON {
+        let upright = |fill_line: f32| {
+            generate_outline_with_fill_line(
+                hourglass_outline,
+                fill_line,
+                [0.0, 1.0],
+                bulb,
+                center_y,
+            )
+        };
+        finish_sand_outline(upright(fill_line), fill_line, params, Some(&upright))
+    } else {
+        let filtered_points = generate_tilted_outline_with_fill_line(
+            hourglass_outline,
+            fill_line,
+            fill_percent,
+            tilt,
+            bulb,
+            center_y,
+        );
+        finish_sand_outline(filtered_points, fill_line, params, None)
+    }
+}
+
+/// Inset raw sand points from the walls and add the funnel, surface wave and falling stream
+///
+/// `upright` generates the raw points for other upright fill lines, used to shape the funnel.
+fn finish_sand_outline(
+    filtered_points: Vec<Point2D>,
+    fill_line: f32,
+    params: &SandOutlineParams,
+    upright: Option<&dyn Fn(f32) -> Vec<Point2D>>,
+) -> Vec<Point2D> {
+    let SandOutlineParams {
+        fill_percent,
+        wall_offset,
+        bulb,
+        neck_height,
+        min_y,
+        max_y,
+        tilt,
+        solid_stream,
+        surface_wave,
+        funnel_fill,
+        robust_geometry,
+    } = *params;
+    let center_y = 0.0;
+    let neck_region_height = neck_height / 2.0; // Check points within half the neck height of center
+
+    if filtered_points.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sand_points = inset_from_walls(filtered_points, wall_offset, neck_height);
+
+    // Hollow out the last of the top sand, as long as its surface is still above the neck
+    if let Some(upright) = upright {
+        if matches!(bulb, SandBulb::Top)
+            && surface_wave.is_none()
+            && fill_percent > 0.0
+            && fill_percent < funnel_fill
+            && fill_line > neck_region_height
+        {
+            let depth = 1.0 - fill_percent / funnel_fill;
+            sand_points = apply_funnel(
+                &sand_points,
+                fill_line,
+                depth,
+                neck_region_height,
+                max_y,
+                |h| inset_from_walls(upright(h), wall_offset, neck_height),
+            );
+        }
+    }
+
+    // Ripple the surface, leaving it flat when it is pressed against the top of the glass
+    if let Some(wave) = surface_wave {
+        if tilt.abs() <= f32::EPSILON && fill_line < max_y {
+            sand_points = apply_surface_wave(&sand_points, fill_line, &wave);
+        }
+    }
+
+    // For top bulb, add falling sand stream from neck to bottom only when sand is still flowing
+    // fill_percent > 0.0 means there's still sand in the top bulb and it's flowing
+    if matches!(bulb, SandBulb::Top)
+        && solid_stream
+        && !sand_points.is_empty()
+        && fill_percent > 0.0
+    {
+        // The last point should be the right neck point
+        // The first point should be the left neck point
+        let left_neck = *sand_points.first().unwrap();
+        let right_neck = *sand_points.last().unwrap();
+
+        // When tilted, sand may pool against a wall without reaching the neck
+        let reaches_neck = [left_neck, right_neck]
+            .iter()
+            .all(|point| (point[1] - center_y).abs() <= neck_region_height);
+
+        if reaches_neck {
+            // Add points extending from neck to the end of the glass below it, falling along gravity
+            let down = [-tilt.sin(), -tilt.cos()];
+            let fall = |point: Point2D| {
+                let distance = if down[1] <= 0.0 {
+                    (point[1] - min_y) / (-down[1]).max(0.3)
+                } else {
+                    (max_y - point[1]) / down[1].max(0.3)
+                };
+                [point[0] + down[0] * distance, point[1] + down[1] * distance]
+            };
+            sand_points.push(fall(right_neck));
+            sand_points.push(fall(left_neck));
+        }
+    }
+
+    if robust_geometry {
+        return ensure_ccw(clean_polygon(sand_points));
+    }
+
+    // Slivers without area can't be triangulated, treat them as no sand at all
+    if sand_points.len() < 3 || polygon_area(&sand_points) < MIN_POLYGON_AREA {
+        return Vec::new();
+    }
+    ensure_ccw(sand_points)
+}
+
+/// Move sand points inward from the glass walls, without crossing the center in the neck
+fn inset_from_walls(points: Vec<Point2D>, wall_offset: f32, neck_height: f32) -> Vec<Point2D> {
+    let center_y = 0.0;
+    let neck_region_height = neck_height / 2.0; // Check points within half the neck height of center
+    let mut sand_points = Vec::with_capacity(points.len());
+
+    // Apply offsetting with special handling at the neck
+    for point in points {
+        let mut offset_to_use = wall_offset;
+
+        // Check if this point is in the neck region
+        if (point[1] - center_y).abs() <= neck_region_height {
+            // For neck region points, ensure we don't cross the center
+            // Calculate what the offset point would be
+            let potential_offset_x = if point[0] >= 0.0 {
+                point[0] - wall_offset
+            } else {
+                point[0] + wall_offset
+            };
+
+            // Check if this would cross the center line (with 1 pixel minimum gap)
+            if point[0] >= 0.0 && potential_offset_x <= 0.5 {
+                // Right side would cross to left - limit offset
+                offset_to_use = (point[0] - 0.5).max(0.0);
+            } else if point[0] < 0.0 && potential_offset_x >= -0.5 {
+                // Left side would cross to right - limit offset
+                offset_to_use = (-point[0] - 0.5).max(0.0);
+            }
+        }
+
+        let offset_point = if point[0] >= 0.0 {
+            // Right side of hourglass - move left (inward)
+            [point[0] - offset_to_use, point[1]]
+        } else {
+            // Left side of hourglass - move right (inward)
+            [point[0] + offset_to_use, point[1]]
+        };
+        sand_points.push(offset_point);
+    }
+
+    sand_points
+}
+
+/// Sink the flat surface of nearly drained top sand into a funnel sloping down to the neck
+///
+/// `depth` goes from 0.0 (flat) to 1.0 (reaching the top of the neck). The surface is raised
+/// along the walls until the sand covers the same area as with the flat surface.
+fn apply_funnel(
+    flat: &[Point2D],
+    fill_line: f32,
+    depth: f32,
+    neck_top: f32,
+    max_y: f32,
+    inset_at: impl Fn(f32) -> Vec<Point2D>,
+) -> Vec<Point2D> {
+    let target_area = polygon_area(flat);
+    let funnel_at = |surface: f32| {
+        let apex = surface + (neck_top - surface) * depth;
+        let points = inset_at(surface);
+        let on_surface = |point: &Point2D| (point[1] - surface).abs() <= 1e-3;
+
+        let mut result = Vec::with_capacity(points.len() + 1);
+        for (i, &start) in points.iter().enumerate() {
+            result.push(start);
+            let end = points[(i + 1) % points.len()];
+            if on_surface(&start) && on_surface(&end) && (end[0] - start[0]).abs() > f32::EPSILON {
+                result.push([(start[0] + end[0]) / 2.0, apex]);
+            }
+        }
+        result
+    };
+
+    // Find the height of the surface along the walls where the area matches the flat sand.
+    // The area grows smoothly with the height, so regula falsi (Illinois variant) gets there
+    // in a handful of outlines where bisection needs dozens.
+    let tolerance = (target_area * 1e-4).max(1e-2);
+    let (mut low, mut high) = (fill_line, max_y);
+    let lowest = funnel_at(low);
+    let mut low_error = polygon_area(&lowest) - target_area;
+    if low_error >= 0.0 {
+        return lowest;
+    }
+    let mut best = funnel_at(high);
+    let mut high_error = polygon_area(&best) - target_area;
+    if high_error <= 0.0 {
+        return best;
+    }
+
+    let mut last_side = 0;
+    for _ in 0..16 {
+        let mid = (low * high_error - high * low_error) / (high_error - low_error);
+        let points = funnel_at(mid);
+        let error = polygon_area(&points) - target_area;
+        if error.abs() <= tolerance {
+            return points;
+        }
+        if error < 0.0 {
+            (low, low_error) = (mid, error);
+            if last_side < 0 {
+                high_error /= 2.0;
+            }
+            last_side = -1;
+        } else {
+            (high, high_error) = (mid, error);
+            best = points;
+            if last_side > 0 {
+                low_error /= 2.0;
+            }
+            last_side = 1;
+        }
+    }
+
+    best
+}
+
+/// Replace flat surface segments at `fill_line` with a wave that fades out towards the walls
+fn apply_surface_wave(points: &[Point2D], fill_line: f32, wave: &SurfaceWave) -> Vec<Point2D> {
+    let on_surface = |point: &Point2D| (point[1] - fill_line).abs() <= 1e-3;
+    let mut result = Vec::with_capacity(points.len());
+
+    for (i, &start) in points.iter().enumerate() {
+        result.push(start);
+
+        let end = points[(i + 1) % points.len()];
+        let width = end[0] - start[0];
+        if !on_surface(&start) || !on_surface(&end) || width.abs() <= f32::EPSILON {
+            continue;
+        }
+
+        let steps = ((width.abs() / (wave.wavelength / 8.0).max(1.0)).ceil() as usize).clamp(2, 64);
+        for step in 1..steps {
+            let t = step as f32 / steps as f32;
+            let x = start[0] + width * t;
+            let height = wave.amplitude
+                * (x * 2.0 * PI / wave.wavelength.max(f32::EPSILON) + wave.phase).sin()
+                * (t * PI).sin();
+            result.push([x, fill_line + height]);
+        }
+    }
+
+    result
+}
+
+/// Calculate the height of the sand surface in the given bulb for a top bulb fill percentage
+pub fn sand_fill_line(
+    fill_percent: f32,
+    bulb: SandBulb,
+    neck_height: f32,
+    min_y: f32,
+    max_y: f32,
+) -> f32 {
+    // The hourglass is always centered at y=0
+    let center_y = 0.0;
+
+    // Calculate neck boundaries to prevent bottom sand from entering neck area
+    let neck_bottom = center_y - (neck_height / 2.0);
+
+    match bulb {
+        SandBulb::Top => {
+            // For top bulb: fill_percent represents how much of the top bulb is filled
+            // 1.0 = full top bulb (fill_line at max_y), 0.0 = empty top bulb (fill_line at center_y)
+            center_y + (fill_percent * (max_y - center_y))
+        }
+        SandBulb::Bottom => {
+            // For bottom bulb: fill based on how much sand has drained from top (1.0 - fill_percent)
+            // When fill_percent = 0.0 (empty top), bottom should be full (fill_line at neck_bottom)
+            // When fill_percent = 1.0 (full top), bottom should be empty (fill_line at min_y)
+            // IMPORTANT: Bottom sand should never go above neck_bottom to prevent entering neck area
+            let max_bottom_fill = neck_bottom;
+            min_y + ((1.0 - fill_percent) * (max_bottom_fill - min_y))
+        }
+    }
+}
+
+/// Generate the outline of sand in a tilted hourglass, keeping the surface level with gravity
+///
+/// The surface height is searched so the sand covers the same area as it would upright.
+fn generate_tilted_outline_with_fill_line(
+    hourglass_outline: &[Point2D],
+    fill_line: f32,
+    fill_percent: f32,
+    tilt: f32,
+    bulb: SandBulb,
+    center_y: f32,
+) -> Vec<Point2D> {
+    // A tilted surface always touches some of the bulb, so handle the empty cases explicitly
+    let empty = match bulb {
+        SandBulb::Top => fill_percent <= 0.0,
+        SandBulb::Bottom => fill_percent >= 1.0,
+    };
+    if empty {
+        return Vec::new();
+    }
+
+    let upright =
+        generate_outline_with_fill_line(hourglass_outline, fill_line, [0.0, 1.0], bulb, center_y);
+    let target_area = polygon_area(&upright);
+    if target_area <= 0.0 {
+        return upright;
+    }
+
+    // World up expressed in the hourglass' local frame
+    let up = [tilt.sin(), tilt.cos()];
+    let heights = hourglass_outline
+        .iter()
+        .filter(|point| match bulb {
+            SandBulb::Top => point[1] >= center_y,
+            SandBulb::Bottom => point[1] <= center_y,
+        })
+        .map(|point| dot(*point, up));
+    let (mut low, mut high) = heights.fold((f32::MAX, f32::MIN), |(low, high), height| {
+        (low.min(height), high.max(height))
+    });
+
+    // Bisect the surface height until the area matches the upright sand
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        let points = generate_outline_with_fill_line(hourglass_outline, mid, up, bulb, center_y);
+        if polygon_area(&points) < target_area {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    generate_outline_with_fill_line(hourglass_outline, high, up, bulb, center_y)
+}
+
+/// Generate outline points with smooth fill line interpolation
+///
+/// The fill line is perpendicular to `up`, at a height of `fill_line` along it.
+fn generate_outline_with_fill_line(
+    hourglass_outline: &[Point2D],
+    fill_line: f32,
+    up: Point2D,
+    bulb: SandBulb,
+    center_y: f32,
+) -> Vec<Point2D> {
+    let mut result_points = Vec::new();
+    let mut fill_line_intersections = Vec::new();
+
+    // Process each segment of the outline
+    for i in 0..hourglass_outline.len() {
+        let current_point = hourglass_outline[i];
+        let next_point = hourglass_outline[(i + 1) % hourglass_outline.len()];
+        let current_height = dot(current_point, up);
+        let next_height = dot(next_point, up);
+
+        // Check if current point should be included based on bulb and fill level
+        let current_included = match bulb {
+            SandBulb::Top => current_point[1] >= center_y && current_height <= fill_line,
+            SandBulb::Bottom => current_point[1] <= center_y && current_height <= fill_line,
+        };
+
+        let next_included = match bulb {
+            SandBulb::Top => next_point[1] >= center_y && next_height <= fill_line,
+            SandBulb::Bottom => next_point[1] <= center_y && next_height <= fill_line,
+        };
+
+        // Add current point if it should be included
+        if current_included {
+            result_points.push(current_point);
+        }
+
+        // Check if the segment crosses the fill line
+        let segment_crosses_fill_line = (current_height <= fill_line && next_height > fill_line)
+            || (current_height > fill_line && next_height <= fill_line);
+
+        if segment_crosses_fill_line {
+            // Calculate intersection point with fill line
+            if let Some(intersection) =
+                calculate_line_intersection(current_point, next_point, fill_line, up)
+            {
+                // Check if this intersection should be included based on bulb type
+                let intersection_valid = match bulb {
+                    SandBulb::Top => intersection[1] >= center_y,
+                    SandBulb::Bottom => intersection[1] <= center_y,
+                };
+
+                if intersection_valid {
+                    // Store intersection for later addition
+                    fill_line_intersections.push(intersection);
+
+                    // Add intersection point if transitioning between included/not-included states
+                    if current_included != next_included {
+                        result_points.push(intersection);
+                    }
+                }
+            }
+        }
+    }
+
+    // Add fill line intersections to close the shape for partial fills
+    if !fill_line_intersections.is_empty() {
+        // Sort intersections along the fill line (by x-coordinate when upright)
+        let along = [up[1], -up[0]];
+        fill_line_intersections
+            .sort_by(|a, b| dot(*a, along).partial_cmp(&dot(*b, along)).unwrap());
+
+        // Add intersections in the appropriate order based on bulb type
+        match bulb {
+            SandBulb::Top => {
+                // For top bulb, add intersections in reverse order to close the shape properly
+                for intersection in fill_line_intersections.iter().rev() {
+                    if !contains_point(&result_points, *intersection) {
+                        result_points.push(*intersection);
+                    }
+                }
+            }
+            SandBulb::Bottom => {
+                // For bottom bulb, add intersections in forward order
+                for intersection in fill_line_intersections.iter() {
+                    if !contains_point(&result_points, *intersection) {
+                        result_points.push(*intersection);
+                    }
+                }
+            }
+        }
+    }
+
+    result_points
+}
+
+/// Whether a point is within [`POINT_EPSILON`] of any of the points
+fn contains_point(points: &[Point2D], point: Point2D) -> bool {
+    points.iter().any(|other| points_coincide(*other, point))
+}
+
+/// Whether two points are within [`POINT_EPSILON`] of each other on both axes
+fn points_coincide(a: Point2D, b: Point2D) -> bool {
+    (a[0] - b[0]).abs() <= POINT_EPSILON && (a[1] - b[1]).abs() <= POINT_EPSILON
+}
+
+/// Calculate intersection point between a line segment and a line perpendicular to `up`
+fn calculate_line_intersection(
+    p1: Point2D,
+    p2: Point2D,
+    height: f32,
+    up: Point2D,
+) -> Option<Point2D> {
+    let h1 = dot(p1, up);
+    let h2 = dot(p2, up);
+
+    // Check if the segment is parallel to the fill line
+    if (h1 - h2).abs() < f32::EPSILON {
+        return None;
+    }
+
+    // Check if the height is within the segment's range
+    if height < h1.min(h2) || height > h1.max(h2) {
+        return None;
+    }
+
+    // Calculate intersection using linear interpolation
+    let t = (height - h1) / (h2 - h1);
+
+    Some([p1[0] + t * (p2[0] - p1[0]), p1[1] + t * (p2[1] - p1[1])])
+}
+
+/// Dot product of two 2D vectors
+fn dot(a: Point2D, b: Point2D) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+/// Outward unit normal of a closed polygon at each of its vertices, averaging adjacent edges
+///
+/// Useful to light flat shapes as if they bulged out of the screen: the normals lie in the plane
+/// along the silhouette and can be tilted towards the viewer inside it.
+pub fn outline_normals(points: &[Point2D]) -> Vec<Point2D> {
+    let count = points.len();
+    // Edges turn right of their direction to face out of counter-clockwise polygons
+    let outward = if signed_area(points) >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    };
+    let edge_normal = |from: Point2D, to: Point2D| {
+        let [dx, dy] = [to[0] - from[0], to[1] - from[1]];
+        let length = dx.hypot(dy);
+        if length <= f32::EPSILON {
+            [0.0, 0.0]
+        } else {
+            [outward * dy / length, -outward * dx / length]
+        }
+    };
+
+    (0..count)
+        .map(|i| {
+            let previous = points[(i + count - 1) % count];
+            let next = points[(i + 1) % count];
+            let incoming = edge_normal(previous, points[i]);
+            let outgoing = edge_normal(points[i], next);
+            let sum = [incoming[0] + outgoing[0], incoming[1] + outgoing[1]];
+            let length = sum[0].hypot(sum[1]);
+            if length <= f32::EPSILON {
+                outgoing
+            } else {
+                [sum[0] / length, sum[1] / length]
+            }
+        })
+        .collect()
+}
+
+/// Turn (in radians) above which a vertex is a corner that resampling keeps in place
+const CORNER_ANGLE: f32 = PI / 6.0;
+
+/// Redistribute the vertices of a closed polygon evenly, about `spacing` apart along its edges
+///
+/// Corners stay where they are so the shape keeps its silhouette, the vertices between two
+/// corners are spaced evenly with the spacing rounded to fit. Useful for strokes, graduations or
+/// UVs along the glass, which look uneven with the denser vertices of the curves.
+pub fn resample_by_arc_length(points: &[Point2D], spacing: f32) -> Vec<Point2D> {
+    let count = points.len();
+    if count < 3 || spacing <= 0.0 {
+        return points.to_vec();
+    }
+
+    let is_corner = |i: usize| {
+        let previous = points[(i + count - 1) % count];
+        let current = points[i];
+        let next = points[(i + 1) % count];
+        let incoming = [current[0] - previous[0], current[1] - previous[1]];
+        let outgoing = [next[0] - current[0], next[1] - current[1]];
+        let lengths = incoming[0].hypot(incoming[1]) * outgoing[0].hypot(outgoing[1]);
+        lengths > f32::EPSILON && (dot(incoming, outgoing) / lengths).acos() > CORNER_ANGLE
+    };
+    let corners: Vec<bool> = (0..count).map(is_corner).collect();
+
+    // Start on a corner if there is one, so every run of edges ends on a corner
+    let start = corners.iter().position(|&corner| corner).unwrap_or(0);
+    let mut resampled = Vec::new();
+    let mut run = vec![points[start]];
+    for step in 1..=count {
+        let index = (start + step) % count;
+        run.push(points[index]);
+        if step == count || corners[index] {
+            resample_run(&run, spacing, &mut resampled);
+            run = vec![points[index]];
+        }
+    }
+    resampled
+}
+
+/// Push evenly spaced points along a polyline, from its start up to (excluding) its end
+fn resample_run(run: &[Point2D], spacing: f32, resampled: &mut Vec<Point2D>) {
+    let edge_length = |i: usize| (run[i + 1][0] - run[i][0]).hypot(run[i + 1][1] - run[i][1]);
+    let steps = ((polyline_length(run) / spacing).round() as usize).max(1);
+    let step_length = polyline_length(run) / steps as f32;
+
+    resampled.push(run[0]);
+    let (mut edge, mut edge_start) = (0, 0.0);
+    for step in 1..steps {
+        let distance = step as f32 * step_length;
+        while edge < run.len() - 2 && edge_start + edge_length(edge) < distance {
+            edge_start += edge_length(edge);
+            edge += 1;
+        }
+        let t = ((distance - edge_start) / edge_length(edge).max(f32::EPSILON)).clamp(0.0, 1.0);
+        let [from, to] = [run[edge], run[edge + 1]];
+        resampled.push([
+            from[0] + (to[0] - from[0]) * t,
+            from[1] + (to[1] - from[1]) * t,
+        ]);
+    }
+}
+
+/// Remove the degenerate parts of a polygon so it can always be triangulated
+///
+/// Drops points coinciding with their neighbor and points lying on the line through their
+/// neighbors, then returns an empty polygon if what is left has no area.
+pub fn clean_polygon(points: Vec<Point2D>) -> Vec<Point2D> {
+    let mut points = points;
+    loop {
+        let count = points.len();
+        if count < 3 {
+            return Vec::new();
+        }
+
+        let degenerate = (0..count).find(|&i| {
+            let previous = points[(i + count - 1) % count];
+            let current = points[i];
+            let next = points[(i + 1) % count];
+            let cross = (current[0] - previous[0]) * (next[1] - previous[1])
+                - (current[1] - previous[1]) * (next[0] - previous[0]);
+            points_coincide(previous, current) || cross.abs() <= POINT_EPSILON
+        });
+        match degenerate {
+            Some(index) => {
+                points.remove(index);
+            }
+            None => break,
+        }
+    }
+
+    if polygon_area(&points) < MIN_POLYGON_AREA {
+        return Vec::new();
+    }
+    points
+}
+
+/// Calculate the area enclosed by a polygon (regardless of winding)
+pub fn polygon_area(points: &[Point2D]) -> f32 {
+    signed_area(points).abs()
+}
+
+/// Calculate the area enclosed by a polygon, positive when it winds counter-clockwise
+pub fn signed_area(points: &[Point2D]) -> f32 {
+    let twice_area: f32 = (0..points.len())
+        .map(|i| {
+            let current = points[i];
+            let next = points[(i + 1) % points.len()];
+            current[0] * next[1] - next[0] * current[1]
+        })
+        .sum();
+
+    twice_area / 2.0
+}
+
+/// Reverse a polygon winding clockwise, so it winds counter-clockwise like all the outlines of
+/// this crate
+pub fn ensure_ccw(mut points: Vec<Point2D>) -> Vec<Point2D> {
+    if signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+    points
+}
+
+/// A polygon winding clockwise, reversed only if needed
+fn clockwise(points: &[Point2D]) -> Cow<'_, [Point2D]> {
+    if signed_area(points) > 0.0 {
+        Cow::Owned(points.iter().rev().copied().collect())
+    } else {
+        Cow::Borrowed(points)
+    }
+}
+
+/// Even-odd test for whether a point lies inside a polygon
+pub fn point_in_polygon(point: Point2D, polygon: &[Point2D]) -> bool {
+    let [x, y] = point;
+    let mut inside = false;
+    let mut previous = polygon.len().wrapping_sub(1);
+    for (current, &[xi, yi]) in polygon.iter().enumerate() {
+        let [xj, yj] = polygon[previous];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        previous = current;
+    }
+    inside
+}
+
+/// Which bulb to generate sand for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SandBulb {
+    Top,
+    Bottom,
+}
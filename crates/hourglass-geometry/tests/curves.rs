@@ -0,0 +1,461 @@
+use hourglass_geometry::*;
+
+fn bottom_sand(surface_wave: Option<SurfaceWave>) -> Vec<Point2D> {
+    let outline = HourglassShapeBuilder::new().generate_outline();
+    generate_sand_outline_with_params(
+        &outline,
+        &SandOutlineParams {
+            fill_percent: 0.5,
+            wall_offset: 0.0,
+            bulb: SandBulb::Bottom,
+            neck_height: 20.0,
+            min_y: -100.0,
+            max_y: 100.0,
+            tilt: 0.0,
+            solid_stream: false,
+            surface_wave,
+            funnel_fill: 0.0,
+            robust_geometry: false,
+        },
+    )
+}
+
+#[test]
+fn surface_wave_ripples_the_surface_between_the_walls() {
+    let flat = bottom_sand(None);
+    let wavy = bottom_sand(Some(SurfaceWave {
+        amplitude: 2.0,
+        wavelength: 10.0,
+        phase: 0.3,
+    }));
+
+    let top = |points: &[Point2D]| points.iter().map(|p| p[1]).fold(f32::MIN, f32::max);
+    assert!(wavy.len() > flat.len());
+    assert!(top(&wavy) > top(&flat));
+    assert!(top(&wavy) <= top(&flat) + 2.0);
+}
+
+#[test]
+fn top_sand_area_shrinks_as_it_drains() {
+    let outline = HourglassShapeBuilder::new().generate_outline();
+    let area = |fill_percent: f32| {
+        polygon_area(&generate_sand_outline_with_params(
+            &outline,
+            &SandOutlineParams {
+                fill_percent,
+                wall_offset: 0.0,
+                bulb: SandBulb::Top,
+                neck_height: 20.0,
+                min_y: -100.0,
+                max_y: 100.0,
+                tilt: 0.0,
+                solid_stream: false,
+                surface_wave: None,
+                funnel_fill: 0.0,
+                robust_geometry: false,
+            },
+        ))
+    };
+    assert!(area(0.8) > area(0.5));
+    assert!(area(0.5) > area(0.2));
+}
+
+#[test]
+fn nearly_drained_top_sand_funnels_into_the_neck() {
+    let outline = HourglassShapeBuilder::new().generate_outline();
+    let top_sand = |funnel_fill: f32| {
+        generate_sand_outline_with_params(
+            &outline,
+            &SandOutlineParams {
+                fill_percent: 0.05,
+                wall_offset: 0.0,
+                bulb: SandBulb::Top,
+                neck_height: 6.0,
+                min_y: -100.0,
+                max_y: 100.0,
+                tilt: 0.0,
+                solid_stream: false,
+                surface_wave: None,
+                funnel_fill,
+                robust_geometry: false,
+            },
+        )
+    };
+    let flat = top_sand(0.0);
+    let funnel = top_sand(0.1);
+
+    // The surface dips in the middle and rises along the walls, keeping the same amount of sand
+    let top = |points: &[Point2D]| points.iter().map(|p| p[1]).fold(f32::MIN, f32::max);
+    let center = funnel
+        .iter()
+        .filter(|p| p[0].abs() < 1e-3 && p[1] > 3.0)
+        .map(|p| p[1])
+        .fold(f32::MAX, f32::min);
+    assert!(top(&funnel) > top(&flat));
+    assert!(center < top(&flat));
+    assert!((polygon_area(&funnel) - polygon_area(&flat)).abs() < 0.5);
+}
+
+#[test]
+fn straight_fast_path_matches_general_path() {
+    let shape = HourglassShapeBuilder {
+        total_height: 200.0,
+        bulb_style: BulbStyle::Straight { width_factor: 0.6 },
+        neck_style: NeckStyle::Straight {
+            width: 10.0,
+            height: 6.0,
+        },
+    };
+    let wall_offset = 6.0;
+    let outline = shape.generate_outline_with_wall_offset(wall_offset);
+    assert!(shape.straight_profile(wall_offset).is_some());
+
+    let bounds = |points: &[Point2D]| {
+        points
+            .iter()
+            .fold([f32::MAX, f32::MAX, f32::MIN, f32::MIN], |b, p| {
+                [
+                    b[0].min(p[0]),
+                    b[1].min(p[1]),
+                    b[2].max(p[0]),
+                    b[3].max(p[1]),
+                ]
+            })
+    };
+    for bulb in [SandBulb::Top, SandBulb::Bottom] {
+        for step in 0..=20 {
+            let params = SandOutlineParams {
+                fill_percent: step as f32 / 20.0,
+                wall_offset,
+                bulb,
+                neck_height: 6.0,
+                min_y: -100.0,
+                max_y: 100.0,
+                tilt: 0.0,
+                solid_stream: true,
+                surface_wave: None,
+                funnel_fill: 0.12,
+                robust_geometry: false,
+            };
+            let general = generate_sand_outline_with_params(&outline, &params);
+            let fast = shape.generate_sand_outline(&outline, &params);
+
+            let context = format!("{bulb:?} at {}", params.fill_percent);
+            assert!(
+                (polygon_area(&general) - polygon_area(&fast)).abs() < 0.05,
+                "{context}"
+            );
+            for (a, b) in bounds(&general).iter().zip(bounds(&fast)) {
+                assert!((a - b).abs() < 1e-3, "{context}");
+            }
+        }
+    }
+}
+
+#[test]
+fn clean_polygon_drops_degenerate_points() {
+    let square = vec![
+        [0.0, 0.0],
+        [0.00001, 0.0],
+        [5.0, 0.0],
+        [10.0, 0.0],
+        [10.0, 10.0],
+        [0.0, 10.0],
+    ];
+    let cleaned = clean_polygon(square);
+    assert_eq!(cleaned.len(), 4);
+    assert!((polygon_area(&cleaned) - 100.0).abs() < 0.01);
+    assert!(clean_polygon(vec![[0.0, 0.0], [5.0, 0.0], [10.0, 0.00001]]).is_empty());
+}
+
+#[test]
+fn robust_sand_keeps_the_same_area() {
+    let outline = HourglassShapeBuilder::new().generate_outline();
+    for bulb in [SandBulb::Top, SandBulb::Bottom] {
+        for step in 0..=40 {
+            let params = SandOutlineParams {
+                fill_percent: step as f32 / 40.0,
+                wall_offset: 4.0,
+                bulb,
+                neck_height: 20.0,
+                min_y: -100.0,
+                max_y: 100.0,
+                tilt: 0.3,
+                solid_stream: true,
+                surface_wave: None,
+                funnel_fill: 0.1,
+                robust_geometry: false,
+            };
+            let fast = generate_sand_outline_with_params(&outline, &params);
+            let robust = generate_sand_outline_with_params(
+                &outline,
+                &SandOutlineParams {
+                    robust_geometry: true,
+                    ..params
+                },
+            );
+            assert!((polygon_area(&fast) - polygon_area(&robust)).abs() < 0.01);
+            assert!(robust.len() <= fast.len());
+        }
+    }
+}
+
+#[test]
+fn outlines_wind_counter_clockwise() {
+    let clockwise_square = vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+    let square = ensure_ccw(clockwise_square);
+    assert!(signed_area(&square) > 0.0);
+    assert_eq!(ensure_ccw(square.clone()), square);
+
+    let shapes = [
+        HourglassShapeBuilder::new(),
+        HourglassShapeBuilder {
+            bulb_style: BulbStyle::Circular {
+                curvature: -0.5,
+                width_factor: 0.6,
+                curve_resolution: 12,
+            },
+            neck_style: NeckStyle::Curved {
+                curvature: 0.5,
+                width: 12.0,
+                height: 8.0,
+                curve_resolution: 6,
+            },
+            ..HourglassShapeBuilder::new()
+        },
+        HourglassShapeBuilder {
+            bulb_style: BulbStyle::Straight { width_factor: 0.6 },
+            ..HourglassShapeBuilder::new()
+        },
+    ];
+    for shape in shapes {
+        assert!(signed_area(&shape.generate_outline()) > 0.0);
+
+        let outline = shape.generate_outline_with_wall_offset(4.0);
+        assert!(signed_area(&outline) > 0.0);
+        for bulb in [SandBulb::Top, SandBulb::Bottom] {
+            let params = SandOutlineParams {
+                fill_percent: 0.5,
+                wall_offset: 4.0,
+                bulb,
+                neck_height: shape.neck_style.height(),
+                min_y: -shape.total_height / 2.0,
+                max_y: shape.total_height / 2.0,
+                tilt: 0.0,
+                solid_stream: true,
+                surface_wave: None,
+                funnel_fill: 0.0,
+                robust_geometry: false,
+            };
+            assert!(signed_area(&shape.generate_sand_outline(&outline, &params)) > 0.0);
+        }
+    }
+}
+
+#[test]
+fn composite_curve_shares_resolution_by_length() {
+    let curve = || {
+        CompositeCurve::new()
+            .with_distribution(ResolutionDistribution::ByLength)
+            .add_transition(SmoothTransition::straight_line([0.0, 0.0], [90.0, 0.0]))
+            .add_transition(SmoothTransition::straight_line([90.0, 0.0], [100.0, 0.0]))
+    };
+    assert_eq!(curve().segment_resolutions(20), vec![18, 2]);
+    assert_eq!(
+        curve()
+            .with_distribution(ResolutionDistribution::default())
+            .segment_resolutions(20),
+        vec![10, 10]
+    );
+
+    // Overridden segments keep their resolution, the rest is shared between the others
+    let curve = curve().add_segment_with_resolution(
+        Box::new(CircularArc::quarter_circle(
+            [100.0, 10.0],
+            10.0,
+            CircleQuadrant::BottomRight,
+        )),
+        8,
+    );
+    let resolutions = curve.segment_resolutions(28);
+    assert_eq!(resolutions, vec![18, 2, 8]);
+    assert_eq!(curve.generate_points(28).len(), 18 + 2 + 8 + 1);
+}
+
+#[test]
+fn resampling_spaces_vertices_evenly_and_keeps_corners() {
+    let square = vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+    let resampled = resample_by_arc_length(&square, 2.5);
+    assert_eq!(resampled.len(), 16);
+    for corner in &square {
+        assert!(resampled.contains(corner));
+    }
+
+    let shape = HourglassShapeBuilder::new();
+    let outline = shape.generate_outline_with_spacing(4.0);
+    assert!(signed_area(&outline) > 0.0);
+    assert!((polygon_area(&outline) - polygon_area(&shape.generate_outline())).abs() < 50.0);
+    for (i, point) in outline.iter().enumerate() {
+        let next = outline[(i + 1) % outline.len()];
+        let distance = (next[0] - point[0]).hypot(next[1] - point[1]);
+        assert!(distance < 6.0, "{distance}");
+    }
+}
+
+#[test]
+fn outline_normals_point_away_from_the_shape() {
+    let outline = HourglassShapeBuilder::new().generate_outline();
+    let normals = outline_normals(&outline);
+    assert_eq!(normals.len(), outline.len());
+    for (point, normal) in outline.iter().zip(&normals) {
+        assert!((normal[0].hypot(normal[1]) - 1.0).abs() < 1e-4);
+        let outside = [point[0] + normal[0], point[1] + normal[1]];
+        let inside = [point[0] - normal[0], point[1] - normal[1]];
+        assert!(!point_in_polygon(outside, &outline), "{point:?}");
+        assert!(point_in_polygon(inside, &outline), "{point:?}");
+    }
+
+    // Either winding gives the same normals
+    let reversed: Vec<Point2D> = outline.iter().rev().copied().collect();
+    let mut reversed_normals = outline_normals(&reversed);
+    reversed_normals.reverse();
+    assert_eq!(reversed_normals, normals);
+}
+
+#[test]
+fn capped_resolution_only_lowers_curve_resolution() {
+    let bulb = BulbStyle::default().capped_resolution(8);
+    assert_eq!(bulb.curve_resolution(), 8);
+    assert_eq!(bulb.width_factor(), BulbStyle::default().width_factor());
+    assert_eq!(
+        NeckStyle::default().capped_resolution(8).curve_resolution(),
+        5
+    );
+    assert_eq!(
+        NeckStyle::default()
+            .with_curve_resolution(12)
+            .curve_resolution(),
+        12
+    );
+    assert_eq!(
+        NeckStyle::Straight {
+            width: 10.0,
+            height: 20.0
+        }
+        .capped_resolution(1)
+        .curve_resolution(),
+        2
+    );
+}
+
+#[test]
+fn equal_styles_hash_alike() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let bulb = BulbStyle::default();
+    assert_eq!(bulb, bulb.clone());
+    assert_eq!(hash(&bulb), hash(&bulb.clone()));
+    assert_ne!(bulb, bulb.with_curve_resolution(8));
+    assert_ne!(hash(&bulb), hash(&bulb.with_curve_resolution(8)));
+
+    // Zeroes of either sign are equal, so they hash alike
+    let neck = |curvature| NeckStyle::Curved {
+        curvature,
+        width: 12.0,
+        height: 8.0,
+        curve_resolution: 5,
+    };
+    assert_eq!(neck(0.0), neck(-0.0));
+    assert_eq!(hash(&neck(0.0)), hash(&neck(-0.0)));
+
+    let builder = HourglassShapeBuilder::new();
+    assert_eq!(hash(&builder), hash(&builder.clone()));
+    assert_ne!(
+        builder,
+        HourglassShapeBuilder {
+            total_height: 150.0,
+            ..builder.clone()
+        }
+    );
+}
+
+#[test]
+fn config_keys_look_up_styles_in_maps() {
+    let neck = |curvature| NeckStyle::Curved {
+        curvature,
+        width: 12.0,
+        height: 8.0,
+        curve_resolution: 5,
+    };
+    let mut necks = std::collections::HashMap::new();
+    necks.insert(ConfigKey::new(&neck(0.0)), "flat");
+    necks.insert(ConfigKey::new(&neck(1.0)), "curved");
+
+    assert_eq!(necks.get(&ConfigKey::new(&neck(-0.0))), Some(&"flat"));
+    assert_eq!(necks.get(&ConfigKey::new(&neck(1.0))), Some(&"curved"));
+    assert_eq!(necks.get(&ConfigKey::new(&neck(0.5))), None);
+
+    // NaN styles never equal themselves, but their keys do
+    assert_ne!(neck(f32::NAN), neck(f32::NAN));
+    assert_eq!(
+        ConfigKey::new(&neck(f32::NAN)),
+        ConfigKey::new(&neck(f32::NAN))
+    );
+    assert_eq!(
+        ConfigKey::new(&"plate").with_f32s([1.0, 2.0]),
+        ConfigKey::new(&"plate").with_f32s([1.0, 2.0])
+    );
+    assert_ne!(
+        ConfigKey::new(&"plate").with_f32s([1.0, 2.0]),
+        ConfigKey::new(&"plate").with_f32s([2.0, 1.0])
+    );
+}
+
+#[test]
+fn styles_blend_smoothly_between_variants() {
+    let straight = NeckStyle::Straight {
+        width: 10.0,
+        height: 20.0,
+    };
+    let curved = NeckStyle::Curved {
+        curvature: 1.0,
+        width: 20.0,
+        height: 10.0,
+        curve_resolution: 6,
+    };
+    assert_eq!(straight.lerp(&curved, 0.0), straight);
+    assert_eq!(straight.lerp(&curved, 1.0), curved);
+    assert_eq!(
+        straight.lerp(&curved, 0.5),
+        NeckStyle::Curved {
+            curvature: 0.5,
+            width: 15.0,
+            height: 15.0,
+            curve_resolution: 6,
+        }
+    );
+
+    // Straight bulbs blend as circular bulbs without curvature, so the outline doesn't jump
+    let bounds = |bulb_style| {
+        HourglassShapeBuilder {
+            bulb_style,
+            ..HourglassShapeBuilder::new()
+        }
+        .generate_outline()
+        .iter()
+        .fold([0.0f32; 2], |max, point| {
+            [max[0].max(point[0].abs()), max[1].max(point[1].abs())]
+        })
+    };
+    let straight = BulbStyle::Straight { width_factor: 0.75 };
+    let blended = bounds(straight.lerp(&BulbStyle::default(), 1e-3));
+    let start = bounds(straight);
+    assert!((blended[0] - start[0]).abs() < 0.1 && (blended[1] - start[1]).abs() < 0.1);
+}
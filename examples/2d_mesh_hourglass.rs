@@ -2,8 +2,9 @@
 
 use bevy::prelude::*;
 use bevy_hourglass::{
-    BulbStyle, HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
-    HourglassMeshSandConfig, HourglassPlugin, NeckStyle, SandSplashConfig,
+    BulbStyle, DustMotesConfig, HourglassMeshBodyConfig, HourglassMeshBuilder,
+    HourglassMeshPlatesConfig, HourglassMeshSandConfig, HourglassPlugin, NeckSparkleConfig,
+    NeckStyle, SandSplashConfig, StreamStyle,
 };
 
 fn main() {
@@ -18,7 +19,7 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Create an hourglass with body, plates, and automatic timing using the builder pattern
     HourglassMeshBuilder::new(Transform::from_xyz(0.0, 0.0, 0.0))
@@ -36,6 +37,7 @@ fn setup(
                 curve_resolution: 10,
             },
             color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+            ..default()
         })
         .with_plates(HourglassMeshPlatesConfig {
             width: 200.0,
@@ -46,8 +48,15 @@ fn setup(
             color: Color::srgb(0.9, 0.8, 0.6),
             fill_percent: 1.0,
             wall_offset: 4.0,
+            stream_style: StreamStyle::Grains {
+                density: 15.0,
+                speed: 150.0,
+            },
+            ..default()
         })
         .with_sand_splash(SandSplashConfig::default())
-        .with_timing(10.0) // 10-second timer for automatic animation
+        .with_neck_sparkle(NeckSparkleConfig::default())
+        .with_dust_motes(DustMotesConfig::default())
+        .with_seconds(10)
         .build(&mut commands, &mut meshes, &mut materials);
 }
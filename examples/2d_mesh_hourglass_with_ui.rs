@@ -2,8 +2,9 @@
 
 use bevy::prelude::*;
 use bevy_hourglass::{
-    BulbStyle, Hourglass, HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
-    HourglassMeshSandConfig, HourglassPlugin, NeckStyle, SandSplashConfig,
+    wasm_support, BulbStyle, Hourglass, HourglassMeshBodyConfig, HourglassMeshBuilder,
+    HourglassMeshPlatesConfig, HourglassMeshSandConfig, HourglassPlugin, NeckStyle,
+    SandSplashConfig, TimeFormat,
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -44,7 +45,10 @@ pub fn start() -> Result<(), JsValue> {
 // Shared run function for both wasm and native
 fn run() {
     App::new()
-        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_plugins((
+            DefaultPlugins.set(wasm_support::recommended_window_plugin()),
+            HourglassPlugin,
+        ))
         .add_systems(Startup, setup)
         .add_systems(Update, (handle_input, update_ui))
         .run();
@@ -65,7 +69,7 @@ fn setup(
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // Add a 2D camera
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Add UI node and text
     commands
@@ -83,7 +87,7 @@ fn setup(
                     margin: UiRect::all(Val::Px(20.0)),
                     ..default()
                 },
-                Text::new("Hourglass: 30s remaining"),
+                Text::new("Hourglass: 00:30 remaining"),
                 TextFont {
                     font_size: 24.0,
                     ..default()
@@ -110,6 +114,7 @@ fn setup(
                 curve_resolution: 5,
             },
             color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+            ..default()
         })
         .with_plates(HourglassMeshPlatesConfig {
             width: 165.0,
@@ -120,9 +125,10 @@ fn setup(
             color: Color::srgb(0.9, 0.8, 0.6),
             fill_percent: 1.0, // Start with full top bulb
             wall_offset: 8.0,  // 8 pixels from glass walls
+            ..default()
         })
         .with_sand_splash(SandSplashConfig::default())
-        .with_timing(30.0) // 30-second timer for automatic animation
+        .with_seconds(30)
         .build(&mut commands, &mut meshes, &mut materials);
 
     // Add the MainHourglass marker to track this specific hourglass
@@ -184,7 +190,7 @@ fn update_ui(
     mut text_query: Query<&mut Text, With<HourglassInfoText>>,
 ) {
     if let (Ok(hourglass), Ok(mut text)) = (hourglasses.single(), text_query.single_mut()) {
-        let remaining_secs = hourglass.remaining_time as u32;
+        let remaining = hourglass.format_remaining(&TimeFormat::MinutesSeconds);
         let status = if hourglass.flipping {
             "Flipping"
         } else if hourglass.flipped {
@@ -200,9 +206,8 @@ fn update_ui(
         };
 
         text.0 = format!(
-            "Hourglass: {}s remaining | Status: {} | {}",
-            remaining_secs, status, running
-        )
-        .into();
+            "Hourglass: {} remaining | Status: {} | {}",
+            remaining, status, running
+        );
     }
 }
@@ -0,0 +1,75 @@
+//! Demonstrates hourglasses following other entities around, like buff timers above a unit.
+//!
+//! Press SPACE to despawn the units; their hourglasses are removed with them.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+#[derive(Component)]
+struct Unit {
+    speed: f32,
+    spin: f32,
+}
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_units, despawn_units))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    let units = [
+        (Vec3::new(-200.0, -50.0, 0.0), 1.0, 0.0, false),
+        (Vec3::new(200.0, -50.0, 0.0), 1.5, 1.0, true),
+    ];
+
+    for (position, speed, spin, follow_rotation) in units {
+        let unit = commands
+            .spawn((
+                Unit { speed, spin },
+                Mesh2d(meshes.add(Rectangle::new(60.0, 60.0))),
+                MeshMaterial2d(materials.add(Color::srgb(0.3, 0.5, 0.8))),
+                Transform::from_translation(position),
+            ))
+            .id();
+
+        let hourglass = HourglassMeshBuilder::new(Transform::from_scale(Vec3::splat(0.4)))
+            .with_body(HourglassMeshBodyConfig::default())
+            .with_plates(HourglassMeshPlatesConfig::default())
+            .with_sand(HourglassMeshSandConfig::default())
+            .with_timing(10.0)
+            .with_auto_flip(true)
+            .build(&mut commands, &mut meshes, &mut materials);
+        commands.entity(hourglass).insert(
+            HourglassAttachment::new(unit, Vec3::new(0.0, 100.0, 1.0))
+                .with_follow_rotation(follow_rotation),
+        );
+    }
+}
+
+fn move_units(time: Res<Time>, mut units: Query<(&Unit, &mut Transform)>) {
+    for (unit, mut transform) in units.iter_mut() {
+        transform.translation.y = -50.0 + (time.elapsed_secs() * unit.speed).sin() * 80.0;
+        transform.rotation = Quat::from_rotation_z((time.elapsed_secs() * unit.spin).sin() * 0.6);
+    }
+}
+
+fn despawn_units(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    units: Query<Entity, With<Unit>>,
+) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        for unit in units.iter() {
+            commands.entity(unit).despawn();
+        }
+    }
+}
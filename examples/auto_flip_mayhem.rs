@@ -3,10 +3,11 @@
 
 use bevy::prelude::*;
 use bevy_hourglass::*;
+use rand::Rng;
 
 fn main() {
     App::new()
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(wasm_support::recommended_window_plugin()))
         .add_plugins(HourglassPlugin)
         .add_systems(Startup, setup)
         .run();
@@ -18,113 +19,38 @@ fn setup(
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // Spawn camera
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Create lots of hourglasses with random configurations
-    let grid_cols = 6;
-    let grid_rows = 5;
-    let num_hourglasses = grid_cols * grid_rows;
-
-    for i in 0..num_hourglasses {
-        let seed = i as f32;
-
-        // Use grid-based positioning with some randomness to avoid perfect alignment
-        let col = i % grid_cols;
-        let row = i / grid_cols;
-
-        let base_x = (col as f32 - (grid_cols as f32 - 1.0) / 2.0) * 180.0;
-        let base_y = (row as f32 - (grid_rows as f32 - 1.0) / 2.0) * 120.0 - 50.0; // Offset down from text
-
-        // Add random offset to break perfect grid alignment
-        let random_x_offset = ((seed * 73.0) % 60.0) - 30.0; // -30 to 30
-        let random_y_offset = ((seed * 127.0) % 40.0) - 20.0; // -20 to 20
-
-        let x = base_x + random_x_offset;
-        let y = base_y + random_y_offset;
-        let position = Vec3::new(x, y, 0.0);
-
-        // Random size scaling
-        let scale = 0.3 + ((seed * 43.0) % 100.0) / 100.0 * 0.7; // 0.3 to 1.0
-
-        // Random timer duration between 1 to 10 seconds
-        let flip_duration = 1.0 + ((seed * 89.0) % 100.0) / 100.0 * 9.0;
-
-        // Random colors
-        let glass_hue = (seed * 37.0) % 360.0;
-        let sand_hue = (seed * 113.0) % 360.0;
-        let plate_hue = (seed * 197.0) % 360.0;
-
-        let glass_color = Color::hsla(glass_hue, 0.7, 0.8, 0.3);
-        let sand_color = Color::hsla(sand_hue, 0.8, 0.6, 1.0);
-        let plate_color = Color::hsla(plate_hue, 0.6, 0.4, 1.0);
-
-        // Random hourglass height
-        let height = 80.0 + ((seed * 157.0) % 100.0) / 100.0 * 120.0; // 80 to 200
-
-        // Random bulb style
-        let bulb_style = match ((seed * 23.0) as u32) % 3 {
-            0 => BulbStyle::Straight {
-                width_factor: 0.6 + ((seed * 61.0) % 100.0) / 100.0 * 0.3, // 0.6 to 0.9
-            },
-            1 => BulbStyle::Circular {
-                curvature: 0.3 + ((seed * 139.0) % 100.0) / 100.0 * 2.7, // 0.3 to 3.0
-                width_factor: 0.6 + ((seed * 71.0) % 100.0) / 100.0 * 0.3, // 0.6 to 0.9
-                curve_resolution: 15 + (((seed * 31.0) as u32) % 15) as usize, // 15 to 30
-            },
-            _ => BulbStyle::Circular {
-                curvature: 1.0,
-                width_factor: 0.75,
-                curve_resolution: 20,
-            },
-        };
-
-        // Random neck style
-        let neck_style = match ((seed * 47.0) as u32) % 2 {
-            0 => NeckStyle::Straight {
-                width: 6.0 + ((seed * 83.0) % 100.0) / 100.0 * 12.0, // 6 to 18
-                height: 4.0 + ((seed * 97.0) % 100.0) / 100.0 * 12.0, // 4 to 16
-            },
-            _ => NeckStyle::Curved {
-                curvature: 0.1 + ((seed * 179.0) % 100.0) / 100.0 * 0.9, // 0.1 to 1.0
-                width: 6.0 + ((seed * 149.0) % 100.0) / 100.0 * 12.0,    // 6 to 18
-                height: 4.0 + ((seed * 163.0) % 100.0) / 100.0 * 12.0,   // 4 to 16
-                curve_resolution: 3 + (((seed * 53.0) as u32) % 8) as usize, // 3 to 10
-            },
-        };
-
-        // Random plate dimensions
-        let plate_width = height * 0.7 + ((seed * 67.0) % 100.0) / 100.0 * height * 0.3; // 0.7 to 1.0 of height
-        let plate_height = 4.0 + ((seed * 101.0) % 100.0) / 100.0 * 8.0; // 4 to 12
-
-        // Random sand fill
-        let sand_fill = 0.3 + ((seed * 191.0) % 100.0) / 100.0 * 0.4; // 0.3 to 0.7
-        let wall_offset = 3.0 + ((seed * 211.0) % 100.0) / 100.0 * 5.0; // 3 to 8
-
-        // Create the hourglass with all random parameters
-        HourglassMeshBuilder::new(
-            Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-        )
-        .with_body(HourglassMeshBodyConfig {
-            total_height: height,
-            bulb_style,
-            neck_style,
-            color: glass_color,
-        })
-        .with_plates(HourglassMeshPlatesConfig {
-            width: plate_width,
-            height: plate_height,
-            color: plate_color,
-        })
-        .with_sand(HourglassMeshSandConfig {
-            color: sand_color,
-            fill_percent: sand_fill,
-            wall_offset,
-        })
-        .with_timing(flip_duration)
-        .with_auto_flip(true)
-        .with_flip_duration(0.1 + ((seed * 223.0) % 100.0) / 100.0 * 2.9) // 0.1 to 3.0 seconds flip animation
-        .build(&mut commands, &mut meshes, &mut materials);
-    }
+    let randomizer = HourglassStyleRandomizer::default();
+    let mut rng = HourglassStyleRandomizer::seeded_rng(7);
+
+    let grid = GridConfig::new(5, 6, Vec2::new(180.0, 120.0), move |cell| {
+        // Random size scaling, timer duration and flip animation duration
+        let scale = rng.random_range(0.3..=1.0);
+        let duration = rng.random_range(1.0..=10.0);
+        let flip_duration = rng.random_range(0.1..=3.0);
+
+        // Random colors, shape and sand
+        let (body, plates, sand) = randomizer.random_style(&mut rng);
+
+        HourglassMeshBuilder::new(cell.transform.with_scale(Vec3::splat(scale)))
+            .with_body(body)
+            .with_plates(plates)
+            .with_sand(sand)
+            // Splashes shrink along with the hourglass
+            .with_sand_splash(SandSplashConfig {
+                size_units: SizeUnits::Local,
+                ..default()
+            })
+            .with_timing(duration)
+            .with_auto_flip(true)
+            .with_flip_duration(flip_duration)
+    })
+    .with_center(Vec3::new(0.0, -50.0, 0.0)) // Offset down from text
+    .with_jitter(Vec2::new(30.0, 20.0), 7); // Avoid perfect alignment
+
+    spawn_hourglass_grid(&mut commands, &mut meshes, &mut materials, grid);
 
     // Add title
     commands.spawn((
@@ -0,0 +1,32 @@
+//! Bakes an hourglass style into static icons at several fill levels and shows them in a row.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera2d);
+
+    let icons = bake_hourglass_images(
+        &HourglassMeshBodyConfig::default(),
+        Some(&HourglassMeshPlatesConfig::default()),
+        &HourglassMeshSandConfig::default(),
+        &DEFAULT_BAKE_FILLS,
+        &HourglassBakeConfig::new(128),
+    );
+
+    let spacing = 150.0;
+    let start_x = -spacing * (icons.len() - 1) as f32 / 2.0;
+    for (i, icon) in icons.into_iter().enumerate() {
+        commands.spawn((
+            Sprite::from_image(images.add(icon)),
+            Transform::from_xyz(start_x + i as f32 * spacing, 0.0, 0.0),
+        ));
+    }
+}
@@ -7,6 +7,13 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(HourglassPlugin)
+        // Press F3 to show the geometry debug overlay
+        .add_plugins(HourglassDebugPlugin {
+            config: HourglassDebugConfig {
+                enabled: false,
+                ..default()
+            },
+        })
         .add_systems(Startup, setup)
         .run();
 }
@@ -17,7 +24,7 @@ fn setup(
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // Spawn camera
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Create different hourglass styles to demonstrate the composable curve system
 
@@ -109,6 +116,7 @@ fn setup(
                 curve_resolution: 10,
             },
             color: Color::srgba(1.0, 0.7, 0.8, 0.3), // Pink glass
+            ..default()
         })
         .with_plates(HourglassMeshPlatesConfig {
             width: 150.0,
@@ -119,6 +127,7 @@ fn setup(
             color: Color::srgb(1.0, 0.9, 0.5), // Light yellow sand
             fill_percent: 0.5,                 // Start with half-filled top bulb
             wall_offset: 5.0,                  // Sand is 5 pixels offset from glass wall
+            ..default()
         })
         .with_timing(10.0)
         .with_auto_flip(true)
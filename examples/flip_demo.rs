@@ -31,20 +31,21 @@ fn setup(
         false, // don't auto-flip
     );
 
-    // Create an auto-flipping hourglass
-    spawn_mesh_hourglass_with_flip(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        3.0,
-        Vec3::new(150.0, 0.0, 0.0),
-        0.5,  // flip duration
-        true, // auto-flip when empty
-    );
+    // Create an auto-flipping hourglass with an eased flip animation
+    HourglassMeshBuilder::new(Transform::from_xyz(150.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_seconds(3)
+        .with_flip_duration(0.5)
+        .with_flip_easing(EaseFunction::CubicInOut)
+        .with_auto_flip(true)
+        .with_label("Auto-flip", LabelConfig::default())
+        .build(&mut commands, &mut meshes, &mut materials);
 
     // Instructions
     commands.spawn((
-        Text::new("Press SPACE to flip the left hourglass\nRight hourglass auto-flips when empty"),
+        Text::new("Press SPACE to flip the left hourglass\nPress R to rewind it\nRight hourglass auto-flips when empty"),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Px(10.0),
@@ -66,4 +67,11 @@ fn handle_input(
             }
         }
     }
+
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        // Run the sand of the left hourglass back up over one second
+        if let Some(mut hourglass) = query.iter_mut().next() {
+            hourglass.rewind(1.0);
+        }
+    }
 }
@@ -0,0 +1,56 @@
+//! Uses an hourglass as a gauge for a resource instead of a timer.
+//!
+//! Press UP/DOWN to change the amount of mana shown by the hourglass.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+#[derive(Component)]
+struct Mana(f32);
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (change_mana, update_gauge).chain())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    let gauge = HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig {
+            color: Color::srgb(0.3, 0.5, 1.0),
+            ..default()
+        })
+        .with_value_source(HourglassValueSource::External)
+        .build(&mut commands, &mut meshes, &mut materials);
+    commands.entity(gauge).insert(Mana(1.0));
+}
+
+fn change_mana(time: Res<Time>, keyboard: Res<ButtonInput<KeyCode>>, mut mana: Query<&mut Mana>) {
+    let mut change = 0.0;
+    if keyboard.pressed(KeyCode::ArrowUp) {
+        change += 0.5;
+    }
+    if keyboard.pressed(KeyCode::ArrowDown) {
+        change -= 0.5;
+    }
+
+    for mut mana in mana.iter_mut() {
+        mana.0 = (mana.0 + change * time.delta_secs()).clamp(0.0, 1.0);
+    }
+}
+
+fn update_gauge(mut gauges: Query<(&Mana, &mut Hourglass), Changed<Mana>>) {
+    for (mana, mut hourglass) in gauges.iter_mut() {
+        hourglass.set_fill(mana.0);
+    }
+}
@@ -0,0 +1,65 @@
+//! Glass lit by a light following the cursor, next to an unlit hourglass.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin, HourglassLightingPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, follow_cursor)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        HourglassLight2d::default(),
+        Transform::from_xyz(-100.0, 100.0, 0.0),
+    ));
+
+    // Rounded glass shaded by the light
+    HourglassMeshBuilder::new(Transform::from_xyz(-150.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig {
+            color: Color::srgba(0.85, 0.95, 1.0, 0.35),
+            ..default()
+        })
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_lighting(HourglassLit::default())
+        .with_seconds(10)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+
+    // Flat glass for comparison
+    HourglassMeshBuilder::new(Transform::from_xyz(150.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig {
+            color: Color::srgba(0.85, 0.95, 1.0, 0.35),
+            ..default()
+        })
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_seconds(10)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+}
+
+/// Move the light to the cursor
+fn follow_cursor(
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut light: Single<&mut Transform, With<HourglassLight2d>>,
+) {
+    let (camera, camera_transform) = *camera;
+    if let Some(position) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    {
+        light.translation = position.extend(0.0);
+    }
+}
@@ -0,0 +1,41 @@
+//! Hourglass filled with liquid instead of sand, next to a regular sand hourglass.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    // Liquid with a waving surface, dripping through the neck
+    HourglassMeshBuilder::new(Transform::from_xyz(-150.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig {
+            color: Color::srgba(0.2, 0.5, 0.9, 0.85),
+            content_style: ContentStyle::liquid(),
+            ..default()
+        })
+        .with_seconds(10)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+
+    // Regular sand for comparison
+    HourglassMeshBuilder::new(Transform::from_xyz(150.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_seconds(10)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+}
@@ -6,10 +6,9 @@
 use bevy::prelude::*;
 use bevy::render::{mesh::Indices, render_resource::PrimitiveTopology};
 use bevy_hourglass::{
-    spawn_mesh_hourglass_with_timer, BulbStyle, Hourglass, HourglassMeshBody,
-    HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlate, HourglassMeshPlatesConfig,
-    HourglassMeshSandConfig, HourglassMeshSandState, HourglassPlugin, HourglassShapeBuilder,
-    NeckStyle, SandSplashConfig,
+    lerp_configs, BulbStyle, Hourglass, HourglassMeshBody, HourglassMeshBodyConfig,
+    HourglassMeshBuilder, HourglassMeshPlate, HourglassMeshPlatesConfig, HourglassMeshSandConfig,
+    HourglassMeshSandState, HourglassPlugin, HourglassShapeBuilder, NeckStyle, SandSplashConfig,
 };
 
 fn main() {
@@ -41,25 +40,38 @@ fn setup(
         .build(&mut commands, &mut meshes, &mut materials);
 }
 
+/// Type alias for the body entities query to reduce type complexity
+type BodyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static HourglassMeshBody,
+        Option<&'static Mesh2d>,
+        Option<&'static MeshMaterial2d<ColorMaterial>>,
+    ),
+>;
+
+/// Type alias for the plate entities query to reduce type complexity
+type PlateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static HourglassMeshPlate,
+        Option<&'static Mesh2d>,
+        Option<&'static MeshMaterial2d<ColorMaterial>>,
+    ),
+>;
+
 fn update_morphing_shape(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
     mut query: Query<(Entity, &Hourglass, &mut HourglassMeshSandState)>,
     children_query: Query<&Children>,
-    body_query: Query<(
-        Entity,
-        &HourglassMeshBody,
-        Option<&Mesh2d>,
-        Option<&MeshMaterial2d<ColorMaterial>>,
-    )>,
-    plate_query: Query<(
-        Entity,
-        &HourglassMeshPlate,
-        Option<&Mesh2d>,
-        Option<&MeshMaterial2d<ColorMaterial>>,
-    )>,
+    body_query: BodyQuery,
+    plate_query: PlateQuery,
 ) {
     // Don't interrupt the hourglass if it's currently flipping
     for (_, hourglass, _) in query.iter() {
@@ -77,16 +89,11 @@ fn update_morphing_shape(
 
     // Update the hourglass components
     for (hourglass_entity, hourglass, mut sand_state) in query.iter_mut() {
-        // Update the body configuration for sand
-        sand_state.body_config = body_config.clone();
+        // Update the body configuration for sand, which triggers sand mesh regeneration
+        sand_state.set_body_config(body_config.clone());
 
         // Preserve the current fill percentage
-        let fill_percent = hourglass.remaining_time / hourglass.total_time;
-        sand_state.fill_percent = fill_percent;
-        sand_state.sand_config.fill_percent = fill_percent;
-
-        // Set the flag to trigger sand mesh regeneration
-        sand_state.needs_update = true;
+        sand_state.set_fill_percent(hourglass.remaining_time / hourglass.total_time);
 
         // Now update the body and plates
         if let Ok(children) = children_query.get(hourglass_entity) {
@@ -110,7 +117,7 @@ fn update_morphing_shape(
                     {
                         let mesh_handle = meshes.add(new_mesh);
 
-                        if let Some(_) = mesh_handle_opt {
+                        if mesh_handle_opt.is_some() {
                             // Replace the mesh component entirely
                             commands.entity(body_entity).insert(Mesh2d(mesh_handle));
                         } else {
@@ -121,7 +128,7 @@ fn update_morphing_shape(
                 }
 
                 // Try to find the plate entities
-                if let Ok((plate_entity, plate_type, mesh_handle_opt, material_opt)) =
+                if let Ok((plate_entity, plate_type, mesh_handle_opt, _material_opt)) =
                     plate_query.get(child)
                 {
                     // Create new plate mesh
@@ -148,7 +155,7 @@ fn update_morphing_shape(
 
                     let mesh_handle = meshes.add(plate_mesh);
 
-                    if let Some(_) = mesh_handle_opt {
+                    if mesh_handle_opt.is_some() {
                         // Replace the mesh component entirely
                         commands.entity(plate_entity).insert(Mesh2d(mesh_handle));
                     } else {
@@ -204,20 +211,10 @@ fn get_morphed_shape_config(t: f32) -> (HourglassMeshBodyConfig, HourglassMeshPl
     let (config2, plates2) = get_main_shape_config(shape2);
 
     // Interpolate between the configurations
-    let interpolated_body = HourglassMeshBodyConfig {
-        total_height: lerp_f32(config1.total_height, config2.total_height, local_t),
-        bulb_style: interpolate_bulb_style(&config1.bulb_style, &config2.bulb_style, local_t),
-        neck_style: interpolate_neck_style(&config1.neck_style, &config2.neck_style, local_t),
-        color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-    };
-
-    let interpolated_plates = HourglassMeshPlatesConfig {
-        width: lerp_f32(plates1.width, plates2.width, local_t),
-        height: lerp_f32(plates1.height, plates2.height, local_t),
-        ..Default::default()
-    };
-
-    (interpolated_body, interpolated_plates)
+    (
+        lerp_configs(&config1, &config2, local_t),
+        lerp_configs(&plates1, &plates2, local_t),
+    )
 }
 
 /// Resource to track the current hourglass configuration
@@ -266,6 +263,7 @@ fn get_main_shape_config(
                     curve_resolution: 10,
                 },
                 color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+                ..default()
             },
             HourglassMeshPlatesConfig {
                 width: 400.0,
@@ -286,6 +284,7 @@ fn get_main_shape_config(
                     height: 32.0,
                 },
                 color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+                ..default()
             },
             HourglassMeshPlatesConfig {
                 width: 380.0,
@@ -308,6 +307,7 @@ fn get_main_shape_config(
                     curve_resolution: 8,
                 },
                 color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+                ..default()
             },
             HourglassMeshPlatesConfig {
                 width: 340.0, // Narrower plates
@@ -330,6 +330,7 @@ fn get_main_shape_config(
                     curve_resolution: 12,
                 },
                 color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+                ..default()
             },
             HourglassMeshPlatesConfig {
                 width: 390.0, // Wider plates
@@ -339,109 +340,3 @@ fn get_main_shape_config(
         ),
     }
 }
-
-// Helper functions for interpolation
-fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
-    a + (b - a) * t
-}
-
-fn interpolate_bulb_style(style1: &BulbStyle, style2: &BulbStyle, t: f32) -> BulbStyle {
-    match (style1, style2) {
-        (
-            BulbStyle::Circular {
-                curvature: c1,
-                width_factor: w1,
-                curve_resolution: r1,
-            },
-            BulbStyle::Circular {
-                curvature: c2,
-                width_factor: w2,
-                curve_resolution: r2,
-            },
-        ) => BulbStyle::Circular {
-            curvature: lerp_f32(*c1, *c2, t),
-            width_factor: lerp_f32(*w1, *w2, t),
-            curve_resolution: (lerp_f32(*r1 as f32, *r2 as f32, t) as usize).max(5),
-        },
-        // If styles are different types, just switch at halfway point
-        (style1, style2) => {
-            if t < 0.5 {
-                style1.clone()
-            } else {
-                style2.clone()
-            }
-        }
-    }
-}
-
-fn interpolate_neck_style(style1: &NeckStyle, style2: &NeckStyle, t: f32) -> NeckStyle {
-    match (style1, style2) {
-        (
-            NeckStyle::Curved {
-                curvature: c1,
-                width: w1,
-                height: h1,
-                curve_resolution: r1,
-            },
-            NeckStyle::Curved {
-                curvature: c2,
-                width: w2,
-                height: h2,
-                curve_resolution: r2,
-            },
-        ) => NeckStyle::Curved {
-            curvature: lerp_f32(*c1, *c2, t),
-            width: lerp_f32(*w1, *w2, t),
-            height: lerp_f32(*h1, *h2, t),
-            curve_resolution: (lerp_f32(*r1 as f32, *r2 as f32, t) as usize).max(3),
-        },
-        (
-            NeckStyle::Straight {
-                width: w1,
-                height: h1,
-            },
-            NeckStyle::Straight {
-                width: w2,
-                height: h2,
-            },
-        ) => NeckStyle::Straight {
-            width: lerp_f32(*w1, *w2, t),
-            height: lerp_f32(*h1, *h2, t),
-        },
-        // Mixed types - convert straight to curved for interpolation
-        (
-            NeckStyle::Straight {
-                width: w1,
-                height: h1,
-            },
-            NeckStyle::Curved {
-                curvature: c2,
-                width: w2,
-                height: h2,
-                curve_resolution: r2,
-            },
-        ) => NeckStyle::Curved {
-            curvature: lerp_f32(0.0, *c2, t),
-            width: lerp_f32(*w1, *w2, t),
-            height: lerp_f32(*h1, *h2, t),
-            curve_resolution: *r2,
-        },
-        (
-            NeckStyle::Curved {
-                curvature: c1,
-                width: w1,
-                height: h1,
-                curve_resolution: r1,
-            },
-            NeckStyle::Straight {
-                width: w2,
-                height: h2,
-            },
-        ) => NeckStyle::Curved {
-            curvature: lerp_f32(*c1, 0.0, t),
-            width: lerp_f32(*w1, *w2, t),
-            height: lerp_f32(*h1, *h2, t),
-            curve_resolution: *r1,
-        },
-    }
-}
@@ -0,0 +1,42 @@
+//! Glowing neon hourglass using emissive materials with HDR and bloom.
+
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let theme = HourglassTheme::neon();
+
+    // Bloom needs an HDR camera to pick up colors above 1.0
+    commands.spawn((
+        Camera2d,
+        Camera {
+            hdr: true,
+            clear_color: ClearColorConfig::Custom(theme.background_hint.unwrap_or(Color::BLACK)),
+            ..default()
+        },
+        Tonemapping::TonyMcMapface,
+        Bloom::default(),
+    ));
+
+    HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_theme(theme)
+        .with_seconds(20)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+}
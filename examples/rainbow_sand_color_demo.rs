@@ -6,7 +6,7 @@
 use bevy::prelude::*;
 use bevy_hourglass::{
     Hourglass, HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
-    HourglassMeshSandConfig, HourglassPlugin, SandSplash, SandSplashConfig,
+    HourglassMeshSandConfig, HourglassPlugin, SandSplash, SandSplashConfig, SizeUnits,
 };
 
 fn main() {
@@ -35,6 +35,7 @@ fn setup(
         particle_color: Color::default(),
         particle_size: 3.0,
         vertical_offset: 5.0,
+        size_units: SizeUnits::World,
     };
 
     HourglassMeshBuilder::new(Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)))
@@ -0,0 +1,45 @@
+//! Renders an hourglass to an image and shows it as an inset in the corner of the UI.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands.spawn(Camera2d);
+
+    let hourglass = HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(15.0)
+        .with_auto_flip(true)
+        .build(&mut commands, &mut meshes, &mut materials);
+
+    let image =
+        render_hourglass_to_image(&mut commands, &mut images, hourglass, UVec2::new(64, 64));
+
+    // Low resolution inset in the top right corner
+    commands.spawn((
+        ImageNode::new(image),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            width: Val::Px(160.0),
+            height: Val::Px(160.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+    ));
+}
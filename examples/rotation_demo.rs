@@ -0,0 +1,54 @@
+//! Demonstrates hourglasses resting at an angle, spinning continuously and swinging.
+//!
+//! Sand surfaces stay level with gravity regardless of the hourglass' orientation.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    // Hourglass mounted at 45° on a wall
+    HourglassMeshBuilder::new(Transform::from_xyz(-300.0, 0.0, 0.0))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(20.0)
+        .with_base_rotation(std::f32::consts::FRAC_PI_4)
+        .build(&mut commands, &mut meshes, &mut materials);
+
+    // Slowly spinning decorative hourglass
+    let spinning = spawn_mesh_hourglass_with_timer(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        60.0,
+        Vec3::new(0.0, 0.0, 0.0),
+    );
+    commands
+        .entity(spinning)
+        .insert(ContinuousRotation::new(0.5));
+
+    // Hourglass hanging from a pivot and swinging like a pendulum
+    let swinging = spawn_mesh_hourglass_with_timer(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        60.0,
+        Vec3::new(300.0, 0.0, 0.0),
+    );
+    commands
+        .entity(swinging)
+        .insert(SwingMount::new(15f32.to_radians(), 0.4).with_length(150.0));
+}
@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use bevy_hourglass::{
     spawn_mesh_hourglass_with_timer, HourglassMeshBodyConfig, HourglassMeshBuilder,
     HourglassMeshPlatesConfig, HourglassMeshSandConfig, HourglassPlugin, SandSplashConfig,
+    SizeUnits,
 };
 
 fn main() {
@@ -54,6 +55,7 @@ fn setup(
         particle_color: Color::srgb(0.0, 0.0, 0.0), // Large black particles
         particle_size: 3.0,
         vertical_offset: 5.0,
+        size_units: SizeUnits::World,
     };
 
     HourglassMeshBuilder::new(Transform::from_translation(Vec3::new(300.0, 0.0, 0.0)))
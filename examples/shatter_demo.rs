@@ -0,0 +1,58 @@
+//! Hourglass that shatters when its time is up.
+//!
+//! Press SPACE to spawn a new hourglass.
+
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, (setup, spawn_hourglass))
+        .add_systems(Update, (shatter_when_empty, respawn_hourglass))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::new("The hourglass shatters when empty\nPress SPACE for a new one"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
+
+fn spawn_hourglass(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_seconds(3)
+        .build(&mut commands, &mut meshes, &mut materials);
+}
+
+fn shatter_when_empty(mut commands: Commands, mut empty_events: EventReader<HourglassEmptyEvent>) {
+    for event in empty_events.read() {
+        shatter_hourglass(&mut commands, event.entity, ShatterConfig::default());
+    }
+}
+
+fn respawn_hourglass(
+    commands: Commands,
+    meshes: ResMut<Assets<Mesh>>,
+    materials: ResMut<Assets<ColorMaterial>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hourglasses: Query<(), With<Hourglass>>,
+) {
+    if keyboard.just_pressed(KeyCode::Space) && hourglasses.is_empty() {
+        spawn_hourglass(commands, meshes, materials);
+    }
+}
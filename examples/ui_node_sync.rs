@@ -0,0 +1,57 @@
+//! Demonstrates slotting a mesh hourglass into a UI layout with `SyncToUiNode`.
+
+use bevy::prelude::*;
+use bevy_hourglass::{
+    spawn_mesh_hourglass_with_timer, AccessibleHourglass, HourglassPlugin, SyncToUiNode,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, HourglassPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    // Side panel with a slot for the hourglass
+    let mut slot = Entity::PLACEHOLDER;
+    commands
+        .spawn(Node {
+            width: Val::Percent(30.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(20.0)),
+            row_gap: Val::Px(10.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(Text::new("Time remaining"));
+            slot = parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(50.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.05)),
+                ))
+                .id();
+        });
+
+    let hourglass = spawn_mesh_hourglass_with_timer(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        30.0,
+        Vec3::ZERO,
+    );
+    commands
+        .entity(hourglass)
+        .insert((SyncToUiNode(slot), AccessibleHourglass::new("Turn timer")));
+}
@@ -0,0 +1,70 @@
+//! Exposes hourglasses to assistive technologies such as screen readers.
+
+use crate::components::Hourglass;
+use crate::format::TimeFormat;
+use accesskit::{Node, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::prelude::*;
+
+/// Component that exposes an hourglass as an accessible timer
+///
+/// The entity gets an [`AccessibilityNode`] with the `Timer` role, the given label and the
+/// remaining time as its value.
+#[derive(Component, Debug, Clone)]
+pub struct AccessibleHourglass {
+    /// Label announced for the timer, e.g. "Turn timer"
+    pub label: String,
+    /// Format of the remaining time reported as the timer's value
+    pub format: TimeFormat,
+}
+
+impl AccessibleHourglass {
+    /// Expose the hourglass with the given label, reporting the time as minutes and seconds
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            format: TimeFormat::default(),
+        }
+    }
+
+    /// Set the format of the remaining time
+    pub fn with_format(mut self, format: TimeFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// System that keeps the accessibility nodes of hourglasses up to date
+///
+/// Nodes are only touched when the label or the formatted remaining time changes, so assistive
+/// technologies aren't flooded with updates.
+pub fn update_accessible_hourglasses(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &Hourglass,
+        &AccessibleHourglass,
+        Option<&mut AccessibilityNode>,
+    )>,
+) {
+    for (entity, hourglass, accessible, node) in query.iter_mut() {
+        let value = hourglass.format_remaining(&accessible.format);
+
+        match node {
+            Some(mut node) => {
+                if node.label() != Some(accessible.label.as_str()) {
+                    node.set_label(accessible.label.as_str());
+                }
+                if node.value() != Some(value.as_str()) {
+                    node.set_value(value);
+                }
+            }
+            None => {
+                let mut node = Node::new(Role::Timer);
+                node.set_label(accessible.label.as_str());
+                node.set_value(value);
+                commands.entity(entity).insert(AccessibilityNode(node));
+            }
+        }
+    }
+}
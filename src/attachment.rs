@@ -0,0 +1,99 @@
+//! Lets hourglasses follow other entities around.
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+
+/// Component that keeps an hourglass floating at an offset from another entity
+///
+/// Useful for buff or cooldown timers above a unit's head. The hourglass is despawned along with
+/// its target. Targets can't be attached hourglasses themselves.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HourglassAttachment {
+    /// Entity to follow
+    pub target: Entity,
+    /// Offset from the target's position
+    pub offset: Vec3,
+    /// Whether the hourglass (and its offset) rotate with the target
+    ///
+    /// The target's rotation around the Z axis becomes the hourglass' base rotation, so flips and
+    /// level sand keep working.
+    pub follow_rotation: bool,
+}
+
+impl HourglassAttachment {
+    /// Attach to a target at the given offset, without following its rotation
+    pub fn new(target: Entity, offset: Vec3) -> Self {
+        Self {
+            target,
+            offset,
+            follow_rotation: false,
+        }
+    }
+
+    /// Set whether the hourglass rotates with its target
+    pub fn with_follow_rotation(mut self, follow_rotation: bool) -> Self {
+        self.follow_rotation = follow_rotation;
+        self
+    }
+}
+
+/// Query data for the entities hourglasses can be attached to
+///
+/// Attached hourglasses can't be targets themselves, their transforms are being written.
+type AttachmentTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static GlobalTransform, Has<ChildOf>),
+    Without<HourglassAttachment>,
+>;
+
+/// System that moves attached hourglasses to their targets and despawns orphaned ones
+///
+/// Runs before transform propagation. Root targets are followed exactly; targets with a parent
+/// are followed through their global transform, which lags by a frame.
+pub fn follow_hourglass_attachments(
+    mut commands: Commands,
+    mut hourglasses: Query<(
+        Entity,
+        &HourglassAttachment,
+        &mut Transform,
+        Option<&mut Hourglass>,
+    )>,
+    targets: AttachmentTargetQuery,
+) {
+    for (entity, attachment, mut transform, hourglass) in hourglasses.iter_mut() {
+        if commands.get_entity(attachment.target).is_err() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Ok((target_transform, target_global, has_parent)) = targets.get(attachment.target)
+        else {
+            continue;
+        };
+
+        let (translation, rotation) = if has_parent {
+            let (_, rotation, translation) = target_global.to_scale_rotation_translation();
+            (translation, rotation)
+        } else {
+            (target_transform.translation, target_transform.rotation)
+        };
+
+        if !attachment.follow_rotation {
+            transform.translation = translation + attachment.offset;
+            continue;
+        }
+
+        transform.translation = translation + rotation * attachment.offset;
+        let (_, _, angle) = rotation.to_euler(EulerRot::XYZ);
+        match hourglass {
+            Some(mut hourglass) => {
+                if hourglass.base_rotation != angle {
+                    hourglass.base_rotation = angle;
+                }
+                transform.rotation =
+                    Quat::from_rotation_z(hourglass.base_rotation + hourglass.current_rotation);
+            }
+            None => transform.rotation = rotation,
+        }
+    }
+}
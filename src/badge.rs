@@ -0,0 +1,170 @@
+//! Numeric badges in a corner of hourglasses, e.g. stack counts of a charging ability.
+
+use crate::components::Hourglass;
+use crate::mesh::HourglassParts;
+use bevy::prelude::*;
+
+/// Corner of the hourglass a badge is centered on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BadgeCorner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl BadgeCorner {
+    /// Direction of the corner from the center, e.g. (1, 1) for the top right
+    pub fn as_vec(self) -> Vec2 {
+        match self {
+            BadgeCorner::TopLeft => Vec2::new(-1.0, 1.0),
+            BadgeCorner::TopRight => Vec2::new(1.0, 1.0),
+            BadgeCorner::BottomLeft => Vec2::new(-1.0, -1.0),
+            BadgeCorner::BottomRight => Vec2::new(1.0, -1.0),
+        }
+    }
+}
+
+/// Configuration of the badge shown by [`Hourglass::set_badge`], added with
+/// [`HourglassMeshBuilder::with_badge`](crate::HourglassMeshBuilder::with_badge)
+///
+/// Hourglasses without it use the default badge.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassBadgeConfig {
+    /// Corner the badge is centered on
+    pub corner: BadgeCorner,
+    /// Radius of the badge's circle (in pixels), widened for longer numbers
+    pub radius: f32,
+    /// Color of the circle
+    pub color: Color,
+    /// Color of the number
+    pub text_color: Color,
+    /// Font size of the number
+    pub font_size: f32,
+    /// Text shown before the number, e.g. "x" for "x3"
+    pub prefix: String,
+}
+
+impl Default for HourglassBadgeConfig {
+    fn default() -> Self {
+        Self {
+            corner: BadgeCorner::TopRight,
+            radius: 12.0,
+            color: Color::srgb(0.85, 0.2, 0.2),
+            text_color: Color::WHITE,
+            font_size: 16.0,
+            prefix: String::new(),
+        }
+    }
+}
+
+/// Component for the badge of an hourglass, its number being a [`Text2d`] child
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HourglassBadge {
+    /// Number shown on the badge
+    pub value: u32,
+}
+
+/// System showing, updating and hiding the badges of hourglasses
+///
+/// Badges are spawned the first time a number is set and hidden while it is `None`. Like labels,
+/// they stay upright and in their corner while the hourglass flips or rotates.
+pub fn update_hourglass_badges(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut hourglasses: Query<(
+        Entity,
+        &Hourglass,
+        &mut HourglassParts,
+        &Transform,
+        Option<&HourglassBadgeConfig>,
+    )>,
+    parts: Query<&Transform, (Without<Hourglass>, Without<HourglassBadge>)>,
+    mut badges: Query<
+        (
+            &mut HourglassBadge,
+            &mut Transform,
+            &mut Visibility,
+            &Mesh2d,
+            &Children,
+        ),
+        Without<Hourglass>,
+    >,
+    mut texts: Query<&mut Text2d>,
+) {
+    let default_config = HourglassBadgeConfig::default();
+
+    for (entity, hourglass, mut hourglass_parts, hourglass_transform, config) in
+        hourglasses.iter_mut()
+    {
+        let config = config.unwrap_or(&default_config);
+        let badge = hourglass_parts.badge;
+        let Some(value) = hourglass.badge else {
+            if let Some(Ok((_, _, mut visibility, _, _))) = badge.map(|badge| badges.get_mut(badge))
+            {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+            continue;
+        };
+
+        // The glass is centered on the visual, whatever the anchor of the hourglass
+        let center = hourglass_parts
+            .body
+            .and_then(|body| parts.get(body).ok())
+            .map_or(Vec2::ZERO, |body| body.translation.truncate());
+        let inverse = hourglass_transform.rotation.inverse();
+        let translation = center.extend(2.0)
+            + inverse * (config.corner.as_vec() * hourglass.size / 2.0).extend(0.0);
+        let text = format!("{}{value}", config.prefix);
+
+        let Some(Ok((mut badge, mut transform, mut visibility, mesh_handle, children))) =
+            badge.map(|badge| badges.get_mut(badge))
+        else {
+            let badge = commands
+                .spawn((
+                    HourglassBadge { value },
+                    Mesh2d(meshes.add(badge_mesh(config, &text))),
+                    MeshMaterial2d(materials.add(config.color)),
+                    Transform::from_translation(translation).with_rotation(inverse),
+                ))
+                .with_child((
+                    Text2d::new(text),
+                    TextFont {
+                        font_size: config.font_size,
+                        ..default()
+                    },
+                    TextColor(config.text_color),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ))
+                .id();
+            commands.entity(entity).add_child(badge);
+            hourglass_parts.badge = Some(badge);
+            continue;
+        };
+
+        transform.translation = translation;
+        transform.rotation = inverse;
+        visibility.set_if_neq(Visibility::Inherited);
+        if badge.value != value {
+            badge.value = value;
+            if let Some(badge_mesh) = meshes.get_mut(&mesh_handle.0) {
+                *badge_mesh = self::badge_mesh(config, &text);
+            }
+            for child in children.iter() {
+                if let Ok(mut child_text) = texts.get_mut(child) {
+                    child_text.0 = text.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Mesh of a badge, a circle stretched into a pill for longer texts
+fn badge_mesh(config: &HourglassBadgeConfig, text: &str) -> Mesh {
+    let extra_characters = text.chars().count().saturating_sub(1);
+    let length = extra_characters as f32 * config.font_size * 0.5;
+    Mesh::from(Capsule2d::new(config.radius, length))
+        .rotated_by(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+}
@@ -0,0 +1,253 @@
+//! CPU baking of hourglass styles into static images, e.g. for inventory icons.
+
+use crate::mesh::{
+    HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
+    HourglassMeshSandConfig, HourglassMeshSandState,
+};
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use hourglass_geometry::{
+    generate_sand_outline_with_params, point_in_polygon, HourglassShapeBuilder, Point2D, SandBulb,
+};
+
+/// Fill percentages baked by default with [`bake_hourglass_images`]
+pub const DEFAULT_BAKE_FILLS: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Settings for baking an hourglass into an image
+#[derive(Debug, Clone)]
+pub struct HourglassBakeConfig {
+    /// Size of the image in pixels
+    pub size: UVec2,
+    /// Empty space kept around the hourglass (in pixels)
+    pub padding: f32,
+    /// Color behind the hourglass
+    pub background: Color,
+    /// Samples per pixel along each axis, used for anti-aliasing
+    pub samples: u32,
+}
+
+impl Default for HourglassBakeConfig {
+    fn default() -> Self {
+        Self {
+            size: UVec2::splat(64),
+            padding: 2.0,
+            background: Color::NONE,
+            samples: 4,
+        }
+    }
+}
+
+impl HourglassBakeConfig {
+    /// Creates a bake config for a square image of the given size
+    pub fn new(size: u32) -> Self {
+        Self {
+            size: UVec2::splat(size),
+            ..default()
+        }
+    }
+
+    /// Sets the empty space kept around the hourglass
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the color behind the hourglass
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Sets the samples per pixel along each axis
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+}
+
+/// Bake an hourglass with the given style and fill percent into an image
+///
+/// The shape is rasterized on the CPU, so no renderer or camera is needed. The resulting image
+/// uses the `Rgba8UnormSrgb` format and can be added to `Assets<Image>` or saved as a PNG with
+/// `Image::try_into_dynamic`. The stream of falling sand is left out as the icon is static.
+pub fn bake_hourglass_image(
+    body: &HourglassMeshBodyConfig,
+    plates: Option<&HourglassMeshPlatesConfig>,
+    sand: &HourglassMeshSandConfig,
+    fill_percent: f32,
+    config: &HourglassBakeConfig,
+) -> Image {
+    let shapes = bake_shapes(body, plates, sand, fill_percent);
+
+    // Fit the whole hourglass in the image, keeping its aspect ratio
+    let visual_size = HourglassMeshBuilder::visual_size(body, plates);
+    let size = config.size.max(UVec2::ONE);
+    let available = (size.as_vec2() - 2.0 * config.padding).max(Vec2::ONE);
+    let scale = (available / visual_size.max(Vec2::splat(f32::EPSILON))).min_element();
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            ..default()
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+
+    let samples = config.samples.max(1);
+    let background = config.background.to_linear();
+    let half_size = size.as_vec2() / 2.0;
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let mut sum = Vec4::ZERO;
+            for sy in 0..samples {
+                for sx in 0..samples {
+                    let offset = (Vec2::new(sx as f32, sy as f32) + 0.5) / samples as f32;
+                    let pixel = Vec2::new(x as f32, y as f32) + offset;
+                    let point = [
+                        (pixel.x - half_size.x) / scale,
+                        (half_size.y - pixel.y) / scale,
+                    ];
+
+                    let mut color = background;
+                    for (polygon, layer_color) in &shapes {
+                        if point_in_polygon(point, polygon) {
+                            color = blend_over(*layer_color, color);
+                        }
+                    }
+                    // Premultiply so edges blend correctly against transparent backgrounds
+                    sum += Vec4::new(
+                        color.red * color.alpha,
+                        color.green * color.alpha,
+                        color.blue * color.alpha,
+                        color.alpha,
+                    );
+                }
+            }
+
+            let average = sum / (samples * samples) as f32;
+            let color = if average.w > 0.0 {
+                LinearRgba::new(
+                    average.x / average.w,
+                    average.y / average.w,
+                    average.z / average.w,
+                    average.w,
+                )
+            } else {
+                LinearRgba::NONE
+            };
+            image
+                .set_color_at(x, y, Color::LinearRgba(color))
+                .expect("pixel is inside the image");
+        }
+    }
+
+    image
+}
+
+/// Bake an hourglass at each of the given fill percentages, e.g. [`DEFAULT_BAKE_FILLS`]
+pub fn bake_hourglass_images(
+    body: &HourglassMeshBodyConfig,
+    plates: Option<&HourglassMeshPlatesConfig>,
+    sand: &HourglassMeshSandConfig,
+    fills: &[f32],
+    config: &HourglassBakeConfig,
+) -> Vec<Image> {
+    fills
+        .iter()
+        .map(|&fill_percent| bake_hourglass_image(body, plates, sand, fill_percent, config))
+        .collect()
+}
+
+/// Polygons to rasterize in drawing order, matching the layering of the spawned hourglass
+fn bake_shapes(
+    body: &HourglassMeshBodyConfig,
+    plates: Option<&HourglassMeshPlatesConfig>,
+    sand: &HourglassMeshSandConfig,
+    fill_percent: f32,
+) -> Vec<(Vec<Point2D>, LinearRgba)> {
+    let mut shapes = Vec::new();
+
+    let shape_builder = HourglassShapeBuilder {
+        total_height: body.total_height,
+        bulb_style: body.bulb_style.clone(),
+        neck_style: body.neck_style.clone(),
+    };
+    shapes.push((
+        shape_builder.generate_outline(),
+        body.material_color().to_linear(),
+    ));
+
+    // Reuse the sand state so the baked sand matches the live hourglass
+    let fill_percent = fill_percent.clamp(0.0, 1.0);
+    let sand_config = HourglassMeshSandConfig {
+        fill_percent,
+        color: sand.color_at(fill_percent),
+        ..sand.clone()
+    };
+    let sand_state = HourglassMeshSandState {
+        fill_percent,
+        body_config: body.clone(),
+        sand_config: sand_config.clone(),
+        tilt: 0.0,
+        surface_phase: 0.0,
+        center_offset: Vec2::ZERO,
+        flips: 0,
+        built_sand_config: sand_config,
+        needs_update: false,
+    };
+    let sand_outline = shape_builder.generate_outline_with_wall_offset(sand.wall_offset);
+    for bulb in [SandBulb::Top, SandBulb::Bottom] {
+        let mut params = sand_state.sand_outline_params(bulb);
+        params.solid_stream = false;
+        let points = generate_sand_outline_with_params(&sand_outline, &params);
+        if !points.is_empty() {
+            shapes.push((
+                points,
+                sand_state.sand_config.bulb_material_color(bulb).to_linear(),
+            ));
+        }
+    }
+
+    if let Some(plates) = plates {
+        let half_width = plates.width / 2.0;
+        let half_total_height = body.total_height / 2.0;
+        for (bottom, top) in [
+            (half_total_height, half_total_height + plates.height),
+            (-half_total_height - plates.height, -half_total_height),
+        ] {
+            shapes.push((
+                vec![
+                    [-half_width, bottom],
+                    [half_width, bottom],
+                    [half_width, top],
+                    [-half_width, top],
+                ],
+                plates.color.to_linear(),
+            ));
+        }
+    }
+
+    shapes
+}
+
+/// Composite a color over another using straight alpha
+fn blend_over(top: LinearRgba, bottom: LinearRgba) -> LinearRgba {
+    let top_alpha = top.alpha.clamp(0.0, 1.0);
+    let alpha = top_alpha + bottom.alpha * (1.0 - top_alpha);
+    if alpha <= 0.0 {
+        return LinearRgba::NONE;
+    }
+    let mix = |t: f32, b: f32| (t * top_alpha + b * bottom.alpha * (1.0 - top_alpha)) / alpha;
+    LinearRgba::new(
+        mix(top.red, bottom.red),
+        mix(top.green, bottom.green),
+        mix(top.blue, bottom.blue),
+        alpha,
+    )
+}
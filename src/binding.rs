@@ -0,0 +1,110 @@
+//! Hourglasses used as gauges, following a value read from the world, e.g. a player's stamina.
+
+use crate::components::{Hourglass, HourglassValueSource};
+use bevy::prelude::*;
+
+/// Function reading the value of a binding, `None` keeping the current fill
+type BindingValue = Box<dyn Fn(&World) -> Option<f32> + Send + Sync>;
+
+/// Component driving the fill of an hourglass from a value evaluated every frame
+///
+/// The value is the fill of the upper chamber (0.0 - 1.0). The sand flows towards it at up to
+/// `max_flow_rate`, so increases and decreases are both visible as sand moving, with the stream
+/// rising while the value goes up. The hourglass becomes [`HourglassValueSource::External`].
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_hourglass::HourglassBinding;
+///
+/// #[derive(Resource)]
+/// struct Stamina {
+///     current: f32,
+///     max: f32,
+/// }
+///
+/// let binding = HourglassBinding::resource(|stamina: &Stamina| stamina.current / stamina.max);
+/// ```
+#[derive(Component)]
+pub struct HourglassBinding {
+    value: BindingValue,
+    /// Fastest change of the fill per second, 0.0 jumps to new values right away
+    pub max_flow_rate: f32,
+}
+
+impl HourglassBinding {
+    /// Binds to a value computed from the world
+    pub fn new(value: impl Fn(&World) -> f32 + Send + Sync + 'static) -> Self {
+        Self::from_option(move |world| Some(value(world)))
+    }
+
+    /// Binds to a value computed from the world, keeping the current fill while it is `None`
+    pub fn from_option(value: impl Fn(&World) -> Option<f32> + Send + Sync + 'static) -> Self {
+        Self {
+            value: Box::new(value),
+            max_flow_rate: 0.5,
+        }
+    }
+
+    /// Binds to a value computed from a resource, keeping the current fill while it is missing
+    pub fn resource<R: Resource>(value: impl Fn(&R) -> f32 + Send + Sync + 'static) -> Self {
+        Self::from_option(move |world| world.get_resource::<R>().map(&value))
+    }
+
+    /// Binds to a value computed from a component of an entity, keeping the current fill while
+    /// it is missing
+    pub fn component<C: Component>(
+        entity: Entity,
+        value: impl Fn(&C) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        Self::from_option(move |world| world.get::<C>(entity).map(&value))
+    }
+
+    /// Sets the fastest change of the fill per second
+    pub fn with_max_flow_rate(mut self, max_flow_rate: f32) -> Self {
+        self.max_flow_rate = max_flow_rate;
+        self
+    }
+
+    /// Evaluate the bound value in the given world
+    pub fn evaluate(&self, world: &World) -> Option<f32> {
+        (self.value)(world).filter(|value| !value.is_nan())
+    }
+}
+
+/// System moving the sand of bound hourglasses towards their values
+///
+/// While the fill moves the hourglass is running, with the nominal flow rate of its total time
+/// made negative while it rises, so the stream follows the sand.
+pub fn apply_hourglass_bindings(world: &mut World) {
+    let delta = world.resource::<Time>().delta_secs();
+    let mut query = world.query::<(Entity, &HourglassBinding)>();
+    let targets: Vec<(Entity, Option<f32>, f32)> = query
+        .iter(world)
+        .map(|(entity, binding)| (entity, binding.evaluate(world), binding.max_flow_rate))
+        .collect();
+
+    for (entity, target, max_flow_rate) in targets {
+        let Some(mut hourglass) = world.get_mut::<Hourglass>(entity) else {
+            continue;
+        };
+        hourglass.value_source = HourglassValueSource::External;
+
+        let current = hourglass.upper_chamber;
+        let target = target.map_or(current, |target| target.clamp(0.0, 1.0));
+        let fill = if max_flow_rate > 0.0 {
+            current + (target - current).clamp(-max_flow_rate * delta, max_flow_rate * delta)
+        } else {
+            target
+        };
+
+        let moving = fill != current;
+        if moving {
+            hourglass.set_fill(fill);
+            hourglass.flow_rate =
+                (current - fill).signum() / hourglass.total_time.max(f32::EPSILON);
+        }
+        if hourglass.running != moving {
+            hourglass.running = moving;
+        }
+    }
+}
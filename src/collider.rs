@@ -0,0 +1,257 @@
+//! Physics colliders for hourglasses, e.g. to knock them over in physics-driven games.
+//!
+//! The outline is split into convex parts that any 2D physics engine accepts. A
+//! [`ColliderBackend`] turns them into the components of a specific engine such as
+//! `bevy_rapier2d` or `avian2d`.
+
+use crate::mesh::{HourglassMeshShape, RenderStyle};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use earcutr::earcut;
+use hourglass_geometry::HourglassShapeBuilder;
+use std::sync::Arc;
+
+/// How the outline of the glass is split into convex parts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColliderShape {
+    /// Hulls of the top bulb, the neck and the bottom bulb, plus the plates
+    #[default]
+    Compound,
+    /// Exact outline split into as few convex polygons as the triangulation allows, plus the
+    /// plates
+    ConvexDecomposition,
+}
+
+/// Component holding the convex parts of the collider of an hourglass
+///
+/// Parts are counter-clockwise polygons relative to the hourglass entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HourglassCollider {
+    pub shape: ColliderShape,
+    pub parts: Vec<Vec<Vec2>>,
+}
+
+/// Physics engine integration, inserting colliders built from the convex parts of hourglasses
+///
+/// ```ignore
+/// use bevy_rapier2d::prelude::*;
+///
+/// struct Rapier;
+///
+/// impl ColliderBackend for Rapier {
+///     fn insert_collider(&self, entity: &mut EntityCommands, collider: &HourglassCollider) {
+///         let parts = collider
+///             .parts
+///             .iter()
+///             .filter_map(|part| Collider::convex_hull(part))
+///             .map(|part| (Vec2::ZERO, 0.0, part))
+///             .collect();
+///         entity.insert((RigidBody::Dynamic, Collider::compound(parts)));
+///     }
+/// }
+/// ```
+pub trait ColliderBackend: Send + Sync + 'static {
+    /// How the outline is split, [`ColliderShape::Compound`] by default
+    fn shape(&self) -> ColliderShape {
+        ColliderShape::Compound
+    }
+
+    /// Insert the engine's collider components on the hourglass entity
+    fn insert_collider(&self, entity: &mut EntityCommands, collider: &HourglassCollider);
+}
+
+/// Component keeping the backend of an hourglass' collider, so resized hourglasses get a new one
+#[derive(Component, Clone)]
+pub struct HourglassColliderBackend {
+    backend: Arc<dyn ColliderBackend>,
+    /// Position of the hourglass' center relative to the entity
+    center: Vec2,
+}
+
+impl HourglassColliderBackend {
+    pub(crate) fn new(backend: Arc<dyn ColliderBackend>, center: Vec2) -> Self {
+        Self { backend, center }
+    }
+
+    /// Build the collider of the given shape, inserting it through the backend
+    pub(crate) fn insert_collider(&self, entity: &mut EntityCommands, shape: &HourglassMeshShape) {
+        let collider = HourglassCollider {
+            shape: self.backend.shape(),
+            parts: shape
+                .collider_parts(self.backend.shape())
+                .into_iter()
+                .map(|part| part.into_iter().map(|point| point + self.center).collect())
+                .collect(),
+        };
+        self.backend.insert_collider(entity, &collider);
+        entity.insert(collider);
+    }
+
+    /// Scale the offset of the collider along with the hourglass
+    pub(crate) fn scale(&mut self, factor: f32) {
+        self.center *= factor;
+    }
+}
+
+impl HourglassMeshShape {
+    /// Convex parts of the hourglass, centered on the glass with the plates resting against it
+    ///
+    /// Minimal hourglasses have no plates.
+    pub fn collider_parts(&self, shape: ColliderShape) -> Vec<Vec<Vec2>> {
+        let body = &self.body_config;
+        let outline: Vec<Vec2> = HourglassShapeBuilder {
+            total_height: body.total_height,
+            bulb_style: body.bulb_style.clone(),
+            neck_style: body.neck_style.clone(),
+        }
+        .generate_outline()
+        .into_iter()
+        .map(Vec2::from)
+        .collect();
+
+        let mut parts = match shape {
+            ColliderShape::Compound => {
+                let neck_top = body.neck_style.height() / 2.0;
+                [
+                    outline
+                        .iter()
+                        .filter(|point| point.y >= neck_top)
+                        .copied()
+                        .collect::<Vec<_>>(),
+                    outline
+                        .iter()
+                        .filter(|point| point.y.abs() <= neck_top)
+                        .copied()
+                        .collect(),
+                    outline
+                        .iter()
+                        .filter(|point| point.y <= -neck_top)
+                        .copied()
+                        .collect(),
+                ]
+                .iter()
+                .map(|points| convex_hull(points))
+                .filter(|hull| hull.len() >= 3)
+                .collect()
+            }
+            ColliderShape::ConvexDecomposition => convex_decomposition(&outline),
+        };
+
+        if let (Some(plates), RenderStyle::Filled) = (&self.plates_config, self.render_style) {
+            let half_size = Vec2::new(plates.width, plates.height) / 2.0;
+            for direction in [1.0, -1.0] {
+                let center = Vec2::Y * direction * (body.total_height / 2.0 + half_size.y);
+                parts.push(vec![
+                    center + Vec2::new(-half_size.x, -half_size.y),
+                    center + Vec2::new(half_size.x, -half_size.y),
+                    center + Vec2::new(half_size.x, half_size.y),
+                    center + Vec2::new(-half_size.x, half_size.y),
+                ]);
+            }
+        }
+        parts
+    }
+}
+
+/// Counter-clockwise convex hull of a set of points
+pub fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Monotone chain, building the lower then the upper half
+    let mut hull: Vec<Vec2> = Vec::with_capacity(sorted.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        for &point in &sorted {
+            while hull.len() >= start + 2
+                && (hull[hull.len() - 1] - hull[hull.len() - 2])
+                    .perp_dot(point - hull[hull.len() - 2])
+                    <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+        if pass == 0 {
+            sorted.reverse();
+        }
+    }
+    hull
+}
+
+/// Split a simple polygon into convex polygons by merging the triangles of its triangulation
+///
+/// Triangles sharing an edge are merged for as long as the result stays convex.
+pub fn convex_decomposition(outline: &[Vec2]) -> Vec<Vec<Vec2>> {
+    let coords: Vec<f32> = outline
+        .iter()
+        .flat_map(|point| [point.x, point.y])
+        .collect();
+    let Ok(triangles) = earcut(&coords, &[], 2) else {
+        return Vec::new();
+    };
+
+    // Polygons as counter-clockwise indices into the outline
+    let mut polygons: Vec<Vec<usize>> = triangles
+        .chunks_exact(3)
+        .map(|triangle| {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            if (outline[b] - outline[a]).perp_dot(outline[c] - outline[a]) < 0.0 {
+                vec![a, c, b]
+            } else {
+                vec![a, b, c]
+            }
+        })
+        .collect();
+
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in 0..polygons.len() {
+            for j in i + 1..polygons.len() {
+                if let Some(polygon) = merge_convex(&polygons[i], &polygons[j], outline) {
+                    polygons[i] = polygon;
+                    polygons.swap_remove(j);
+                    merged = true;
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    polygons
+        .into_iter()
+        .map(|polygon| polygon.into_iter().map(|index| outline[index]).collect())
+        .collect()
+}
+
+/// Merge two counter-clockwise polygons sharing an edge, if the result is convex
+fn merge_convex(first: &[usize], second: &[usize], outline: &[Vec2]) -> Option<Vec<usize>> {
+    // The shared edge runs a -> b in the first polygon and b -> a in the second
+    let (start, a, b) = (0..first.len()).find_map(|i| {
+        let (a, b) = (first[i], first[(i + 1) % first.len()]);
+        (0..second.len())
+            .any(|k| second[k] == b && second[(k + 1) % second.len()] == a)
+            .then_some((i, a, b))
+    })?;
+
+    // Walk the first polygon from b around to a, then the second from a to b without the ends
+    let mut polygon: Vec<usize> = (1..=first.len())
+        .map(|offset| first[(start + offset) % first.len()])
+        .collect();
+    let second_start = second.iter().position(|&index| index == a)?;
+    polygon
+        .extend((1..second.len() - 1).map(|offset| second[(second_start + offset) % second.len()]));
+    debug_assert_eq!(polygon.first(), Some(&b));
+
+    let is_convex = (0..polygon.len()).all(|i| {
+        let [p0, p1, p2] = [0, 1, 2].map(|k| outline[polygon[(i + k) % polygon.len()]]);
+        (p1 - p0).perp_dot(p2 - p1) >= -1e-4
+    });
+    is_convex.then_some(polygon)
+}
@@ -1,4 +1,10 @@
+use crate::format::{format_time, TimeFormat};
+use crate::units::SizeUnits;
+use crate::warnings::{report_warning, HourglassWarning};
 use bevy::prelude::*;
+use hourglass_geometry::SandBulb;
+use rand::{rngs::StdRng, SeedableRng};
+use std::num::NonZeroU32;
 
 /// Core component for an hourglass
 #[derive(Component, Debug, Clone)]
@@ -10,9 +16,13 @@ pub struct Hourglass {
     pub remaining_time: f32,
     /// Whether the hourglass is currently running
     pub running: bool,
+    /// What drives the sand levels, the internal timer or an external value
+    pub value_source: HourglassValueSource,
+    /// Multiplier applied to elapsed time, e.g. 0.5 for slow motion or 2.0 for haste
+    pub speed: f32,
 
     // State properties
-    /// Whether the hourglass is currently flipped (upside down)
+    /// Whether the hourglass is currently flipped (upside down), toggled when a flip completes
     pub flipped: bool,
     /// Whether the hourglass is currently in the process of flipping
     pub flipping: bool,
@@ -20,11 +30,44 @@ pub struct Hourglass {
     pub flip_duration: f32,
     /// Current flip progress (0.0 to 1.0)
     pub flip_progress: f32,
+    /// Whether a flip was started since the last update, reported by the plugin with
+    /// `HourglassFlipStartEvent`
+    pub flip_started: bool,
     /// Whether this hourglass should auto-flip when empty
     pub auto_flip_when_empty: bool,
+    /// Easing applied to the flip rotation
+    pub flip_easing: EaseFunction,
+    /// Whether sand keeps flowing while the hourglass is flipping
+    pub update_during_flip: bool,
+    /// Whether sand is currently flowing back up into the upper chamber
+    pub rewinding: bool,
+    /// Duration of the rewind animation in seconds
+    pub rewind_duration: f32,
+    /// Current rewind progress (0.0 to 1.0)
+    pub rewind_progress: f32,
+    /// Fill of the upper chamber when the rewind started
+    pub rewind_from: f32,
+    /// Duration of the sand transition after time was added or removed (in seconds)
+    pub adjust_duration: f32,
+    /// Progress of the sand transition after time was added or removed (0.0 to 1.0)
+    pub adjust_progress: f32,
+    /// Difference between the shown and the actual upper chamber fill when time was adjusted
+    pub adjust_offset: f32,
+    /// Time added or removed since the last update (in seconds), reported by the plugin with
+    /// `HourglassTimeAdjustedEvent`
+    pub pending_time_adjustment: f32,
+    /// Time added or removed during a flip or rewind (in seconds), applied once it completes
+    pub queued_time_adjustment: f32,
+    /// What happens to time added beyond the total time
+    pub overflow_policy: TimeOverflowPolicy,
+    /// Time that did not fit since the last update (in seconds), reported by the plugin with
+    /// `HourglassTimeOverflowEvent` under [`TimeOverflowPolicy::EmitOverflowEvent`]
+    pub pending_overflow: f32,
 
     // Rotation properties
-    /// Current rotation in radians
+    /// Resting rotation in radians, flips rotate relative to it
+    pub base_rotation: f32,
+    /// Current flip rotation in radians, relative to `base_rotation`
     pub current_rotation: f32,
     /// Target rotation for flip animation
     pub target_rotation: f32,
@@ -34,7 +77,7 @@ pub struct Hourglass {
     pub container_color: Color,
     /// Color of the sand
     pub sand_color: Color,
-    /// Size of the hourglass
+    /// Overall size of the hourglass (set by the mesh builder from its body and plates)
     pub size: Vec2,
 
     // Sand properties
@@ -44,6 +87,38 @@ pub struct Hourglass {
     pub lower_chamber: f32,
     /// Flow rate in percentage per second
     pub flow_rate: f32,
+
+    /// Lifetime statistics, e.g. for achievements
+    pub stats: HourglassStats,
+
+    /// Number shown on the badge of the hourglass, e.g. a stack count
+    pub badge: Option<u32>,
+}
+
+/// What happens to time that doesn't fit in an [`Hourglass`] when adding or removing time
+///
+/// Time removed beyond zero is always dropped, as the hourglass can't hold less than no sand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeOverflowPolicy {
+    /// Time beyond the capacity of the hourglass is dropped
+    #[default]
+    ClampSilently,
+    /// Time beyond the capacity is dropped and reported with `HourglassTimeOverflowEvent`
+    EmitOverflowEvent,
+    /// The total time grows to hold all the time, refilling the upper chamber and slowing the
+    /// flow to match
+    ExtendTotalTime,
+}
+
+/// Lifetime statistics of an [`Hourglass`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HourglassStats {
+    /// Number of completed flips
+    pub flips: u32,
+    /// Number of times the upper chamber ran empty
+    pub empties: u32,
+    /// Total time the sand was flowing (in seconds of hourglass time, so scaled by its speed)
+    pub elapsed: f32,
 }
 
 impl Default for Hourglass {
@@ -53,15 +128,32 @@ impl Default for Hourglass {
             total_time: 60.0,
             remaining_time: 60.0,
             running: true,
+            value_source: HourglassValueSource::Timer,
+            speed: 1.0,
 
             // State properties
             flipped: false,
             flipping: false,
             flip_duration: 1.0,
             flip_progress: 0.0,
+            flip_started: false,
             auto_flip_when_empty: false,
+            flip_easing: EaseFunction::Linear,
+            update_during_flip: false,
+            rewinding: false,
+            rewind_duration: 1.0,
+            rewind_progress: 0.0,
+            rewind_from: 0.0,
+            adjust_duration: 0.3,
+            adjust_progress: 1.0,
+            adjust_offset: 0.0,
+            pending_time_adjustment: 0.0,
+            queued_time_adjustment: 0.0,
+            overflow_policy: TimeOverflowPolicy::default(),
+            pending_overflow: 0.0,
 
             // Rotation properties
+            base_rotation: 0.0,
             current_rotation: 0.0,
             target_rotation: 0.0,
 
@@ -74,12 +166,158 @@ impl Default for Hourglass {
             upper_chamber: 1.0,
             lower_chamber: 0.0,
             flow_rate: 1.0 / 60.0,
+
+            stats: HourglassStats::default(),
+            badge: None,
+        }
+    }
+}
+
+/// Nonzero duration of an hourglass in whole seconds
+///
+/// The constructors are `const`, so zero durations are rejected at compile time when used in a
+/// const context (and panic otherwise):
+///
+/// ```compile_fail
+/// use bevy_hourglass::HourglassDuration;
+///
+/// const NOTHING: HourglassDuration = HourglassDuration::seconds(0);
+/// ```
+///
+/// ```
+/// use bevy_hourglass::HourglassDuration;
+///
+/// const EGG_TIMER: HourglassDuration = HourglassDuration::minutes(3);
+/// assert_eq!(EGG_TIMER.as_secs(), 180);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HourglassDuration(NonZeroU32);
+
+impl HourglassDuration {
+    /// A 25 minute pomodoro work session
+    pub const POMODORO: Self = Self::minutes(25);
+
+    /// Duration of the given number of seconds, which must not be zero
+    pub const fn seconds(seconds: u32) -> Self {
+        match NonZeroU32::new(seconds) {
+            Some(seconds) => Self(seconds),
+            None => panic!("hourglass duration must not be zero"),
+        }
+    }
+
+    /// Duration of the given number of minutes, which must not be zero or overflow `u32` seconds
+    pub const fn minutes(minutes: u32) -> Self {
+        match minutes.checked_mul(60) {
+            Some(seconds) => Self::seconds(seconds),
+            None => panic!("hourglass duration in minutes overflows u32 seconds"),
+        }
+    }
+
+    /// Length of the duration in seconds
+    pub const fn as_secs(self) -> u32 {
+        self.0.get()
+    }
+
+    /// Length of the duration in seconds, as used by [`Hourglass::total_time`]
+    pub fn as_secs_f32(self) -> f32 {
+        self.0.get() as f32
+    }
+}
+
+/// Source of the sand levels of an [`Hourglass`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HourglassValueSource {
+    /// Sand flows over time and the hourglass works as a timer
+    #[default]
+    Timer,
+    /// Sand levels are only changed through [`Hourglass::set_fill`], e.g. to use the hourglass
+    /// as a gauge for health or other resources
+    External,
+}
+
+/// One of the two bulbs of the glass, following it through flips
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bulb {
+    /// The bulb on top when the hourglass was spawned
+    First,
+    /// The bulb at the bottom when the hourglass was spawned
+    Second,
+}
+
+/// Marker component on hourglasses whose upper chamber is empty, e.g. to filter queries with
+/// `With<HourglassEmpty>`
+///
+/// Inserted and removed by the plugin, so it is only added or removed once per emptying.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HourglassEmpty;
+
+/// Component that slowly spins an hourglass around its base rotation, e.g. for menu decoration
+///
+/// Sand surfaces stay level with gravity while the hourglass turns.
+#[derive(Component, Debug, Clone)]
+pub struct ContinuousRotation {
+    /// Angular velocity in radians per second (positive is counter-clockwise)
+    pub angular_velocity: f32,
+}
+
+impl ContinuousRotation {
+    /// Create a continuous rotation with the given angular velocity in radians per second
+    pub fn new(angular_velocity: f32) -> Self {
+        Self { angular_velocity }
+    }
+}
+
+/// Component that hangs an hourglass from a pivot above it and swings it gently, e.g. as a
+/// pendulum decoration
+///
+/// The swing is added to the base rotation and the translation, so sand surfaces stay level and
+/// the hourglass can still be moved or rotated on top of it.
+#[derive(Component, Debug, Clone)]
+pub struct SwingMount {
+    /// Largest angle of the swing in radians
+    pub amplitude: f32,
+    /// Swings per second
+    pub frequency: f32,
+    /// Distance from the pivot down to the hourglass' center in pixels
+    pub length: f32,
+    /// Time spent swinging (in seconds)
+    pub elapsed: f32,
+    /// Swing angle applied so far, removed again before applying the next one
+    pub angle: f32,
+}
+
+impl SwingMount {
+    /// Create a swing with the given amplitude in radians and frequency in swings per second
+    pub fn new(amplitude: f32, frequency: f32) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            length: 100.0,
+            elapsed: 0.0,
+            angle: 0.0,
         }
     }
+
+    /// Set the distance from the pivot down to the hourglass' center in pixels
+    pub fn with_length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Offset of the hourglass' center from where it hangs at rest, for a swing angle
+    pub fn offset_at(&self, angle: f32) -> Vec2 {
+        Vec2::new(angle.sin(), 1.0 - angle.cos()) * self.length
+    }
+}
+
+impl Default for SwingMount {
+    fn default() -> Self {
+        Self::new(10f32.to_radians(), 0.5)
+    }
 }
 
 /// Configuration for sand splash animation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SandSplashConfig {
     /// Radius around impact point where sand particles appear
     pub splash_radius: f32,
@@ -95,6 +333,8 @@ pub struct SandSplashConfig {
     pub particle_size: f32,
     /// Vertical offset of splash particles from the impact point
     pub vertical_offset: f32,
+    /// Units of the splash radius and particle size, the vertical offset is always local
+    pub size_units: SizeUnits,
 }
 
 impl Default for SandSplashConfig {
@@ -107,12 +347,16 @@ impl Default for SandSplashConfig {
             particle_color: Color::srgb(0.8, 0.6, 0.2),
             particle_size: 1.0,
             vertical_offset: 5.0, // Slightly above the bottom plate
+            size_units: SizeUnits::World,
         }
     }
 }
 
 /// Component that tracks sand splash state for an hourglass
+///
+/// Splashes are spawned at the [`SandImpactPoint`] placed by the hourglass' rendering backend.
 #[derive(Component, Debug, Clone)]
+#[require(SandImpactPoint)]
 pub struct SandSplash {
     pub config: SandSplashConfig,
     /// Timer for spawning new splash particles
@@ -131,6 +375,29 @@ impl SandSplash {
     }
 }
 
+/// World position where the falling sand hits the sand in the bottom bulb, `None` while no sand
+/// is falling
+///
+/// Sand splashes are spawned here, whatever backend draws the hourglass. The mesh backend places
+/// it for mesh hourglasses; sprite, UI or 3D backends get splashes as well by setting it from
+/// their own systems running before [`HourglassRenderSet`](crate::HourglassRenderSet).
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct SandImpactPoint(pub Option<Vec3>);
+
+/// Seeded random number generator placing the sand splash particles of an hourglass
+///
+/// Without it particles are placed at random, so splashes differ between runs. Seeding makes
+/// them reproducible, e.g. for replays and tests.
+#[derive(Component, Debug, Clone)]
+pub struct SandSplashRng(pub StdRng);
+
+impl SandSplashRng {
+    /// Creates a generator from a seed
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
 /// Marker component for sand splash particles
 #[derive(Component, Debug)]
 pub struct SandSplashParticle {
@@ -138,6 +405,157 @@ pub struct SandSplashParticle {
     pub lifetime: f32,
 }
 
+/// Configuration for the glitter effect at the neck of the hourglass
+#[derive(Debug, Clone)]
+pub struct NeckSparkleConfig {
+    /// Number of sparkles spawned each interval
+    pub particle_count: u32,
+    /// Duration each sparkle twinkles for (in seconds)
+    pub particle_duration: f32,
+    /// How often to spawn new sparkles (in seconds)
+    pub spawn_interval: f32,
+    /// Color of the sparkles at their brightest
+    pub particle_color: Color,
+    /// Size of each sparkle
+    pub particle_size: f32,
+    /// How far below the neck sparkles can appear, following the falling sand
+    pub spread: f32,
+    /// Units of the particle size, the spread is always local
+    pub size_units: SizeUnits,
+}
+
+impl Default for NeckSparkleConfig {
+    fn default() -> Self {
+        Self {
+            particle_count: 1,
+            particle_duration: 0.4,
+            spawn_interval: 0.15,
+            particle_color: Color::srgb(1.0, 0.95, 0.8),
+            particle_size: 1.5,
+            spread: 12.0,
+            size_units: SizeUnits::World,
+        }
+    }
+}
+
+/// Component that tracks the neck sparkle state for an hourglass
+#[derive(Component, Debug, Clone)]
+pub struct NeckSparkle {
+    pub config: NeckSparkleConfig,
+    /// Timer for spawning new sparkles
+    pub spawn_timer: f32,
+}
+
+impl NeckSparkle {
+    pub fn new(config: NeckSparkleConfig) -> Self {
+        Self {
+            config,
+            spawn_timer: 0.0,
+        }
+    }
+}
+
+/// Component for neck sparkle particles, which fade in and out over their lifetime
+///
+/// Sparkles also carry a [`SandSplashParticle`] so they expire like splash particles.
+#[derive(Component, Debug)]
+pub struct NeckSparkleParticle {
+    /// Total time the sparkle is visible for
+    pub duration: f32,
+    /// Brightest color of the sparkle
+    pub color: Color,
+}
+
+/// Configuration for dust motes drifting in the empty parts of the bulbs
+#[derive(Debug, Clone)]
+pub struct DustMotesConfig {
+    /// Number of motes per 100x100 pixels of empty glass
+    pub density: f32,
+    /// Drifting speed of the motes in pixels per second
+    pub speed: f32,
+    /// Size of each mote
+    pub size: f32,
+    /// Color of the motes
+    pub color: Color,
+}
+
+impl Default for DustMotesConfig {
+    fn default() -> Self {
+        Self {
+            density: 1.5,
+            speed: 6.0,
+            size: 1.5,
+            color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+        }
+    }
+}
+
+/// A single dust mote, in the hourglass' local space
+#[derive(Debug, Clone, Copy)]
+pub struct DustMote {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+/// Component for the child entity drawing the dust motes of an hourglass
+#[derive(Component, Debug, Clone)]
+pub struct DustMotes {
+    pub config: DustMotesConfig,
+    /// Motes currently drifting in the glass
+    pub motes: Vec<DustMote>,
+}
+
+impl DustMotes {
+    pub fn new(config: DustMotesConfig) -> Self {
+        Self {
+            config,
+            motes: Vec::new(),
+        }
+    }
+}
+
+/// Configuration for the pile of sand spilling around the bottom plate once the hourglass is empty
+#[derive(Debug, Clone)]
+pub struct SandOverflowConfig {
+    /// Width of the fully grown pile relative to the bottom plate (or the glass without plates),
+    /// values above 1.0 spill over its edges
+    pub overflow_factor: f32,
+    /// Time for the pile to grow to its full size after the hourglass empties (in seconds)
+    pub grow_duration: f32,
+    /// Color of the pile, falling back to the color of the bottom sand
+    pub color: Option<Color>,
+}
+
+impl Default for SandOverflowConfig {
+    fn default() -> Self {
+        Self {
+            overflow_factor: 1.2,
+            grow_duration: 3.0,
+            color: None,
+        }
+    }
+}
+
+/// Component for the child entity drawing the sand overflowing an empty hourglass
+///
+/// The pile grows while the hourglass stays empty and shrinks away once sand flows again. It is
+/// hidden during flips.
+#[derive(Component, Debug, Clone)]
+pub struct SandOverflow {
+    pub config: SandOverflowConfig,
+    /// How grown the pile is, from 0.0 (gone) to 1.0 (full size)
+    pub growth: f32,
+}
+
+impl SandOverflow {
+    pub fn new(config: SandOverflowConfig) -> Self {
+        Self {
+            config,
+            growth: 0.0,
+        }
+    }
+}
+
 impl Hourglass {
     /// Create a new hourglass with the specified total time in seconds
     pub fn new(total_time: f32) -> Self {
@@ -158,8 +576,49 @@ impl Hourglass {
         }
     }
 
+    /// Create a new hourglass running for the given duration
+    pub fn from_duration(duration: HourglassDuration) -> Self {
+        Self::new(duration.as_secs_f32())
+    }
+
+    /// Create a new hourglass running for the given number of seconds (must not be zero)
+    pub fn seconds(seconds: u32) -> Self {
+        Self::from_duration(HourglassDuration::seconds(seconds))
+    }
+
+    /// Create a new hourglass running for the given number of minutes (must not be zero)
+    pub fn minutes(minutes: u32) -> Self {
+        Self::from_duration(HourglassDuration::minutes(minutes))
+    }
+
+    /// Create a new hourglass timing a 25 minute pomodoro work session
+    pub fn pomodoro() -> Self {
+        Self::from_duration(HourglassDuration::POMODORO)
+    }
+
     /// Update the hourglass state
     pub fn update(&mut self, delta: f32) {
+        // Scale time by this hourglass' own speed, independently of the global clock
+        let delta = delta * self.speed.max(0.0);
+
+        // Recover from float error or out of range values written from outside
+        self.sanitize();
+
+        // Apply time added or removed during a flip or rewind now that it completed
+        if !self.flipping && !self.rewinding && self.queued_time_adjustment != 0.0 {
+            let queued = std::mem::take(&mut self.queued_time_adjustment);
+            self.add_time(queued);
+        }
+
+        // Ease the shown sand towards its level after time was added or removed
+        if self.adjust_progress < 1.0 {
+            self.adjust_progress = if self.adjust_duration > 0.0 {
+                (self.adjust_progress + delta / self.adjust_duration).min(1.0)
+            } else {
+                1.0
+            };
+        }
+
         // Handle flip animation first
         if self.flipping {
             self.flip_progress += delta / self.flip_duration;
@@ -168,6 +627,8 @@ impl Hourglass {
                 // Flip animation complete
                 self.flip_progress = 1.0;
                 self.flipping = false;
+                self.flipped = !self.flipped;
+                self.stats.flips += 1;
 
                 // Snap back to upright orientation
                 self.current_rotation = 0.0;
@@ -175,24 +636,43 @@ impl Hourglass {
 
                 // Invert the sand fill percentages (flip effect)
                 std::mem::swap(&mut self.upper_chamber, &mut self.lower_chamber);
+                self.adjust_progress = 1.0;
 
                 // Invert the timer (if 2s left in a 10s timer, it should read 8s after flipping)
                 self.remaining_time = self.total_time - self.remaining_time;
 
                 // Always ensure the timer is running if there's sand in the upper chamber
-                if !self.running && self.upper_chamber > 0.0 {
+                if !self.running
+                    && self.upper_chamber > 0.0
+                    && self.value_source == HourglassValueSource::Timer
+                {
                     self.running = true;
                 }
             } else {
                 // Interpolate rotation during flip (always from 0 to PI)
-                self.current_rotation = self.flip_progress * std::f32::consts::PI;
+                self.current_rotation =
+                    self.flip_easing.sample_clamped(self.flip_progress) * std::f32::consts::PI;
             }
         }
 
-        // Only update sand levels and time if not flipping
-        if self.running && !self.flipping {
-            // Update sand flow
+        // Rewinding sand flows back up and replaces the regular flow
+        if self.rewinding {
+            self.update_rewind(delta);
+            return;
+        }
+
+        // Only update sand levels and time if not flipping (unless configured to keep flowing)
+        // Externally driven hourglasses only change through `set_fill`
+        if self.running
+            && self.value_source == HourglassValueSource::Timer
+            && (!self.flipping || self.update_during_flip)
+        {
+            // Update sand flow, counting only the time it took the sand that actually moved
+            let upper_before = self.upper_chamber;
             self.update_sand(delta);
+            if self.flow_rate > 0.0 {
+                self.stats.elapsed += (upper_before - self.upper_chamber) / self.flow_rate;
+            }
 
             // Update remaining time based on sand in the upper chamber
             self.remaining_time = self.upper_chamber * self.total_time;
@@ -200,6 +680,7 @@ impl Hourglass {
             // Check if the hourglass is empty (no sand in the upper chamber)
             if self.upper_chamber <= 0.0 {
                 self.running = false;
+                self.stats.empties += 1;
 
                 // Auto-flip if enabled
                 if self.auto_flip_when_empty {
@@ -209,6 +690,21 @@ impl Hourglass {
         }
     }
 
+    /// Move the sand back up along the rewind animation
+    fn update_rewind(&mut self, delta: f32) {
+        self.rewind_progress = if self.rewind_duration > 0.0 {
+            (self.rewind_progress + delta / self.rewind_duration).min(1.0)
+        } else {
+            1.0
+        };
+        self.set_fill(self.rewind_from + (1.0 - self.rewind_from) * self.rewind_progress);
+
+        if self.rewind_progress >= 1.0 {
+            self.rewinding = false;
+            self.running = self.value_source == HourglassValueSource::Timer;
+        }
+    }
+
     /// Update the sand levels
     fn update_sand(&mut self, delta: f32) {
         // Calculate the amount to transfer based on flow rate and delta time
@@ -224,18 +720,174 @@ impl Hourglass {
         self.lower_chamber = self.lower_chamber.clamp(0.0, 1.0);
     }
 
+    /// Keep the chambers and the timer within their valid ranges
+    ///
+    /// Chambers are clamped to 0.0 - 1.0 and never hold more than all the sand together, the upper
+    /// one taking precedence. The remaining time is clamped to the total time. NaN values count as
+    /// empty and are reported as warnings.
+    pub fn sanitize(&mut self) {
+        let clamp = |value: f32, max: f32| {
+            if value.is_nan() {
+                report_warning(HourglassWarning::ClampedConfig {
+                    field: "chamber or remaining time",
+                    value,
+                    clamped: 0.0,
+                });
+                0.0
+            } else {
+                value.clamp(0.0, max.max(0.0))
+            }
+        };
+        self.upper_chamber = clamp(self.upper_chamber, 1.0);
+        self.lower_chamber = clamp(self.lower_chamber, 1.0 - self.upper_chamber);
+        self.remaining_time = clamp(self.remaining_time, self.total_time);
+    }
+
+    /// Set the fill of the upper chamber (0.0 - 1.0), moving the rest of the sand to the lower one
+    ///
+    /// Meant for [`HourglassValueSource::External`] hourglasses; timer driven hourglasses keep
+    /// flowing from the new level.
+    pub fn set_fill(&mut self, fill: f32) {
+        self.upper_chamber = fill.clamp(0.0, 1.0);
+        self.lower_chamber = 1.0 - self.upper_chamber;
+        self.remaining_time = self.upper_chamber * self.total_time;
+    }
+
+    /// Add time to the hourglass (in seconds), up to its total time
+    ///
+    /// The timer changes right away while the sand moves to its new level over
+    /// `adjust_duration`. An empty hourglass starts running again. Time beyond the total time is
+    /// handled by the `overflow_policy`. Returns the time that was actually added.
+    ///
+    /// During a flip or rewind the time is queued in `queued_time_adjustment` instead, and added
+    /// on the first update after it completes. Nothing is added right away, so this returns 0.0.
+    pub fn add_time(&mut self, seconds: f32) -> f32 {
+        if self.total_time <= 0.0 {
+            return 0.0;
+        }
+        if self.flipping || self.rewinding {
+            self.queued_time_adjustment += seconds;
+            return 0.0;
+        }
+
+        let shown = self.displayed_upper_chamber();
+        let before = self.remaining_time;
+        let target = self.remaining_time + seconds;
+        if target > self.total_time && self.overflow_policy == TimeOverflowPolicy::ExtendTotalTime {
+            self.total_time = target;
+            self.flow_rate = 1.0 / target;
+        }
+        self.set_fill(target / self.total_time);
+        let applied = self.remaining_time - before;
+        // Only time beyond the capacity is reported, time removed beyond zero is always dropped
+        if self.overflow_policy == TimeOverflowPolicy::EmitOverflowEvent {
+            self.pending_overflow += (seconds - applied).max(0.0);
+        }
+        if applied == 0.0 {
+            return 0.0;
+        }
+
+        self.adjust_offset = shown - self.upper_chamber;
+        self.adjust_progress = 0.0;
+        self.pending_time_adjustment += applied;
+        if before <= 0.0 && self.value_source == HourglassValueSource::Timer {
+            self.running = true;
+        }
+        applied
+    }
+
+    /// Remove time from the hourglass (in seconds), down to zero
+    ///
+    /// Works like [`Hourglass::add_time`], an hourglass running out this way is reported as
+    /// empty. Returns the time that was actually removed.
+    pub fn remove_time(&mut self, seconds: f32) -> f32 {
+        -self.add_time(-seconds)
+    }
+
+    /// Fill of the upper chamber to show, following the transition after time was adjusted
+    pub fn displayed_upper_chamber(&self) -> f32 {
+        let remaining = 1.0 - EaseFunction::CubicOut.sample_clamped(self.adjust_progress);
+        (self.upper_chamber + self.adjust_offset * remaining).clamp(0.0, 1.0)
+    }
+
+    /// Whether the flip animation has turned the hourglass past its side, so the upper chamber is
+    /// shown at the bottom until the flip completes
+    fn turned_over(&self) -> bool {
+        self.flipping && self.current_rotation.abs() > std::f32::consts::FRAC_PI_2
+    }
+
+    /// Bulb of the glass currently shown on top, switching halfway through flips
+    pub fn current_top_bulb(&self) -> Bulb {
+        if self.flipped != self.turned_over() {
+            Bulb::Second
+        } else {
+            Bulb::First
+        }
+    }
+
+    /// Shown fill (0.0 - 1.0) of the bulb currently on top or at the bottom
+    ///
+    /// Halfway through a flip the chambers trade places on screen, before the upper and lower
+    /// chambers are swapped when the flip completes. Follows the transition after time was
+    /// adjusted like [`Hourglass::displayed_upper_chamber`].
+    pub fn visual_fill(&self, bulb: SandBulb) -> f32 {
+        let upper = self.displayed_upper_chamber();
+        let lower = (self.upper_chamber + self.lower_chamber - upper).clamp(0.0, 1.0);
+        match (bulb, self.turned_over()) {
+            (SandBulb::Top, false) | (SandBulb::Bottom, true) => upper,
+            (SandBulb::Bottom, false) | (SandBulb::Top, true) => lower,
+        }
+    }
+
+    /// Show a number on the badge of the hourglass, or hide it with `None`
+    ///
+    /// The badge is drawn by the mesh renderer, see `HourglassBadgeConfig`.
+    pub fn set_badge(&mut self, badge: Option<u32>) {
+        self.badge = badge;
+    }
+
     /// Start flipping the hourglass
     pub fn flip(&mut self) {
         if !self.flipping {
             self.flipping = true;
+            self.flip_started = true;
             self.flip_progress = 0.0;
             // Always flip 180 degrees (PI radians) from current upright position
             self.target_rotation = std::f32::consts::PI;
         }
     }
 
+    /// Run the sand back up into the upper chamber over the given duration in seconds
+    ///
+    /// The timer is paused while rewinding and restarts from the full time once the upper chamber
+    /// is refilled. Ignored while flipping.
+    pub fn rewind(&mut self, duration: f32) {
+        if self.flipping {
+            return;
+        }
+        self.rewinding = true;
+        self.rewind_duration = duration;
+        self.rewind_progress = 0.0;
+        self.rewind_from = self.upper_chamber;
+        self.running = false;
+    }
+
+    /// Rate at which sand flows back up while rewinding, in percentage per second
+    pub fn rewind_flow_rate(&self) -> f32 {
+        if self.rewinding && self.rewind_duration > 0.0 {
+            (1.0 - self.rewind_from) / self.rewind_duration
+        } else {
+            0.0
+        }
+    }
+
     /// Check if the hourglass is ready to be flipped (not currently flipping)
     pub fn can_flip(&self) -> bool {
         !self.flipping
     }
+
+    /// Format the remaining time for display using the given style
+    pub fn format_remaining(&self, style: &TimeFormat) -> String {
+        format_time(self.remaining_time, style)
+    }
 }
@@ -0,0 +1,66 @@
+//! Skipping sand and splash updates for hourglasses that aren't visible.
+
+use crate::mesh::{HourglassMesh, HourglassParts};
+use bevy::prelude::*;
+use bevy::render::view::PreviousVisibleEntities;
+
+/// Marker component for hourglasses that are hidden or were off-screen last frame
+///
+/// Added and removed by the plugin. Culled hourglasses keep their timers running, but their sand
+/// meshes aren't regenerated and they don't spawn splash particles until they are visible again.
+#[derive(Component, Debug, Default)]
+pub struct HourglassCulled;
+
+/// Marker component keeping the sand and splashes of an hourglass updated while it isn't visible
+///
+/// Useful when the hourglass is rendered by a camera that doesn't compute visibility, or when
+/// it has to look up to date on the very frame it comes back on screen.
+#[derive(Component, Debug, Default)]
+pub struct HourglassUpdateWhenHidden;
+
+/// Type alias for the culled hourglass query to reduce type complexity
+type CullingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static InheritedVisibility,
+        Option<&'static HourglassParts>,
+        Has<HourglassCulled>,
+        Has<HourglassUpdateWhenHidden>,
+    ),
+    With<HourglassMesh>,
+>;
+
+/// System that marks hourglasses which are hidden or weren't in view last frame
+///
+/// Apps without visibility computation (e.g. headless apps) never cull hourglasses.
+pub fn update_hourglass_culling(
+    mut commands: Commands,
+    visibility_computed: Option<Res<PreviousVisibleEntities>>,
+    hourglasses: CullingQuery,
+    view_visibility: Query<&ViewVisibility>,
+) {
+    for (entity, inherited, parts, is_culled, update_when_hidden) in hourglasses.iter() {
+        let visible = update_when_hidden
+            || visibility_computed.is_none()
+            || (inherited.get() && parts.is_none_or(|parts| is_in_view(parts, &view_visibility)));
+
+        if visible && is_culled {
+            commands.entity(entity).remove::<HourglassCulled>();
+        } else if !visible && !is_culled {
+            commands.entity(entity).insert(HourglassCulled);
+        }
+    }
+}
+
+/// Whether any drawn part of the hourglass was in view last frame
+fn is_in_view(parts: &HourglassParts, view_visibility: &Query<&ViewVisibility>) -> bool {
+    let mut drawn = parts.meshes().peekable();
+
+    // Hourglasses without any parts can't be culled
+    drawn.peek().is_none()
+        || view_visibility
+            .iter_many(drawn)
+            .any(|visibility| visibility.get())
+}
@@ -0,0 +1,100 @@
+//! Binding hourglasses to wall clock deadlines, e.g. "the event starts at 18:00".
+
+use crate::components::{Hourglass, HourglassValueSource};
+use crate::events::HourglassEmptyEvent;
+use bevy::prelude::*;
+use std::time::Duration;
+use web_time::SystemTime;
+
+/// Component binding an [`Hourglass`] to a wall clock deadline
+///
+/// The hourglass spans from the moment it was bound to the target time and is re-synced against
+/// the system clock every frame, so it stays correct across pauses, frame drops or the app being
+/// suspended. Times are absolute (UTC), so local times must be converted by the caller before
+/// binding, e.g. with a date/time crate.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassDeadline {
+    /// Time at which the hourglass runs out
+    pub target: SystemTime,
+    /// Time at which the hourglass was full
+    pub bound_at: SystemTime,
+    /// Whether the deadline has been reached and the empty event sent
+    pub reached: bool,
+}
+
+impl HourglassDeadline {
+    /// Creates a deadline at the given time, starting from now
+    pub fn new(target: SystemTime) -> Self {
+        Self {
+            target,
+            bound_at: SystemTime::now(),
+            reached: false,
+        }
+    }
+
+    /// Creates a deadline at the given Unix timestamp (in seconds), starting from now
+    pub fn from_unix_timestamp(seconds: u64) -> Self {
+        Self::new(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    /// Creates a deadline the given duration from now
+    pub fn in_duration(duration: Duration) -> Self {
+        Self::new(SystemTime::now() + duration)
+    }
+
+    /// Sets the time at which the hourglass was full
+    pub fn with_bound_at(mut self, bound_at: SystemTime) -> Self {
+        self.bound_at = bound_at;
+        self
+    }
+
+    /// Time left until the deadline at `now`, zero once it has passed
+    pub fn remaining_at(&self, now: SystemTime) -> Duration {
+        self.target.duration_since(now).unwrap_or_default()
+    }
+
+    /// Update the hourglass to match the deadline at `now`
+    ///
+    /// Returns `true` the first time the deadline is found to have been reached.
+    pub fn sync(&mut self, hourglass: &mut Hourglass, now: SystemTime) -> bool {
+        let total = self
+            .target
+            .duration_since(self.bound_at)
+            .unwrap_or_default()
+            .as_secs_f32();
+        let remaining = self.remaining_at(now).as_secs_f32();
+
+        // The deadline drives the sand instead of the hourglass' own timer
+        hourglass.value_source = HourglassValueSource::External;
+        hourglass.total_time = total;
+        hourglass.set_fill(if total > 0.0 { remaining / total } else { 0.0 });
+
+        if remaining > 0.0 {
+            self.reached = false;
+            false
+        } else {
+            !std::mem::replace(&mut self.reached, true)
+        }
+    }
+}
+
+/// System that re-syncs hourglasses with their deadlines against the system clock
+///
+/// The clock is read through `web_time`, as `std::time::SystemTime::now` panics on the web.
+pub fn sync_hourglass_deadlines(
+    mut hourglasses: Query<(Entity, &mut Hourglass, &mut HourglassDeadline)>,
+    mut empty_events: EventWriter<HourglassEmptyEvent>,
+) {
+    if hourglasses.is_empty() {
+        return;
+    }
+    let now = SystemTime::now();
+    for (entity, mut hourglass, mut deadline) in hourglasses.iter_mut() {
+        if deadline.sync(&mut hourglass, now) {
+            empty_events.write(HourglassEmptyEvent {
+                entity,
+                total_time: hourglass.total_time,
+            });
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Gizmo debug overlay for hourglass geometry.
+
+use crate::components::{Hourglass, SandSplash};
+use crate::mesh::HourglassMeshSandState;
+use crate::systems::splash_impact_point;
+use bevy::prelude::*;
+use hourglass_geometry::{sand_fill_line, HourglassShapeBuilder, Point2D, SandBulb};
+
+/// Plugin drawing gizmo lines over mesh hourglasses to help tune shapes and report bugs
+///
+/// Toggle the overlay at runtime through the [`HourglassDebugConfig`] resource, or with its
+/// `toggle_key` when keyboard input is available.
+#[derive(Default)]
+pub struct HourglassDebugPlugin {
+    /// Initial configuration of the overlay
+    pub config: HourglassDebugConfig,
+}
+
+impl Plugin for HourglassDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone());
+
+        app.add_systems(
+            Update,
+            (
+                toggle_hourglass_debug,
+                draw_hourglass_debug_gizmos
+                    .run_if(|config: Res<HourglassDebugConfig>| config.enabled),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Runtime configuration of the hourglass debug overlay
+#[derive(Resource, Debug, Clone)]
+pub struct HourglassDebugConfig {
+    /// Whether the overlay is drawn at all
+    pub enabled: bool,
+    /// Key toggling `enabled` at runtime
+    pub toggle_key: Option<KeyCode>,
+    /// Draw the glass outline
+    pub show_outline: bool,
+    /// Draw the outline offset by the sand wall offset
+    pub show_wall_offset_outline: bool,
+    /// Draw the top and bottom sand fill lines
+    pub show_fill_lines: bool,
+    /// Draw the upper and lower bounds of the neck
+    pub show_neck_bounds: bool,
+    /// Draw the sand splash impact point
+    pub show_splash_impact: bool,
+}
+
+impl Default for HourglassDebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: Some(KeyCode::F3),
+            show_outline: true,
+            show_wall_offset_outline: true,
+            show_fill_lines: true,
+            show_neck_bounds: true,
+            show_splash_impact: true,
+        }
+    }
+}
+
+/// System that toggles the debug overlay when the configured key is pressed
+pub fn toggle_hourglass_debug(
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    mut config: ResMut<HourglassDebugConfig>,
+) {
+    let (Some(keyboard), Some(key)) = (keyboard, config.toggle_key) else {
+        return;
+    };
+
+    if keyboard.just_pressed(key) {
+        config.enabled = !config.enabled;
+    }
+}
+
+/// System that draws the debug gizmos for every mesh hourglass
+pub fn draw_hourglass_debug_gizmos(
+    mut gizmos: Gizmos,
+    config: Res<HourglassDebugConfig>,
+    query: Query<(
+        &Hourglass,
+        &HourglassMeshSandState,
+        &GlobalTransform,
+        Option<&SandSplash>,
+    )>,
+) {
+    for (hourglass, sand_state, global_transform, sand_splash) in query.iter() {
+        let body_config = &sand_state.body_config;
+        let shape_builder = HourglassShapeBuilder {
+            total_height: body_config.total_height,
+            bulb_style: body_config.bulb_style.clone(),
+            neck_style: body_config.neck_style.clone(),
+        };
+
+        let to_world = |point: Point2D| {
+            global_transform
+                .transform_point((sand_state.center_offset + Vec2::from(point)).extend(0.0))
+                .truncate()
+        };
+        let mut draw_outline = |outline: Vec<Point2D>, color: Color| {
+            if let Some(&first) = outline.first() {
+                gizmos.linestrip_2d(outline.into_iter().chain([first]).map(to_world), color);
+            }
+        };
+
+        if config.show_outline {
+            draw_outline(shape_builder.generate_outline(), Color::srgb(0.2, 0.8, 1.0));
+        }
+
+        if config.show_wall_offset_outline {
+            let outline =
+                shape_builder.generate_outline_with_wall_offset(sand_state.sand_config.wall_offset);
+            draw_outline(outline, Color::srgb(1.0, 0.6, 0.1));
+        }
+
+        let half_height = body_config.total_height / 2.0;
+        let bulb_height = (body_config.total_height - body_config.neck_style.height()) / 2.0;
+        let half_width = bulb_height * body_config.bulb_style.width_factor();
+
+        if config.show_fill_lines {
+            for bulb in [SandBulb::Top, SandBulb::Bottom] {
+                let y = sand_fill_line(
+                    sand_state.fill_percent,
+                    bulb,
+                    body_config.neck_style.height(),
+                    -half_height,
+                    half_height,
+                );
+                gizmos.line_2d(
+                    to_world([-half_width, y]),
+                    to_world([half_width, y]),
+                    Color::srgb(1.0, 1.0, 0.0),
+                );
+            }
+        }
+
+        if config.show_neck_bounds {
+            let neck_half_height = body_config.neck_style.height() / 2.0;
+            let neck_half_width = body_config.neck_style.width();
+            for y in [neck_half_height, -neck_half_height] {
+                gizmos.line_2d(
+                    to_world([-neck_half_width, y]),
+                    to_world([neck_half_width, y]),
+                    Color::srgb(1.0, 0.2, 0.8),
+                );
+            }
+        }
+
+        if config.show_splash_impact && !hourglass.flipping {
+            if let Some(sand_splash) = sand_splash {
+                let impact = splash_impact_point(global_transform, sand_state, &sand_splash.config);
+                gizmos.circle_2d(
+                    impact.truncate(),
+                    sand_splash.config.splash_radius.max(2.0),
+                    Color::srgb(1.0, 0.2, 0.2),
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Defines events for the hourglass plugin.
 
+use crate::interaction::InteractionType;
+use crate::replay::HourglassRecordedEvent;
 use bevy::prelude::*;
 
 /// Event sent when an hourglass starts flipping
@@ -18,3 +20,89 @@ pub struct HourglassEmptyEvent {
     /// Total time the hourglass was running (in seconds)
     pub total_time: f32,
 }
+
+/// Event sent when time was added to or removed from an hourglass
+#[derive(Event, Debug, Clone)]
+pub struct HourglassTimeAdjustedEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// Time added (in seconds), negative when time was removed
+    pub seconds: f32,
+
+    /// Remaining time after the adjustment (in seconds)
+    pub remaining_time: f32,
+}
+
+/// Event sent when time added to an hourglass did not fit, with
+/// [`TimeOverflowPolicy::EmitOverflowEvent`](crate::TimeOverflowPolicy::EmitOverflowEvent)
+#[derive(Event, Debug, Clone)]
+pub struct HourglassTimeOverflowEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// Time beyond the total time (in seconds)
+    pub seconds: f32,
+}
+
+/// Event sent when the speed multiplier of an hourglass changes
+#[derive(Event, Debug, Clone)]
+pub struct HourglassSpeedChangeEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// Speed before the change
+    pub previous_speed: f32,
+
+    /// Speed after the change
+    pub speed: f32,
+}
+
+/// Event sent when an hourglass starts shattering
+#[derive(Event, Debug, Clone)]
+pub struct HourglassShatteredEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+}
+
+/// Event sent when an hourglass with
+/// [`HourglassTipDetection`](crate::HourglassTipDetection) tips over or stands up again
+#[derive(Event, Debug, Clone)]
+pub struct HourglassTippedEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// Whether the hourglass now lies on its side, `false` once it stands again
+    pub tipped: bool,
+}
+
+/// Event sent when a replayed hourglass passes a recorded event
+#[derive(Event, Debug, Clone)]
+pub struct HourglassReplayEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// Event that was recorded
+    pub event: HourglassRecordedEvent,
+}
+
+/// Event sent when the last hourglass of a chain of [`HourglassLink`](crate::HourglassLink)s
+/// runs out
+#[derive(Event, Debug, Clone)]
+pub struct HourglassChainCompleteEvent {
+    /// Entity ID of the first hourglass of the chain
+    pub first: Entity,
+
+    /// Entity ID of the last hourglass of the chain
+    pub last: Entity,
+}
+
+/// Event sent when an [`HourglassInteractive`](crate::HourglassInteractive) hourglass is clicked
+#[derive(Event, Debug, Clone)]
+pub struct HourglassInteractionEvent {
+    /// Entity ID of the hourglass
+    pub entity: Entity,
+
+    /// What was clicked
+    pub interaction: InteractionType,
+}
@@ -0,0 +1,92 @@
+//! Formatting helpers for displaying hourglass times.
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Formatter closure used by [`TimeFormat::Custom`], receiving the time in seconds
+pub type TimeFormatter = Arc<dyn Fn(f32) -> String + Send + Sync>;
+
+/// Style used to turn a time in seconds into display text
+#[derive(Clone, Default)]
+pub enum TimeFormat {
+    /// Minutes and seconds, e.g. `02:05`
+    #[default]
+    MinutesSeconds,
+    /// Hours, minutes and seconds, e.g. `1:02:05`
+    HoursMinutesSeconds,
+    /// Seconds with a fixed number of decimals, e.g. `125.3`
+    Seconds { decimals: usize },
+    /// Approximate English text, e.g. `about 2 minutes`
+    Fuzzy,
+    /// User-provided formatter, e.g. for localization
+    Custom(TimeFormatter),
+}
+
+impl TimeFormat {
+    /// Create a custom format from a formatter closure
+    pub fn custom(formatter: impl Fn(f32) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(formatter))
+    }
+}
+
+impl fmt::Debug for TimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinutesSeconds => write!(f, "MinutesSeconds"),
+            Self::HoursMinutesSeconds => write!(f, "HoursMinutesSeconds"),
+            Self::Seconds { decimals } => f
+                .debug_struct("Seconds")
+                .field("decimals", decimals)
+                .finish(),
+            Self::Fuzzy => write!(f, "Fuzzy"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Format a time in seconds using the given style
+///
+/// Clock styles round up to the next whole second so a countdown only reads `00:00` once
+/// the time is actually up.
+pub fn format_time(seconds: f32, style: &TimeFormat) -> String {
+    let seconds = seconds.max(0.0);
+
+    match style {
+        TimeFormat::MinutesSeconds => {
+            let total = seconds.ceil() as u64;
+            format!("{:02}:{:02}", total / 60, total % 60)
+        }
+        TimeFormat::HoursMinutesSeconds => {
+            let total = seconds.ceil() as u64;
+            format!(
+                "{}:{:02}:{:02}",
+                total / 3600,
+                (total % 3600) / 60,
+                total % 60
+            )
+        }
+        TimeFormat::Seconds { decimals } => format!("{:.*}", decimals, seconds),
+        TimeFormat::Fuzzy => format_fuzzy(seconds),
+        TimeFormat::Custom(formatter) => formatter(seconds),
+    }
+}
+
+/// Approximate English description of a time in seconds
+fn format_fuzzy(seconds: f32) -> String {
+    let minutes = seconds / 60.0;
+    let hours = minutes / 60.0;
+
+    if seconds <= 0.0 {
+        "no time left".to_string()
+    } else if seconds < 45.0 {
+        "less than a minute".to_string()
+    } else if seconds < 90.0 {
+        "about a minute".to_string()
+    } else if minutes < 45.0 {
+        format!("about {} minutes", minutes.round() as u32)
+    } else if minutes < 90.0 {
+        "about an hour".to_string()
+    } else {
+        format!("about {} hours", hours.round() as u32)
+    }
+}
@@ -0,0 +1,106 @@
+//! Spawning many hourglasses laid out in a grid.
+
+use crate::mesh::{HourglassAssetCache, HourglassMeshBuilder};
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A cell of a grid spawned with [`spawn_hourglass_grid`]
+#[derive(Debug, Clone, Copy)]
+pub struct GridCell {
+    pub row: usize,
+    pub col: usize,
+    /// Index of the cell, counting row by row
+    pub index: usize,
+    /// Transform of the cell, including jitter
+    pub transform: Transform,
+}
+
+/// Layout and style of a grid of hourglasses
+pub struct GridConfig {
+    pub rows: usize,
+    pub cols: usize,
+    /// Distance between the centers of neighboring cells
+    pub spacing: Vec2,
+    /// Center of the grid
+    pub center: Vec3,
+    /// Maximum random offset from the cell center on each axis
+    pub jitter: Vec2,
+    /// Seed for the jitter, so layouts are reproducible
+    pub seed: u64,
+    /// Creates the builder for each cell, usually starting from `HourglassMeshBuilder::new(cell.transform)`
+    pub style_fn: Box<dyn FnMut(GridCell) -> HourglassMeshBuilder>,
+}
+
+impl GridConfig {
+    /// Grid of the given size using the same style function for every cell
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        spacing: Vec2,
+        style_fn: impl FnMut(GridCell) -> HourglassMeshBuilder + 'static,
+    ) -> Self {
+        Self {
+            rows,
+            cols,
+            spacing,
+            center: Vec3::ZERO,
+            jitter: Vec2::ZERO,
+            seed: 0,
+            style_fn: Box::new(style_fn),
+        }
+    }
+
+    /// Set the center of the grid
+    pub fn with_center(mut self, center: Vec3) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Set the maximum random offset of each hourglass from its cell center
+    pub fn with_jitter(mut self, jitter: Vec2, seed: u64) -> Self {
+        self.jitter = jitter;
+        self.seed = seed;
+        self
+    }
+}
+
+/// Spawn a grid of hourglasses, returning the entities row by row
+///
+/// Hourglasses with identical shapes or colors share their body and plate meshes and materials.
+pub fn spawn_hourglass_grid(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    mut config: GridConfig,
+) -> Vec<Entity> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut cache = HourglassAssetCache::default();
+    let mut entities = Vec::with_capacity(config.rows * config.cols);
+
+    for row in 0..config.rows {
+        for col in 0..config.cols {
+            let mut offset = Vec2::new(
+                (col as f32 - (config.cols as f32 - 1.0) / 2.0) * config.spacing.x,
+                ((config.rows as f32 - 1.0) / 2.0 - row as f32) * config.spacing.y,
+            );
+            if config.jitter != Vec2::ZERO {
+                offset += Vec2::new(
+                    rng.random_range(-1.0..=1.0) * config.jitter.x,
+                    rng.random_range(-1.0..=1.0) * config.jitter.y,
+                );
+            }
+
+            let cell = GridCell {
+                row,
+                col,
+                index: row * config.cols + col,
+                transform: Transform::from_translation(config.center + offset.extend(0.0)),
+            };
+            let builder = (config.style_fn)(cell);
+            entities.push(builder.build_with_cache(commands, meshes, materials, &mut cache));
+        }
+    }
+
+    entities
+}
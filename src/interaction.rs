@@ -0,0 +1,139 @@
+//! Clicking hourglasses and their parts, e.g. the top bulb to add time and the bottom one to
+//! take it away.
+
+use crate::events::HourglassInteractionEvent;
+use crate::mesh::{HourglassMeshBuilder, HourglassMeshShape, HourglassParts};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use hourglass_geometry::{point_in_polygon, HourglassShapeBuilder};
+
+/// Marker for hourglasses sending [`HourglassInteractionEvent`]s when clicked
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct HourglassInteractive;
+
+/// Parts of an hourglass that can be clicked separately
+///
+/// Bulbs are named as seen at rest: flips swap the sand between the bulbs rather than leaving
+/// the hourglass upside down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HourglassPart {
+    TopBulb,
+    BottomBulb,
+    TopPlate,
+    BottomPlate,
+}
+
+/// How an hourglass was interacted with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionType {
+    /// Clicked within the bounds of the hourglass but outside of its parts, e.g. beside the neck
+    Click,
+    /// Clicked on one of its parts
+    ClickPart(HourglassPart),
+}
+
+impl HourglassMeshShape {
+    /// Part of the hourglass under a point given in the frame of that part
+    ///
+    /// `body_point` is relative to the glass body, with the neck at the origin, and the plate
+    /// points are relative to the center of each plate.
+    pub fn part_at(
+        &self,
+        body_point: Vec2,
+        top_plate_point: Option<Vec2>,
+        bottom_plate_point: Option<Vec2>,
+    ) -> Option<HourglassPart> {
+        if let Some(plates) = &self.plates_config {
+            let half_size = Vec2::new(plates.width, plates.height) / 2.0;
+            let on_plate =
+                |point: Option<Vec2>| point.is_some_and(|point| point.abs().cmple(half_size).all());
+            if on_plate(top_plate_point) {
+                return Some(HourglassPart::TopPlate);
+            }
+            if on_plate(bottom_plate_point) {
+                return Some(HourglassPart::BottomPlate);
+            }
+        }
+
+        let outline = HourglassShapeBuilder {
+            total_height: self.body_config.total_height,
+            bulb_style: self.body_config.bulb_style.clone(),
+            neck_style: self.body_config.neck_style.clone(),
+        }
+        .generate_outline();
+        point_in_polygon([body_point.x, body_point.y], &outline).then_some(if body_point.y >= 0.0 {
+            HourglassPart::TopBulb
+        } else {
+            HourglassPart::BottomBulb
+        })
+    }
+}
+
+/// System sending interaction events for clicks on interactive hourglasses
+///
+/// Only the topmost hourglass under the cursor receives the click.
+pub fn detect_hourglass_clicks(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hourglasses: Query<
+        (
+            Entity,
+            &HourglassParts,
+            &HourglassMeshShape,
+            &GlobalTransform,
+        ),
+        With<HourglassInteractive>,
+    >,
+    transforms: Query<&GlobalTransform>,
+    mut interaction_events: EventWriter<HourglassInteractionEvent>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = windows.iter().find_map(Window::cursor_position) else {
+        return;
+    };
+    let Some(world_point) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .find_map(|(camera, transform)| camera.viewport_to_world_2d(transform, cursor).ok())
+    else {
+        return;
+    };
+
+    // Point in the local frame of a part
+    let local_point = |part: Option<Entity>| {
+        let transform = transforms.get(part?).ok()?;
+        Some(
+            transform
+                .affine()
+                .inverse()
+                .transform_point3(world_point.extend(0.0))
+                .truncate(),
+        )
+    };
+
+    let topmost = hourglasses
+        .iter()
+        .filter_map(|(entity, parts, shape, transform)| {
+            let body = local_point(parts.body)?;
+            let part = shape.part_at(
+                body,
+                local_point(parts.top_plate),
+                local_point(parts.bottom_plate),
+            );
+            let size =
+                HourglassMeshBuilder::visual_size(&shape.body_config, shape.plates_config.as_ref());
+            let in_bounds = body.abs().cmple(size / 2.0).all();
+            (part.is_some() || in_bounds).then_some((entity, part, transform.translation().z))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2));
+
+    if let Some((entity, part, _)) = topmost {
+        interaction_events.write(HourglassInteractionEvent {
+            entity,
+            interaction: part.map_or(InteractionType::Click, InteractionType::ClickPart),
+        });
+    }
+}
@@ -0,0 +1,246 @@
+//! Blending between hourglass styles and configurations, e.g. to morph shapes or transition
+//! themes.
+
+use crate::components::SandSplashConfig;
+use crate::mesh::{
+    ContentStyle, EdgeQuality, HourglassMeshBodyConfig, HourglassMeshPlatesConfig,
+    HourglassMeshSandConfig, HourglassVisualConfig, RenderStyle, StreamStyle, UvMode,
+};
+use bevy::prelude::*;
+use hourglass_geometry::{BulbStyle, NeckStyle};
+
+/// Configuration or style that can be blended with another one of the same type
+///
+/// Numbers and colors are interpolated. Values that can't be blended, such as flags, curves,
+/// gradients or mismatched enum variants, switch from `a` to `b` halfway through. Bulb and neck
+/// styles are the exception: straight ones are blended as curved ones without curvature.
+pub trait LerpConfig: Clone {
+    /// Value at `t` (0.0 - 1.0) between `self` and `other`
+    fn lerp_config(&self, other: &Self, t: f32) -> Self;
+}
+
+/// Configuration at `t` (0.0 - 1.0) between `a` and `b`, see [`LerpConfig`]
+///
+/// `t` at or beyond the ends gives a copy of that end.
+pub fn lerp_configs<T: LerpConfig>(a: &T, b: &T, t: f32) -> T {
+    if t <= 0.0 {
+        a.clone()
+    } else if t >= 1.0 {
+        b.clone()
+    } else {
+        a.lerp_config(b, t)
+    }
+}
+
+/// `a` for the first half of the blend and `b` for the second
+fn switch<T: Clone>(a: &T, b: &T, t: f32) -> T {
+    if t < 0.5 {
+        a.clone()
+    } else {
+        b.clone()
+    }
+}
+
+impl LerpConfig for f32 {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
+impl LerpConfig for Color {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        self.mix(other, t)
+    }
+}
+
+impl<T: LerpConfig> LerpConfig for Option<T> {
+    /// Blended when both are set, switched otherwise
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.lerp_config(b, t)),
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for BulbStyle {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl LerpConfig for NeckStyle {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl LerpConfig for UvMode {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (UvMode::Planar { size: a }, UvMode::Planar { size: b }) => UvMode::Planar {
+                size: a.lerp(*b, t),
+            },
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for EdgeQuality {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (EdgeQuality::Feathered { width: a }, EdgeQuality::Feathered { width: b }) => {
+                EdgeQuality::Feathered {
+                    width: a.lerp(*b, t),
+                }
+            }
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for RenderStyle {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (
+                RenderStyle::Minimal { stroke_width: a },
+                RenderStyle::Minimal { stroke_width: b },
+            ) => RenderStyle::Minimal {
+                stroke_width: a.lerp(*b, t),
+            },
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for StreamStyle {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (
+                StreamStyle::Grains {
+                    density: density_a,
+                    speed: speed_a,
+                },
+                StreamStyle::Grains {
+                    density: density_b,
+                    speed: speed_b,
+                },
+            ) => StreamStyle::Grains {
+                density: density_a.lerp(*density_b, t),
+                speed: speed_a.lerp(*speed_b, t),
+            },
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for ContentStyle {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (
+                ContentStyle::Liquid {
+                    wave_amplitude: amplitude_a,
+                    wavelength: wavelength_a,
+                    wave_speed: wave_speed_a,
+                    droplet_spacing: spacing_a,
+                    droplet_speed: droplet_speed_a,
+                },
+                ContentStyle::Liquid {
+                    wave_amplitude: amplitude_b,
+                    wavelength: wavelength_b,
+                    wave_speed: wave_speed_b,
+                    droplet_spacing: spacing_b,
+                    droplet_speed: droplet_speed_b,
+                },
+            ) => ContentStyle::Liquid {
+                wave_amplitude: amplitude_a.lerp(*amplitude_b, t),
+                wavelength: wavelength_a.lerp(*wavelength_b, t),
+                wave_speed: wave_speed_a.lerp(*wave_speed_b, t),
+                droplet_spacing: spacing_a.lerp(*spacing_b, t),
+                droplet_speed: droplet_speed_a.lerp(*droplet_speed_b, t),
+            },
+            _ => switch(self, other, t),
+        }
+    }
+}
+
+impl LerpConfig for HourglassMeshBodyConfig {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        Self {
+            total_height: self.total_height.lerp(other.total_height, t),
+            bulb_style: self.bulb_style.lerp(&other.bulb_style, t),
+            neck_style: self.neck_style.lerp(&other.neck_style, t),
+            color: self.color.mix(&other.color, t),
+            emissive: self.emissive.lerp(other.emissive, t),
+            outline_spacing: self.outline_spacing.lerp_config(&other.outline_spacing, t),
+            uv_mode: self.uv_mode.lerp_config(&other.uv_mode, t),
+            stroke_units: switch(&self.stroke_units, &other.stroke_units, t),
+            edge_quality: self.edge_quality.lerp_config(&other.edge_quality, t),
+        }
+    }
+}
+
+impl LerpConfig for HourglassMeshPlatesConfig {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        Self {
+            width: self.width.lerp(other.width, t),
+            height: self.height.lerp(other.height, t),
+            color: self.color.mix(&other.color, t),
+        }
+    }
+}
+
+impl LerpConfig for HourglassMeshSandConfig {
+    /// Bulb colors that are only set on one side blend from or to the main color
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        let bulb_color = |a: Option<Color>, b: Option<Color>| {
+            (a.is_some() || b.is_some())
+                .then(|| a.unwrap_or(self.color).mix(&b.unwrap_or(other.color), t))
+        };
+        Self {
+            color: self.color.mix(&other.color, t),
+            fill_percent: self.fill_percent.lerp(other.fill_percent, t),
+            wall_offset: self.wall_offset.lerp(other.wall_offset, t),
+            stream_style: self.stream_style.lerp_config(&other.stream_style, t),
+            min_flow_fill: self.min_flow_fill.lerp(other.min_flow_fill, t),
+            emissive: self.emissive.lerp(other.emissive, t),
+            top_color: bulb_color(self.top_color, other.top_color),
+            bottom_color: bulb_color(self.bottom_color, other.bottom_color),
+            content_style: self.content_style.lerp_config(&other.content_style, t),
+            color_by_remaining: switch(&self.color_by_remaining, &other.color_by_remaining, t),
+            funnel_fill: self.funnel_fill.lerp(other.funnel_fill, t),
+            robust_geometry: switch(&self.robust_geometry, &other.robust_geometry, t),
+            flow_curve: switch(&self.flow_curve, &other.flow_curve, t),
+            fill_smoothing: self.fill_smoothing.lerp(other.fill_smoothing, t),
+        }
+    }
+}
+
+impl LerpConfig for SandSplashConfig {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        Self {
+            splash_radius: self.splash_radius.lerp(other.splash_radius, t),
+            particle_count: (self.particle_count as f32)
+                .lerp(other.particle_count as f32, t)
+                .round() as u32,
+            particle_duration: self.particle_duration.lerp(other.particle_duration, t),
+            spawn_interval: self.spawn_interval.lerp(other.spawn_interval, t),
+            particle_color: self.particle_color.mix(&other.particle_color, t),
+            particle_size: self.particle_size.lerp(other.particle_size, t),
+            vertical_offset: self.vertical_offset.lerp(other.vertical_offset, t),
+            size_units: switch(&self.size_units, &other.size_units, t),
+        }
+    }
+}
+
+impl LerpConfig for HourglassVisualConfig {
+    fn lerp_config(&self, other: &Self, t: f32) -> Self {
+        Self {
+            body: self.body.lerp_config(&other.body, t),
+            plates: self.plates.lerp_config(&other.plates, t),
+            sand: self.sand.lerp_config(&other.sand, t),
+            sand_splash: self.sand_splash.lerp_config(&other.sand_splash, t),
+            render_style: self.render_style.lerp_config(&other.render_style, t),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Text labels shown above or below hourglasses, e.g. "Boss Enrage".
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+
+/// Where a label is placed relative to its hourglass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelPosition {
+    /// Above the hourglass
+    #[default]
+    Above,
+    /// Below the hourglass
+    Below,
+}
+
+/// Configuration for a label added with
+/// [`HourglassMeshBuilder::with_label`](crate::HourglassMeshBuilder::with_label)
+#[derive(Debug, Clone)]
+pub struct LabelConfig {
+    /// Where the label is placed
+    pub position: LabelPosition,
+    /// Font size of the label
+    pub font_size: f32,
+    /// Color of the label
+    pub color: Color,
+    /// Space between the hourglass and the label (in pixels)
+    pub gap: f32,
+}
+
+impl Default for LabelConfig {
+    fn default() -> Self {
+        Self {
+            position: LabelPosition::Above,
+            font_size: 24.0,
+            color: Color::WHITE,
+            gap: 8.0,
+        }
+    }
+}
+
+/// Component for the text label of an hourglass
+///
+/// The label is a child of the hourglass so it scales and moves with it, but it stays upright
+/// and on the same side while the hourglass flips or rotates.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassLabel {
+    pub config: LabelConfig,
+    /// Position of the hourglass' center relative to its origin, set by its anchor
+    pub center: Vec2,
+    /// Distance from the center of the hourglass to the center of the label
+    pub offset: f32,
+}
+
+impl HourglassLabel {
+    /// Offset of the label from the hourglass center when upright, given the hourglass' size
+    pub fn upright_offset(config: &LabelConfig, hourglass_size: Vec2) -> Vec2 {
+        let distance = hourglass_size.y / 2.0 + config.gap + config.font_size / 2.0;
+        match config.position {
+            LabelPosition::Above => Vec2::new(0.0, distance),
+            LabelPosition::Below => Vec2::new(0.0, -distance),
+        }
+    }
+}
+
+/// Spawn a label as a child of the hourglass
+pub fn spawn_hourglass_label(
+    commands: &mut Commands,
+    hourglass: Entity,
+    center: Vec2,
+    hourglass_size: Vec2,
+    text: impl Into<String>,
+    config: LabelConfig,
+) -> Entity {
+    let offset = HourglassLabel::upright_offset(&config, hourglass_size);
+    let label = commands
+        .spawn((
+            Text2d::new(text),
+            TextFont {
+                font_size: config.font_size,
+                ..default()
+            },
+            TextColor(config.color),
+            Transform::from_translation((center + offset).extend(1.0)),
+            HourglassLabel {
+                config,
+                center,
+                offset: offset.y,
+            },
+        ))
+        .id();
+    commands.entity(hourglass).add_child(label);
+    label
+}
+
+/// System that keeps labels upright and on their side of the hourglass while it rotates
+pub fn keep_hourglass_labels_upright(
+    hourglasses: Query<&Transform, (With<Hourglass>, Without<HourglassLabel>)>,
+    mut labels: Query<(&HourglassLabel, &ChildOf, &mut Transform)>,
+) {
+    for (label, child_of, mut transform) in labels.iter_mut() {
+        let Ok(hourglass_transform) = hourglasses.get(child_of.parent()) else {
+            continue;
+        };
+
+        // Undo the hourglass' rotation, so the label only follows its position and scale
+        let inverse = hourglass_transform.rotation.inverse();
+        transform.rotation = inverse;
+        transform.translation = label.center.extend(transform.translation.z)
+            + inverse * Vec3::new(0.0, label.offset, 0.0);
+    }
+}
@@ -5,17 +5,89 @@
 //! This plugin allows you to spawn hourglasses in Bevy games/apps.
 //! Hourglasses can be customized in terms of appearance, size, and behavior.
 
+mod accessibility;
+mod attachment;
+mod badge;
+mod bake;
+mod binding;
+mod collider;
 mod components;
-mod curves;
+mod culling;
+mod deadline;
+mod debug;
 mod events;
-mod mesh_hourglass;
+mod format;
+mod grid;
+mod interaction;
+mod interpolate;
+mod label;
+mod lighting;
+mod link;
+mod loading;
+mod mesh;
+#[cfg(feature = "persistence")]
+mod persistence;
 mod plugin;
+mod quality;
+mod randomizer;
+mod render_target;
+mod renderer;
+mod replay;
+mod resize;
 mod resources;
+mod shatter;
+mod simulation;
+mod spawn_animation;
+mod states;
 mod systems;
+mod theme;
+mod time_zone;
+mod tipping;
+mod tweening;
+mod ui_sync;
+mod units;
+mod warnings;
+pub mod wasm_support;
 
+pub use accessibility::*;
+pub use attachment::*;
+pub use badge::*;
+pub use bake::*;
+pub use binding::*;
+pub use collider::*;
 pub use components::*;
-pub use curves::*;
+pub use culling::*;
+pub use deadline::*;
+pub use debug::*;
 pub use events::*;
-pub use mesh_hourglass::*;
+pub use format::*;
+pub use grid::*;
+pub use hourglass_geometry::*;
+pub use interaction::*;
+pub use interpolate::*;
+pub use label::*;
+pub use lighting::*;
+pub use link::*;
+pub use loading::*;
+pub use mesh::*;
+#[cfg(feature = "persistence")]
+pub use persistence::*;
 pub use plugin::HourglassPlugin;
+pub use quality::*;
+pub use randomizer::*;
+pub use render_target::*;
+pub use renderer::*;
+pub use replay::*;
+pub use resize::*;
 pub use resources::*;
+pub use shatter::*;
+pub use simulation::*;
+pub use spawn_animation::*;
+pub use states::*;
+pub use theme::*;
+pub use time_zone::*;
+pub use tipping::*;
+pub use tweening::*;
+pub use ui_sync::*;
+pub use units::*;
+pub use warnings::{HourglassWarning, HourglassWarnings};
@@ -0,0 +1,201 @@
+//! Pseudo-3D lighting giving the flat glass a rounded look.
+
+use crate::mesh::HourglassParts;
+use crate::renderer::HourglassRenderSet;
+use bevy::asset::embedded_asset;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{AsBindGroup, AsBindGroupShaderType, ShaderRef};
+use bevy::render::texture::GpuImage;
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+
+/// Plugin lighting the glass of hourglasses with [`HourglassLit`] from a [`HourglassLight2d`]
+///
+/// The glass is drawn with [`HourglassLitMaterial`] instead of a `ColorMaterial`, shading its
+/// mesh as if it bulged out of the screen. Requires the render plugins.
+pub struct HourglassLightingPlugin;
+
+impl Plugin for HourglassLightingPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "lighting.wgsl");
+        app.add_plugins(Material2dPlugin::<HourglassLitMaterial>::default());
+
+        app.add_systems(
+            Update,
+            (apply_hourglass_lighting, update_hourglass_lighting)
+                .chain()
+                .after(HourglassRenderSet),
+        );
+    }
+}
+
+/// Component lighting the glass of an hourglass, see [`HourglassLightingPlugin`]
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HourglassLit {
+    /// Share of the glass color shown where the light doesn't reach
+    pub ambient: f32,
+    /// Brightness of the highlight facing the light
+    pub specular: f32,
+    /// Sharpness of the highlight, higher values make it smaller
+    pub shininess: f32,
+}
+
+impl Default for HourglassLit {
+    fn default() -> Self {
+        Self {
+            ambient: 0.4,
+            specular: 0.8,
+            shininess: 24.0,
+        }
+    }
+}
+
+/// A point light shining on lit hourglasses from above the screen
+///
+/// Move it with its `Transform`. Only the first light found is used.
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct HourglassLight2d {
+    pub color: Color,
+    pub intensity: f32,
+    /// Distance of the light in front of the screen (in pixels)
+    pub height: f32,
+}
+
+impl Default for HourglassLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            height: 150.0,
+        }
+    }
+}
+
+/// Material shading flat glass as if it were rounded, using the normals of the glass mesh
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[uniform(0, HourglassLitMaterialUniform)]
+pub struct HourglassLitMaterial {
+    pub color: Color,
+    pub lighting: HourglassLit,
+    /// World position and color of the light, kept up to date by the plugin
+    ///
+    /// Without a light the glass keeps its flat color.
+    pub light: Option<(Vec3, LinearRgba)>,
+}
+
+pub use uniform::HourglassLitMaterialUniform;
+
+mod uniform {
+    // The layout checks generated by `ShaderType` are flagged as unused outside the render world
+    #![allow(dead_code)]
+
+    use bevy::prelude::*;
+    use bevy::render::render_resource::ShaderType;
+
+    /// Uniform of [`HourglassLitMaterial`](super::HourglassLitMaterial) passed to its shader
+    #[derive(Clone, Default, ShaderType)]
+    pub struct HourglassLitMaterialUniform {
+        pub color: Vec4,
+        pub light_color: Vec4,
+        pub light_position: Vec3,
+        pub ambient: f32,
+        pub specular: f32,
+        pub shininess: f32,
+    }
+}
+
+impl AsBindGroupShaderType<HourglassLitMaterialUniform> for HourglassLitMaterial {
+    fn as_bind_group_shader_type(
+        &self,
+        _images: &RenderAssets<GpuImage>,
+    ) -> HourglassLitMaterialUniform {
+        let color = self.color.to_linear().to_vec4();
+        match self.light {
+            Some((position, light_color)) => HourglassLitMaterialUniform {
+                color,
+                light_color: light_color.to_vec4(),
+                light_position: position,
+                ambient: self.lighting.ambient,
+                specular: self.lighting.specular,
+                shininess: self.lighting.shininess,
+            },
+            None => HourglassLitMaterialUniform {
+                color,
+                ambient: 1.0,
+                shininess: 1.0,
+                ..default()
+            },
+        }
+    }
+}
+
+impl Material2d for HourglassLitMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_hourglass/lighting.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// System giving the glass of newly lit hourglasses a [`HourglassLitMaterial`]
+pub fn apply_hourglass_lighting(
+    mut commands: Commands,
+    color_materials: Res<Assets<ColorMaterial>>,
+    mut lit_materials: ResMut<Assets<HourglassLitMaterial>>,
+    hourglasses: Query<(&HourglassLit, &HourglassParts)>,
+    bodies: Query<&MeshMaterial2d<ColorMaterial>>,
+) {
+    for (lit, parts) in hourglasses.iter() {
+        let Some((body, handle)) = parts
+            .body
+            .and_then(|body| bodies.get(body).ok().map(|handle| (body, handle)))
+        else {
+            continue;
+        };
+        let color = color_materials
+            .get(&handle.0)
+            .map_or(Color::WHITE, |material| material.color);
+
+        let material = lit_materials.add(HourglassLitMaterial {
+            color,
+            lighting: lit.clone(),
+            light: None,
+        });
+        commands
+            .entity(body)
+            .remove::<MeshMaterial2d<ColorMaterial>>()
+            .insert(MeshMaterial2d(material));
+    }
+}
+
+/// System passing the light and lighting settings to the lit glass materials
+pub fn update_hourglass_lighting(
+    lights: Query<(&GlobalTransform, &HourglassLight2d)>,
+    hourglasses: Query<(&HourglassLit, &HourglassParts)>,
+    bodies: Query<&MeshMaterial2d<HourglassLitMaterial>>,
+    mut materials: ResMut<Assets<HourglassLitMaterial>>,
+) {
+    let light = lights.iter().next().map(|(transform, light)| {
+        (
+            transform.translation().truncate().extend(light.height),
+            light.color.to_linear() * light.intensity,
+        )
+    });
+
+    for (lit, parts) in hourglasses.iter() {
+        let Some(handle) = parts.body.and_then(|body| bodies.get(body).ok()) else {
+            continue;
+        };
+        // Only touch materials that changed, so they aren't uploaded again every frame
+        let outdated = materials
+            .get(&handle.0)
+            .is_some_and(|material| material.light != light || material.lighting != *lit);
+        if let (true, Some(material)) = (outdated, materials.get_mut(&handle.0)) {
+            material.light = light;
+            material.lighting = lit.clone();
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Pseudo-3D lighting for flat hourglass meshes, treating them as bulging out of the screen.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct HourglassLitMaterial {
+    color: vec4<f32>,
+    light_color: vec4<f32>,
+    light_position: vec3<f32>,
+    ambient: f32,
+    specular: f32,
+    shininess: f32,
+}
+
+@group(2) @binding(0) var<uniform> material: HourglassLitMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Normals lie in the plane along the silhouette, tilt them towards the viewer inside it
+    let flat_normal = in.world_normal.xy;
+    let bulge = min(length(flat_normal), 1.0);
+    let normal = normalize(vec3<f32>(flat_normal, sqrt(1.0 - bulge * bulge) + 1e-4));
+
+    let to_light = normalize(material.light_position - in.world_position.xyz);
+    let diffuse = max(dot(normal, to_light), 0.0);
+    let halfway = normalize(to_light + vec3<f32>(0.0, 0.0, 1.0));
+    let highlight = pow(max(dot(normal, halfway), 0.0), material.shininess) * material.specular;
+
+    let light = material.light_color.rgb;
+    let color = material.color.rgb * (material.ambient + diffuse * light) + highlight * light;
+    // Highlights stay visible on translucent glass
+    let alpha = clamp(material.color.a + highlight * (1.0 - material.color.a), 0.0, 1.0);
+    return vec4<f32>(color, alpha);
+}
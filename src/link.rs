@@ -0,0 +1,131 @@
+//! Linking hourglasses so the sand running out of one fills the next, for cascading timers.
+
+use crate::components::{Hourglass, HourglassValueSource};
+use crate::events::HourglassChainCompleteEvent;
+use bevy::prelude::*;
+
+/// Component linking two hourglasses, spawned on an entity of its own
+///
+/// While `from` drains, the upper chamber of `to` fills by the same amount and `to` waits. Once
+/// `from` is empty, `to` is full and starts running on its own timer. Refilling `from`, e.g. by
+/// flipping it, takes the sand back. Links can be chained, and
+/// [`HourglassChainCompleteEvent`] is sent when the last hourglass of a chain runs out.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_hourglass::{Hourglass, HourglassLink};
+///
+/// fn spawn_cascade(mut commands: Commands) {
+///     let stages: Vec<Entity> = (0..3)
+///         .map(|_| commands.spawn(Hourglass::new(10.0)).id())
+///         .collect();
+///     for link in HourglassLink::chain(&stages) {
+///         commands.spawn(link);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct HourglassLink {
+    /// Hourglass whose draining sand fills `to`
+    pub from: Entity,
+    /// Hourglass filled by the sand of `from`
+    pub to: Entity,
+    /// Whether `from` has run out and handed over to `to`
+    pub released: bool,
+    /// Whether the chain completion event has been sent for the current run of `to`
+    pub completed: bool,
+}
+
+impl HourglassLink {
+    /// Links the hourglass `from` to the hourglass `to`
+    pub fn new(from: Entity, to: Entity) -> Self {
+        Self {
+            from,
+            to,
+            released: false,
+            completed: false,
+        }
+    }
+
+    /// Links the given hourglasses one after the other
+    pub fn chain(hourglasses: &[Entity]) -> Vec<Self> {
+        hourglasses
+            .windows(2)
+            .map(|pair| Self::new(pair[0], pair[1]))
+            .collect()
+    }
+}
+
+/// System that moves the sand along hourglass links and detects completed chains
+///
+/// Links are updated from the start of their chains, so sand passes down a whole chain in the same
+/// frame.
+pub fn sync_hourglass_links(
+    mut links: Query<(Entity, &mut HourglassLink)>,
+    mut hourglasses: Query<&mut Hourglass>,
+    mut chain_complete_events: EventWriter<HourglassChainCompleteEvent>,
+) {
+    let pairs: Vec<(Entity, Entity)> = links.iter().map(|(_, link)| (link.from, link.to)).collect();
+    let mut order: Vec<(usize, Entity)> = links
+        .iter()
+        .map(|(entity, link)| (chain_position(&pairs, link.from).1, entity))
+        .collect();
+    order.sort_unstable();
+
+    // Hourglasses still waiting for the sand of an earlier hourglass in their chain
+    let mut waiting = Vec::new();
+
+    for (_, entity) in order {
+        let Ok((_, mut link)) = links.get_mut(entity) else {
+            continue;
+        };
+        let Ok(from_fill) = hourglasses.get(link.from).map(|from| from.upper_chamber) else {
+            continue;
+        };
+        let Ok(mut to) = hourglasses.get_mut(link.to) else {
+            continue;
+        };
+
+        let from_waiting = waiting.contains(&link.from);
+        if from_waiting || from_fill > 0.0 {
+            // Still draining, `to` holds what has come through so far
+            link.released = false;
+            link.completed = false;
+            to.running = false;
+            to.set_fill(if from_waiting { 0.0 } else { 1.0 - from_fill });
+            waiting.push(link.to);
+            continue;
+        }
+
+        if !link.released {
+            link.released = true;
+            to.set_fill(1.0);
+            to.running = to.value_source == HourglassValueSource::Timer;
+        }
+
+        // Only the last link of a chain reports its completion
+        let is_last = !pairs.iter().any(|&(from, _)| from == link.to);
+        if is_last && to.upper_chamber <= 0.0 && !link.completed {
+            link.completed = true;
+            chain_complete_events.write(HourglassChainCompleteEvent {
+                first: chain_position(&pairs, link.from).0,
+                last: link.to,
+            });
+        }
+    }
+}
+
+/// Follow the links back from `entity` to the first hourglass of its chain, returning it along
+/// with the number of links in between
+fn chain_position(pairs: &[(Entity, Entity)], mut entity: Entity) -> (Entity, usize) {
+    // Bounded by the number of links in case they form a cycle
+    let mut depth = 0;
+    while depth < pairs.len() {
+        match pairs.iter().find(|&&(_, to)| to == entity) {
+            Some(&(from, _)) => entity = from,
+            None => break,
+        }
+        depth += 1;
+    }
+    (entity, depth)
+}
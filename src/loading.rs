@@ -0,0 +1,192 @@
+//! Ready-made loading indicator built on top of the mesh hourglass.
+
+use crate::mesh::{
+    HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
+    HourglassMeshSandConfig,
+};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use hourglass_geometry::{BulbStyle, NeckStyle};
+use std::time::Duration;
+
+/// Configuration for a loading indicator spawned with [`spawn_loading_indicator`]
+#[derive(Debug, Clone)]
+pub struct LoadingIndicatorConfig {
+    /// Position of the indicator
+    pub position: Vec3,
+    /// Total height of the hourglass body
+    pub height: f32,
+    /// Color used for the sand, plates and text (the glass uses a translucent variant)
+    pub color: Color,
+    /// Time it takes for the sand to run through once (in seconds)
+    pub cycle_duration: f32,
+    /// Duration of the flip animation between cycles (in seconds)
+    pub flip_duration: f32,
+    /// Optional text shown below the hourglass, followed by animated dots
+    pub text: Option<String>,
+    /// Font size of the text
+    pub font_size: f32,
+}
+
+impl Default for LoadingIndicatorConfig {
+    fn default() -> Self {
+        Self {
+            position: Vec3::ZERO,
+            height: 150.0,
+            color: Color::WHITE,
+            cycle_duration: 1.5,
+            flip_duration: 0.5,
+            text: Some("Loading".to_string()),
+            font_size: 32.0,
+        }
+    }
+}
+
+/// Marker component for the root entity of a loading indicator
+#[derive(Component, Debug)]
+pub struct LoadingIndicator;
+
+/// Component for the animated dots following the loading text
+#[derive(Component, Debug)]
+pub struct LoadingIndicatorDot {
+    /// Delay applied every time the fade cycle restarts (in seconds)
+    pub delay: f32,
+    /// Timer driving the fade in/out cycle
+    pub timer: Timer,
+}
+
+/// Spawn a small auto-flipping hourglass with an optional animated "Loading..." text
+///
+/// Returns the root entity, which can be removed with [`despawn_loading_indicator`].
+pub fn spawn_loading_indicator(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    config: LoadingIndicatorConfig,
+) -> Entity {
+    // Proportions taken from the loading demo, scaled to the requested height
+    let scale = config.height / 150.0;
+    let plate_height = 8.0 * scale;
+
+    let root = commands
+        .spawn((
+            LoadingIndicator,
+            Transform::from_translation(config.position),
+            Visibility::default(),
+        ))
+        .id();
+
+    let hourglass = HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig {
+            total_height: config.height,
+            bulb_style: BulbStyle::Circular {
+                curvature: 1.0,
+                width_factor: 1.0,
+                curve_resolution: 20,
+            },
+            neck_style: NeckStyle::Curved {
+                curvature: 1.0,
+                width: 10.0 * scale,
+                height: 15.0 * scale,
+                curve_resolution: 10,
+            },
+            color: config.color.with_alpha(0.3),
+            ..default()
+        })
+        .with_plates(HourglassMeshPlatesConfig {
+            width: 140.0 * scale,
+            height: plate_height,
+            color: config.color,
+        })
+        .with_sand(HourglassMeshSandConfig {
+            color: config.color,
+            fill_percent: 1.0,
+            wall_offset: 3.0 * scale,
+            ..default()
+        })
+        .with_timing(config.cycle_duration)
+        .with_flip_duration(config.flip_duration)
+        .with_auto_flip(true)
+        .build(commands, meshes, materials);
+    commands.entity(root).add_child(hourglass);
+
+    if let Some(text) = config.text {
+        // Rough text metrics so the text and dots end up centered under the hourglass
+        let dot_spacing = config.font_size * 0.45;
+        let text_width = text.chars().count() as f32 * config.font_size * 0.5;
+        let start_x = -(text_width + 3.0 * dot_spacing) / 2.0;
+        let text_y = -(config.height / 2.0 + plate_height + config.font_size * 1.2);
+
+        let label = commands
+            .spawn((
+                Text2d::new(text),
+                TextFont {
+                    font_size: config.font_size,
+                    ..default()
+                },
+                TextColor(config.color),
+                Anchor::CenterLeft,
+                Transform::from_xyz(start_x, text_y, 1.0),
+            ))
+            .id();
+        commands.entity(root).add_child(label);
+
+        for i in 0..3 {
+            let dot = commands
+                .spawn((
+                    Text2d::new("."),
+                    TextFont {
+                        font_size: config.font_size,
+                        ..default()
+                    },
+                    TextColor(config.color),
+                    Anchor::CenterLeft,
+                    Transform::from_xyz(
+                        start_x + text_width + i as f32 * dot_spacing,
+                        text_y - config.font_size * 0.2,
+                        1.0,
+                    ),
+                    LoadingIndicatorDot {
+                        delay: i as f32 * 0.2,
+                        timer: Timer::from_seconds(0.8, TimerMode::Repeating),
+                    },
+                ))
+                .id();
+            commands.entity(root).add_child(dot);
+        }
+    }
+
+    root
+}
+
+/// Despawn a loading indicator previously spawned with [`spawn_loading_indicator`]
+pub fn despawn_loading_indicator(commands: &mut Commands, indicator: Entity) {
+    commands.entity(indicator).despawn();
+}
+
+/// System that fades the loading dots in and out one after the other
+pub fn animate_loading_indicator_dots(
+    time: Res<Time>,
+    mut dots: Query<(&mut TextColor, &mut LoadingIndicatorDot)>,
+) {
+    for (mut color, mut dot) in dots.iter_mut() {
+        dot.timer.tick(time.delta());
+
+        if dot.timer.just_finished() {
+            // Reset timer with delay
+            let delay = dot.delay;
+            dot.timer.reset();
+            dot.timer.tick(Duration::from_secs_f32(delay));
+        }
+
+        // Calculate alpha based on timer progress
+        let progress = dot.timer.fraction();
+        let alpha = if progress < 0.5 {
+            progress * 2.0
+        } else {
+            2.0 - (progress * 2.0)
+        };
+
+        color.0 = color.0.with_alpha(alpha.clamp(0.2, 1.0));
+    }
+}
@@ -0,0 +1,1202 @@
+//! Building mesh hourglasses and sharing their assets.
+
+use super::components::{
+    HourglassCustomMaterial, HourglassMesh, HourglassMeshBody, HourglassMeshPlate,
+    HourglassMeshSand, HourglassMeshSandState, HourglassMeshSandStream, HourglassMeshShape,
+    HourglassParts,
+};
+use super::config::{
+    EdgeQuality, HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassMeshSandConfig,
+    HourglassVisualConfig, PartOffsets, RenderStyle, SandOcclusion,
+};
+use super::systems::generate_sand_meshes;
+use crate::badge::HourglassBadgeConfig;
+use crate::binding::HourglassBinding;
+use crate::collider::{ColliderBackend, HourglassColliderBackend};
+use crate::components::{
+    DustMotes, DustMotesConfig, Hourglass, HourglassDuration, HourglassValueSource, NeckSparkle,
+    NeckSparkleConfig, SandOverflow, SandOverflowConfig, SandSplash, SandSplashConfig,
+    SandSplashRng, TimeOverflowPolicy,
+};
+use crate::culling::HourglassUpdateWhenHidden;
+use crate::interaction::HourglassInteractive;
+use crate::label::{spawn_hourglass_label, LabelConfig};
+use crate::lighting::HourglassLit;
+use crate::quality::QualityTier;
+use crate::resources::HourglassConfig;
+use crate::spawn_animation::{HourglassSpawnAnimation, SpawnAnim};
+use crate::states::HourglassActiveInState;
+use crate::theme::HourglassTheme;
+use crate::tipping::HourglassTipDetection;
+use crate::warnings::{clamp_config, report_warning, HourglassWarning};
+use bevy::{
+    prelude::*,
+    sprite::{AlphaMode2d, Anchor, Material2d},
+};
+use hourglass_geometry::{BulbStyle, ConfigKey, NeckStyle, SandBulb};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Cache of mesh and material assets shared between identical hourglasses
+///
+/// Assets are keyed by the parts of the configuration they were generated from. The cache doesn't
+/// keep assets alive, entries are reused for as long as some hourglass still uses them. The
+/// plugin provides a global instance as a resource, which [`HourglassMeshBuilder::build`] shares
+/// assets through. Pass it to [`HourglassMeshBuilder::build_with_cache`] to share them right away.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HourglassAssetCache {
+    meshes: HashMap<ConfigKey, AssetId<Mesh>>,
+    materials: HashMap<ConfigKey, AssetId<ColorMaterial>>,
+}
+
+impl HourglassAssetCache {
+    /// Number of cached mesh and material entries, including ones whose assets were dropped
+    pub fn len(&self) -> usize {
+        self.meshes.len() + self.materials.len()
+    }
+
+    /// Whether nothing has been cached yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all cached assets
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+        self.materials.clear();
+    }
+
+    /// Get the mesh for the configuration, creating it if it isn't cached or was dropped
+    fn mesh(
+        &mut self,
+        key: ConfigKey,
+        meshes: &mut Assets<Mesh>,
+        create: impl FnOnce() -> Mesh,
+    ) -> Handle<Mesh> {
+        if let Some(handle) = self
+            .meshes
+            .get(&key)
+            .and_then(|id| meshes.get_strong_handle(*id))
+        {
+            return handle;
+        }
+        let handle = meshes.add(create());
+        self.meshes.insert(key, handle.id());
+        handle
+    }
+
+    /// Get the material for the configuration, creating it if it isn't cached or was dropped
+    fn material(
+        &mut self,
+        key: ConfigKey,
+        materials: &mut Assets<ColorMaterial>,
+        create: impl FnOnce() -> ColorMaterial,
+    ) -> Handle<ColorMaterial> {
+        if let Some(handle) = self
+            .materials
+            .get(&key)
+            .and_then(|id| materials.get_strong_handle(*id))
+        {
+            return handle;
+        }
+        let handle = materials.add(create());
+        self.materials.insert(key, handle.id());
+        handle
+    }
+
+    /// Move the assets of a freshly built hourglass into the world's cache
+    ///
+    /// Parts whose assets are already cached in the world switch to the cached ones, dropping
+    /// their own, and the others are added to the world's cache for later hourglasses.
+    fn share_with_world(self, world: &mut World, hourglass: Entity) {
+        if !world.contains_resource::<HourglassAssetCache>() {
+            return;
+        }
+        world.resource_scope(|world, mut shared: Mut<HourglassAssetCache>| {
+            let mut mesh_swaps = HashMap::new();
+            if let Some(mut meshes) = world.get_resource_mut::<Assets<Mesh>>() {
+                for (key, id) in self.meshes {
+                    match shared
+                        .meshes
+                        .get(&key)
+                        .and_then(|shared_id| meshes.get_strong_handle(*shared_id))
+                    {
+                        Some(handle) if handle.id() != id => {
+                            mesh_swaps.insert(id, handle);
+                        }
+                        Some(_) => {}
+                        None => {
+                            shared.meshes.insert(key, id);
+                        }
+                    }
+                }
+            }
+            let mut material_swaps = HashMap::new();
+            if let Some(mut materials) = world.get_resource_mut::<Assets<ColorMaterial>>() {
+                for (key, id) in self.materials {
+                    match shared
+                        .materials
+                        .get(&key)
+                        .and_then(|shared_id| materials.get_strong_handle(*shared_id))
+                    {
+                        Some(handle) if handle.id() != id => {
+                            material_swaps.insert(id, handle);
+                        }
+                        Some(_) => {}
+                        None => {
+                            shared.materials.insert(key, id);
+                        }
+                    }
+                }
+            }
+            if mesh_swaps.is_empty() && material_swaps.is_empty() {
+                return;
+            }
+
+            let Some(parts) = world.get::<HourglassParts>(hourglass).copied() else {
+                return;
+            };
+            for part in [parts.body, parts.top_plate, parts.bottom_plate]
+                .into_iter()
+                .flatten()
+            {
+                let Ok(mut part) = world.get_entity_mut(part) else {
+                    continue;
+                };
+                if let Some(mut mesh) = part.get_mut::<Mesh2d>() {
+                    if let Some(handle) = mesh_swaps.get(&mesh.id()) {
+                        mesh.0 = handle.clone();
+                    }
+                }
+                if let Some(mut material) = part.get_mut::<MeshMaterial2d<ColorMaterial>>() {
+                    if let Some(handle) = material_swaps.get(&material.id()) {
+                        material.0 = handle.clone();
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Smallest distance of sand drawn behind the glass, so it never shares the body's z
+const MIN_BEHIND_GLASS_Z: f32 = 0.01;
+
+/// Type alias for the deferred insertion of a state scope, which is generic over the state type
+type StateScopeInserter = Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>;
+
+/// Type alias for swapping the `ColorMaterial` of parts for a material of any `Material2d` type
+type MaterialInserter = Box<dyn Fn(&mut EntityCommands) + Send + Sync>;
+
+/// Replace the `ColorMaterial` of a part with the given material
+fn material_inserter<M: Material2d>(handle: Handle<M>) -> MaterialInserter {
+    Box::new(move |entity: &mut EntityCommands| {
+        entity
+            .remove::<MeshMaterial2d<ColorMaterial>>()
+            .insert((MeshMaterial2d(handle.clone()), HourglassCustomMaterial));
+    })
+}
+
+/// Builder for creating a mesh-based hourglass
+#[derive(Default)]
+pub struct HourglassMeshBuilder {
+    transform: Transform,
+    body_config: Option<HourglassMeshBodyConfig>,
+    plates_config: Option<HourglassMeshPlatesConfig>,
+    sand_config: Option<HourglassMeshSandConfig>,
+    sand_splash_config: Option<SandSplashConfig>,
+    sand_splash_seed: Option<u64>,
+    neck_sparkle_config: Option<NeckSparkleConfig>,
+    dust_motes_config: Option<DustMotesConfig>,
+    sand_overflow_config: Option<SandOverflowConfig>,
+    timing: Option<f32>,
+    flip_duration: Option<f32>,
+    flip_easing: Option<EaseFunction>,
+    flow_curve: Option<EaseFunction>,
+    auto_flip: Option<bool>,
+    update_during_flip: Option<bool>,
+    base_rotation: Option<f32>,
+    speed: Option<f32>,
+    overflow_policy: Option<TimeOverflowPolicy>,
+    value_source: Option<HourglassValueSource>,
+    theme: Option<HourglassTheme>,
+    render_style: RenderStyle,
+    state_scope: Option<StateScopeInserter>,
+    body_material: Option<MaterialInserter>,
+    plates_material: Option<MaterialInserter>,
+    sand_material: Option<MaterialInserter>,
+    label: Option<(String, LabelConfig)>,
+    badge_config: Option<HourglassBadgeConfig>,
+    anchor: Anchor,
+    update_when_hidden: bool,
+    interactive: bool,
+    spawn_animation: Option<SpawnAnim>,
+    lighting: Option<HourglassLit>,
+    quality: Option<QualityTier>,
+    tip_detection: Option<HourglassTipDetection>,
+    binding: Option<HourglassBinding>,
+    collider: Option<Arc<dyn ColliderBackend>>,
+    global_config: Option<HourglassConfig>,
+    part_offsets: PartOffsets,
+    sand_occlusion: SandOcclusion,
+}
+
+impl HourglassMeshBuilder {
+    /// Creates a new hourglass builder with the specified transform
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            body_config: None,
+            plates_config: None,
+            sand_config: None,
+            sand_splash_config: None,
+            sand_splash_seed: None,
+            neck_sparkle_config: None,
+            dust_motes_config: None,
+            sand_overflow_config: None,
+            timing: None,
+            flip_duration: None,
+            flip_easing: None,
+            flow_curve: None,
+            auto_flip: None,
+            update_during_flip: None,
+            base_rotation: None,
+            speed: None,
+            overflow_policy: None,
+            value_source: None,
+            theme: None,
+            render_style: RenderStyle::Filled,
+            state_scope: None,
+            body_material: None,
+            plates_material: None,
+            sand_material: None,
+            label: None,
+            badge_config: None,
+            anchor: Anchor::Center,
+            update_when_hidden: false,
+            interactive: false,
+            spawn_animation: None,
+            lighting: None,
+            quality: None,
+            tip_detection: None,
+            binding: None,
+            collider: None,
+            global_config: None,
+            part_offsets: PartOffsets::default(),
+            sand_occlusion: SandOcclusion::InFront,
+        }
+    }
+
+    /// Creates a builder for an hourglass with the given look, at the origin
+    ///
+    /// Use [`Self::with_visual_config`] on a builder from [`Self::new`] to place it elsewhere.
+    pub fn styled(config: HourglassVisualConfig) -> Self {
+        Self::new(Transform::IDENTITY).with_visual_config(config)
+    }
+
+    /// Sets the body, plates, sand, splash and render style at once
+    ///
+    /// Replaces all of them, including removing plates or splashes the config doesn't have.
+    pub fn with_visual_config(mut self, config: HourglassVisualConfig) -> Self {
+        self.body_config = Some(config.body);
+        self.plates_config = config.plates;
+        self.sand_config = Some(config.sand);
+        self.sand_splash_config = config.sand_splash;
+        self.render_style = config.render_style;
+        self
+    }
+
+    /// Adds a body configuration to the hourglass
+    pub fn with_body(mut self, config: HourglassMeshBodyConfig) -> Self {
+        self.body_config = Some(config);
+        self
+    }
+
+    /// Adds plates configuration to the hourglass
+    pub fn with_plates(mut self, config: HourglassMeshPlatesConfig) -> Self {
+        self.plates_config = Some(config);
+        self
+    }
+
+    /// Moves the parts relative to the body, e.g. overlapping plates or sand behind the glass
+    pub fn with_part_offsets(mut self, offsets: PartOffsets) -> Self {
+        self.part_offsets = offsets;
+        self
+    }
+
+    /// Sets whether the sand is drawn in front of or behind the glass
+    pub fn with_sand_occlusion(mut self, occlusion: SandOcclusion) -> Self {
+        self.sand_occlusion = occlusion;
+        self
+    }
+
+    /// Draws the glass with a custom material instead of a `ColorMaterial`, e.g. with a shader
+    ///
+    /// The material type must be registered with a `Material2dPlugin`. The plugin leaves custom
+    /// materials untouched, so colors from the body config, themes and fades don't apply.
+    pub fn with_body_material<M: Material2d>(mut self, material: Handle<M>) -> Self {
+        self.body_material = Some(material_inserter(material));
+        self
+    }
+
+    /// Draws both plates with a custom material, see [`Self::with_body_material`]
+    pub fn with_plates_material<M: Material2d>(mut self, material: Handle<M>) -> Self {
+        self.plates_material = Some(material_inserter(material));
+        self
+    }
+
+    /// Draws the sand of both bulbs and the falling stream with a custom material, see
+    /// [`Self::with_body_material`]
+    ///
+    /// The sand meshes keep being regenerated as it flows, only the material is left alone.
+    pub fn with_sand_material<M: Material2d>(mut self, material: Handle<M>) -> Self {
+        self.sand_material = Some(material_inserter(material));
+        self
+    }
+
+    /// Adds sand configuration to the hourglass
+    pub fn with_sand(mut self, config: HourglassMeshSandConfig) -> Self {
+        self.sand_config = Some(config);
+        self
+    }
+
+    /// Adds automatic timing to the hourglass with the specified duration in seconds
+    pub fn with_timing(mut self, duration: f32) -> Self {
+        self.timing = Some(duration);
+        self
+    }
+
+    /// Adds automatic timing to the hourglass with the specified duration
+    pub fn with_duration(self, duration: HourglassDuration) -> Self {
+        self.with_timing(duration.as_secs_f32())
+    }
+
+    /// Adds automatic timing to the hourglass for the given number of seconds (must not be zero)
+    pub fn with_seconds(self, seconds: u32) -> Self {
+        self.with_duration(HourglassDuration::seconds(seconds))
+    }
+
+    /// Adds automatic timing to the hourglass for the given number of minutes (must not be zero)
+    pub fn with_minutes(self, minutes: u32) -> Self {
+        self.with_duration(HourglassDuration::minutes(minutes))
+    }
+
+    /// Adds automatic timing for a 25 minute pomodoro work session
+    pub fn with_pomodoro(self) -> Self {
+        self.with_duration(HourglassDuration::POMODORO)
+    }
+
+    /// Sets the flip animation duration
+    pub fn with_flip_duration(mut self, duration: f32) -> Self {
+        self.flip_duration = Some(duration);
+        self
+    }
+
+    /// Sets the easing applied to the flip rotation
+    pub fn with_flip_easing(mut self, easing: EaseFunction) -> Self {
+        self.flip_easing = Some(easing);
+        self
+    }
+
+    /// Sets the easing of the shown drain (requires sand)
+    pub fn with_flow_curve(mut self, flow_curve: EaseFunction) -> Self {
+        self.flow_curve = Some(flow_curve);
+        self
+    }
+
+    /// Sets whether sand keeps flowing while the hourglass is flipping
+    pub fn with_update_during_flip(mut self, update_during_flip: bool) -> Self {
+        self.update_during_flip = Some(update_during_flip);
+        self
+    }
+
+    /// Sets whether the hourglass should auto-flip when empty
+    pub fn with_auto_flip(mut self, auto_flip: bool) -> Self {
+        self.auto_flip = Some(auto_flip);
+        self
+    }
+
+    /// Sets the resting rotation of the hourglass in radians (e.g. mounted at an angle)
+    ///
+    /// Flips rotate 180° relative to this rotation and sand surfaces stay level with gravity.
+    pub fn with_base_rotation(mut self, rotation: f32) -> Self {
+        self.base_rotation = Some(rotation);
+        self
+    }
+
+    /// Sets the speed multiplier of the hourglass, e.g. 0.5 for slow motion or 2.0 for haste
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Sets what happens to time added beyond the total time
+    pub fn with_overflow_policy(mut self, policy: TimeOverflowPolicy) -> Self {
+        self.overflow_policy = Some(policy);
+        self
+    }
+
+    /// Sets what drives the sand levels
+    ///
+    /// With [`HourglassValueSource::External`] the hourglass does not run as a timer and its fill
+    /// is set through [`Hourglass::set_fill`], starting from the sand config's fill percentage.
+    pub fn with_value_source(mut self, value_source: HourglassValueSource) -> Self {
+        self.value_source = Some(value_source);
+        self
+    }
+
+    /// Sets how the glass and plates are drawn
+    ///
+    /// [`RenderStyle::Minimal`] ignores any plates configuration.
+    pub fn with_render_style(mut self, render_style: RenderStyle) -> Self {
+        self.render_style = render_style;
+        self
+    }
+
+    /// Colors the body, plates and sand with the theme's effective colors
+    ///
+    /// Only affects the parts that are configured, and overrides the colors in their configs.
+    pub fn with_theme(mut self, theme: HourglassTheme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Adds sand splash configuration to the hourglass
+    pub fn with_sand_splash(mut self, config: SandSplashConfig) -> Self {
+        self.sand_splash_config = Some(config);
+        self
+    }
+
+    /// Seeds the placement of sand splash particles so splashes are reproducible
+    pub fn with_sand_splash_seed(mut self, seed: u64) -> Self {
+        self.sand_splash_seed = Some(seed);
+        self
+    }
+
+    /// Adds a twinkling glitter effect at the neck while sand is flowing
+    pub fn with_neck_sparkle(mut self, config: NeckSparkleConfig) -> Self {
+        self.neck_sparkle_config = Some(config);
+        self
+    }
+
+    /// Uses the defaults of the global configuration for everything that is not configured
+    ///
+    /// Without a body, a default body as tall as [`HourglassConfig::default_size`] is added in the
+    /// default container color, and without sand, default sand in the default sand color. The flip
+    /// behavior, flow curve and sand splash also fall back to the configured defaults.
+    pub fn with_global_config(mut self, config: &HourglassConfig) -> Self {
+        self.global_config = Some(config.clone());
+        self
+    }
+
+    /// Adds dust motes drifting in the empty parts of the bulbs (requires sand)
+    pub fn with_dust_motes(mut self, config: DustMotesConfig) -> Self {
+        self.dust_motes_config = Some(config);
+        self
+    }
+
+    /// Adds a pile of sand growing around the bottom plate once the hourglass is empty (requires
+    /// sand)
+    pub fn with_sand_overflow(mut self, config: SandOverflowConfig) -> Self {
+        self.sand_overflow_config = Some(config);
+        self
+    }
+
+    /// Plays an animation when the hourglass appears, see also
+    /// [`despawn_with_animation`](crate::despawn_with_animation)
+    pub fn with_spawn_animation(mut self, anim: SpawnAnim) -> Self {
+        self.spawn_animation = Some(anim);
+        self
+    }
+
+    /// Lights the glass from a [`HourglassLight2d`](crate::HourglassLight2d) for a rounded look
+    ///
+    /// Requires [`HourglassLightingPlugin`](crate::HourglassLightingPlugin).
+    pub fn with_lighting(mut self, lighting: HourglassLit) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Overrides the global level of detail of the sand for this hourglass
+    pub fn with_quality(mut self, quality: QualityTier) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Stops the sand while the hourglass lies on its side, e.g. knocked over by physics
+    pub fn with_tip_detection(mut self, detection: HourglassTipDetection) -> Self {
+        self.tip_detection = Some(detection);
+        self
+    }
+
+    /// Keeps the sand and splashes updated while the hourglass is hidden or off-screen
+    ///
+    /// By default they are skipped for hourglasses that aren't visible, see [`HourglassCulled`](crate::HourglassCulled).
+    pub fn with_update_when_hidden(mut self) -> Self {
+        self.update_when_hidden = true;
+        self
+    }
+
+    /// Sends [`HourglassInteractionEvent`](crate::HourglassInteractionEvent)s when the hourglass
+    /// or its parts are clicked
+    pub fn with_interaction(mut self) -> Self {
+        self.interactive = true;
+        self
+    }
+
+    /// Turns the hourglass into a gauge following a value computed from the world every frame
+    ///
+    /// The value is the fill of the upper chamber (0.0 - 1.0), see [`HourglassBinding`].
+    pub fn bind_to(self, value: impl Fn(&World) -> f32 + Send + Sync + 'static) -> Self {
+        self.with_binding(HourglassBinding::new(value))
+    }
+
+    /// Gives the hourglass a physics collider, inserted by the backend of a physics engine
+    ///
+    /// Requires a body. The convex parts are also kept in an [`HourglassCollider`].
+    pub fn with_collider(mut self, backend: impl ColliderBackend) -> Self {
+        self.collider = Some(Arc::new(backend));
+        self
+    }
+
+    /// Turns the hourglass into a gauge driven by the given binding
+    pub fn with_binding(mut self, binding: HourglassBinding) -> Self {
+        self.value_source = Some(HourglassValueSource::External);
+        self.binding = Some(binding);
+        self
+    }
+
+    /// Sets which point of the hourglass is placed at the entity's transform
+    ///
+    /// For example [`Anchor::BottomCenter`] makes the hourglass stand on its transform's
+    /// position. Flips and rotations pivot around the anchor. Requires a body configuration.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Configures the numeric badge shown with [`Hourglass::set_badge`]
+    pub fn with_badge(mut self, config: HourglassBadgeConfig) -> Self {
+        self.badge_config = Some(config);
+        self
+    }
+
+    /// Adds a text label above or below the hourglass, kept upright during flips
+    pub fn with_label(mut self, text: impl Into<String>, config: LabelConfig) -> Self {
+        self.label = Some((text.into(), config));
+        self
+    }
+
+    /// Only runs the hourglass while the app is in the given state
+    ///
+    /// Requires [`HourglassStatePlugin`](crate::states::HourglassStatePlugin) for the state type.
+    pub fn with_active_in_state<S: States>(self, state: S) -> Self {
+        self.with_state_scope(HourglassActiveInState::new(state))
+    }
+
+    /// Scopes the hourglass to a state, e.g. to also hide it outside of the state
+    pub fn with_state_scope<S: States>(mut self, scope: HourglassActiveInState<S>) -> Self {
+        self.state_scope = Some(Box::new(move |entity: &mut EntityCommands| {
+            entity.insert(scope);
+        }));
+        self
+    }
+
+    /// Position of the hourglass' center relative to its anchor
+    fn center_offset(&self) -> Vec2 {
+        self.body_config.as_ref().map_or(Vec2::ZERO, |body_config| {
+            -self.anchor.as_vec() * Self::visual_size(body_config, self.plates_config.as_ref())
+        })
+    }
+
+    /// Builds the hourglass entity and all its configured components
+    ///
+    /// Body and plate assets are shared with identical hourglasses through the plugin's
+    /// [`HourglassAssetCache`] once the commands are applied.
+    pub fn build(
+        self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+    ) -> Entity {
+        // Assets are shared through the plugin's cache once the hourglass is spawned
+        let mut cache = HourglassAssetCache::default();
+        let hourglass = self.build_with_cache(commands, meshes, materials, &mut cache);
+        commands.queue(move |world: &mut World| cache.share_with_world(world, hourglass));
+        hourglass
+    }
+
+    /// Builds the hourglass, reusing body and plate meshes and materials from the cache
+    ///
+    /// Hourglasses built with the same cache and identical shapes or colors share assets, e.g.
+    /// using the plugin's `ResMut<HourglassAssetCache>`. Sand meshes and materials are animated
+    /// per hourglass and never shared.
+    pub fn build_with_cache(
+        mut self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+        cache: &mut HourglassAssetCache,
+    ) -> Entity {
+        if let RenderStyle::Minimal { .. } = self.render_style {
+            self.plates_config = None;
+        }
+
+        if let Some(config) = &self.global_config {
+            self.body_config
+                .get_or_insert_with(|| HourglassMeshBodyConfig {
+                    total_height: config.default_size.y,
+                    color: config.default_container_color,
+                    ..default()
+                });
+            self.sand_config
+                .get_or_insert_with(|| HourglassMeshSandConfig {
+                    color: config.default_sand_color,
+                    ..default()
+                });
+            self.flip_duration
+                .get_or_insert(config.default_flip_duration);
+            self.auto_flip.get_or_insert(config.default_auto_flip);
+            self.flow_curve.get_or_insert(config.default_flow_curve);
+            if self.sand_splash_config.is_none() {
+                self.sand_splash_config = config.default_sand_splash.clone();
+            }
+        }
+
+        if let (Some(flow_curve), Some(sand_config)) = (self.flow_curve, &mut self.sand_config) {
+            sand_config.flow_curve = flow_curve;
+        }
+
+        if let Some(sand_config) = &mut self.sand_config {
+            sand_config.fill_percent =
+                clamp_config("sand fill_percent", sand_config.fill_percent, 0.0, 1.0);
+            if self.body_config.is_none() {
+                report_warning(HourglassWarning::SandWithoutBody);
+            }
+        }
+        if let Some(duration) = &mut self.timing {
+            *duration = clamp_config("timing duration", *duration, 0.0, f32::MAX);
+        }
+
+        if let Some(theme) = &self.theme {
+            let colors = theme.effective_colors();
+            if let Some(body_config) = &mut self.body_config {
+                body_config.color = colors.glass_color;
+                body_config.emissive = theme.emissive;
+            }
+            if let Some(plates_config) = &mut self.plates_config {
+                plates_config.color = colors.plate_color;
+            }
+            if let Some(sand_config) = &mut self.sand_config {
+                sand_config.color = colors.sand_color;
+                sand_config.emissive = theme.emissive;
+            }
+        }
+
+        // Create parent entity for the hourglass
+        let mut transform = self.transform;
+        if let Some(base_rotation) = self.base_rotation {
+            transform.rotation = Quat::from_rotation_z(base_rotation);
+        }
+        let mut entity_commands = commands.spawn((HourglassMesh, transform));
+
+        // Always add Hourglass component - use timing if specified, otherwise create default
+        let mut hourglass = if let Some(duration) = self.timing {
+            Hourglass::new(duration)
+        } else {
+            // Create default hourglass but don't start it running
+            Hourglass {
+                running: false,
+                ..Default::default()
+            }
+        };
+
+        // Apply flip configuration
+        if let Some(flip_duration) = self.flip_duration {
+            hourglass.flip_duration = flip_duration;
+        }
+        if let Some(auto_flip) = self.auto_flip {
+            hourglass.auto_flip_when_empty = auto_flip;
+        }
+        if let Some(flip_easing) = self.flip_easing {
+            hourglass.flip_easing = flip_easing;
+        }
+        if let Some(update_during_flip) = self.update_during_flip {
+            hourglass.update_during_flip = update_during_flip;
+        }
+        if let Some(base_rotation) = self.base_rotation {
+            hourglass.base_rotation = base_rotation;
+        }
+        if let Some(speed) = self.speed {
+            hourglass.speed = speed;
+        }
+        if let Some(policy) = self.overflow_policy {
+            hourglass.overflow_policy = policy;
+        }
+        if let Some(value_source) = self.value_source {
+            hourglass.value_source = value_source;
+            if value_source == HourglassValueSource::External {
+                hourglass.running = false;
+                hourglass.set_fill(
+                    self.sand_config
+                        .as_ref()
+                        .map_or(hourglass.upper_chamber, |sand| sand.fill_percent),
+                );
+            }
+        }
+
+        // Keep the hourglass colors in sync with the configured parts, since the sand color is
+        // synced back from the hourglass
+        if let Some(body_config) = &self.body_config {
+            hourglass.container_color = body_config.color;
+        }
+        if let Some(sand_config) = &self.sand_config {
+            hourglass.sand_color = sand_config.color;
+        }
+
+        // Record the overall size of the visual so layout helpers can fit it
+        if let Some(body_config) = &self.body_config {
+            hourglass.size = Self::visual_size(body_config, self.plates_config.as_ref());
+        }
+
+        let visual_size = hourglass.size;
+        entity_commands.insert(hourglass);
+
+        // Add state scope if configured
+        if let Some(insert_state_scope) = self.state_scope.take() {
+            insert_state_scope(&mut entity_commands);
+        }
+
+        // Add sand splash if configured
+        if let Some(sand_splash_config) = &self.sand_splash_config {
+            entity_commands.insert(SandSplash::new(sand_splash_config.clone()));
+            if let Some(seed) = self.sand_splash_seed {
+                entity_commands.insert(SandSplashRng::seeded(seed));
+            }
+        }
+
+        if self.update_when_hidden {
+            entity_commands.insert(HourglassUpdateWhenHidden);
+        }
+
+        if self.interactive {
+            entity_commands.insert(HourglassInteractive);
+        }
+
+        if let Some(binding) = self.binding.take() {
+            entity_commands.insert(binding);
+        }
+
+        if let Some(badge_config) = self.badge_config.take() {
+            entity_commands.insert(badge_config);
+        }
+
+        if let Some(lighting) = &self.lighting {
+            entity_commands.insert(lighting.clone());
+        }
+        if let Some(quality) = self.quality {
+            entity_commands.insert(quality);
+        }
+        if let Some(detection) = self.tip_detection.take() {
+            entity_commands.insert(detection);
+        }
+
+        // Add spawn animation if configured
+        if let Some(anim) = self.spawn_animation {
+            entity_commands.insert(HourglassSpawnAnimation::appear(anim));
+        }
+
+        // Add neck sparkle if configured
+        if let Some(neck_sparkle_config) = &self.neck_sparkle_config {
+            entity_commands.insert(NeckSparkle::new(neck_sparkle_config.clone()));
+        }
+
+        let hourglass_entity = entity_commands.id();
+        let mut parts = HourglassParts::default();
+
+        // Add label if configured
+        if let Some((text, label_config)) = self.label.take() {
+            parts.label = Some(spawn_hourglass_label(
+                commands,
+                hourglass_entity,
+                self.center_offset(),
+                visual_size,
+                text,
+                label_config,
+            ));
+        }
+
+        // Add body if configured
+        if let Some(body_config) = &self.body_config {
+            let shape = HourglassMeshShape {
+                body_config: body_config.clone(),
+                plates_config: self.plates_config.clone(),
+                render_style: self.render_style,
+            };
+            if let Some(backend) = &self.collider {
+                let backend = HourglassColliderBackend::new(backend.clone(), self.center_offset());
+                let mut entity_commands = commands.entity(hourglass_entity);
+                backend.insert_collider(&mut entity_commands, &shape);
+                entity_commands.insert(backend);
+            }
+            commands.entity(hourglass_entity).insert(shape);
+            let body_entity = self.spawn_body(commands, meshes, materials, body_config, cache);
+            commands.entity(hourglass_entity).add_child(body_entity);
+            parts.body = Some(body_entity);
+        }
+
+        // Add plates if configured
+        if let Some(plates_config) = &self.plates_config {
+            let (top_plate, bottom_plate) =
+                self.spawn_plates(commands, meshes, materials, plates_config, cache);
+            commands
+                .entity(hourglass_entity)
+                .add_child(top_plate)
+                .add_child(bottom_plate);
+            parts.top_plate = Some(top_plate);
+            parts.bottom_plate = Some(bottom_plate);
+        }
+
+        // Add sand if configured
+        if let Some(sand_config) = &self.sand_config {
+            if let Some(body_config) = &self.body_config {
+                // Sand state component for animation support
+                let sand_state = HourglassMeshSandState {
+                    fill_percent: sand_config.fill_percent,
+                    body_config: body_config.clone(),
+                    sand_config: sand_config.clone(),
+                    tilt: self.base_rotation.unwrap_or(0.0),
+                    surface_phase: 0.0,
+                    center_offset: self.center_offset(),
+                    flips: 0,
+                    built_sand_config: sand_config.clone(),
+                    needs_update: false,
+                };
+
+                let (top_sand, bottom_sand, stream) =
+                    self.spawn_sand(commands, meshes, materials, &sand_state);
+                commands
+                    .entity(hourglass_entity)
+                    .add_child(top_sand)
+                    .add_child(bottom_sand)
+                    .add_child(stream)
+                    .insert(sand_state);
+                parts.top_sand = Some(top_sand);
+                parts.bottom_sand = Some(bottom_sand);
+                parts.stream = Some(stream);
+
+                // Dust motes are drawn behind the sand and start drifting on the next update
+                if let Some(dust_motes_config) = &self.dust_motes_config {
+                    let dust_motes = commands
+                        .spawn((
+                            DustMotes::new(dust_motes_config.clone()),
+                            Mesh2d(meshes.add(Self::create_quads_mesh(&[], 0.0))),
+                            MeshMaterial2d(materials.add(ColorMaterial {
+                                color: dust_motes_config.color,
+                                alpha_mode: AlphaMode2d::Blend,
+                                ..default()
+                            })),
+                            Transform::from_translation(self.center_offset().extend(0.05)),
+                        ))
+                        .id();
+                    commands.entity(hourglass_entity).add_child(dust_motes);
+                    parts.dust_motes = Some(dust_motes);
+                }
+
+                // The overflow pile is placed and grown by `update_sand_overflow`
+                if let Some(overflow_config) = &self.sand_overflow_config {
+                    let color = overflow_config
+                        .color
+                        .unwrap_or_else(|| sand_config.bulb_material_color(SandBulb::Bottom));
+                    let overflow = commands
+                        .spawn((
+                            SandOverflow::new(overflow_config.clone()),
+                            Mesh2d(meshes.add(Self::create_sand_pile_mesh())),
+                            MeshMaterial2d(materials.add(color)),
+                            Transform::default(),
+                            Visibility::Hidden,
+                        ))
+                        .id();
+                    commands.entity(hourglass_entity).add_child(overflow);
+                    parts.overflow = Some(overflow);
+                }
+            }
+        }
+
+        // Swap in custom materials once all parts are spawned
+        for (insert_material, part_entities) in [
+            (&self.body_material, vec![parts.body]),
+            (
+                &self.plates_material,
+                vec![parts.top_plate, parts.bottom_plate],
+            ),
+            (
+                &self.sand_material,
+                vec![parts.top_sand, parts.bottom_sand, parts.stream],
+            ),
+        ] {
+            if let Some(insert_material) = insert_material {
+                for part in part_entities.into_iter().flatten() {
+                    insert_material(&mut commands.entity(part));
+                }
+            }
+        }
+
+        commands.entity(hourglass_entity).insert(parts);
+        hourglass_entity
+    }
+
+    /// Spawns just the hourglass body using the new curve system
+    fn spawn_body(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+        config: &HourglassMeshBodyConfig,
+        cache: &mut HourglassAssetCache,
+    ) -> Entity {
+        let render_style = self.render_style;
+        let mesh_key = ConfigKey::new(&"body")
+            .with_f32s([config.total_height])
+            .with(&config.bulb_style)
+            .with(&config.neck_style)
+            .with(&config.outline_spacing.is_some())
+            .with_f32s(config.outline_spacing)
+            .with(&config.uv_mode)
+            .with(&config.edge_quality)
+            .with(&render_style);
+        let mesh = cache.mesh(mesh_key, meshes, || {
+            Self::create_body_mesh(config, render_style)
+        });
+
+        let color = match render_style {
+            RenderStyle::Filled => config.material_color(),
+            RenderStyle::Minimal { .. } => config.material_color().with_alpha(1.0),
+        };
+
+        // Create glass material with transparency
+        let material_key = ConfigKey::new(&"glass").with_f32s(color.to_linear().to_f32_array());
+        let glass_material = cache.material(material_key, materials, || ColorMaterial {
+            color,
+            alpha_mode: AlphaMode2d::Blend,
+            ..default()
+        });
+
+        commands
+            .spawn((
+                HourglassMeshBody,
+                Mesh2d(mesh),
+                MeshMaterial2d(glass_material),
+                Transform::from_translation(self.center_offset().extend(0.0))
+                    .with_scale(self.part_offsets.body_scale.extend(1.0)),
+            ))
+            .id()
+    }
+
+    /// Spawns the top and bottom plates
+    fn spawn_plates(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+        config: &HourglassMeshPlatesConfig,
+        cache: &mut HourglassAssetCache,
+    ) -> (Entity, Entity) {
+        let mesh_key = ConfigKey::new(&"plate").with_f32s([config.width, config.height]);
+        let plate_mesh_handle = cache.mesh(mesh_key, meshes, || Self::create_plate_mesh(config));
+        let material_key =
+            ConfigKey::new(&"plate").with_f32s(config.color.to_linear().to_f32_array());
+        let plate_material = cache.material(material_key, materials, || config.color.into());
+
+        // Get the total height from body config or use a default
+        let total_height = self
+            .body_config
+            .as_ref()
+            .map(|cfg| cfg.total_height)
+            .unwrap_or(200.0);
+
+        let half_total_height = total_height / 2.0 + self.part_offsets.plate_offset;
+        let offset = self.center_offset();
+
+        // Spawn top plate
+        let top_plate = commands
+            .spawn((
+                HourglassMeshPlate::Top,
+                Mesh2d(plate_mesh_handle.clone()),
+                MeshMaterial2d(plate_material.clone()),
+                Transform::from_xyz(
+                    offset.x,
+                    offset.y + half_total_height + config.height / 2.0,
+                    0.0,
+                ),
+            ))
+            .id();
+
+        // Spawn bottom plate
+        let bottom_plate = commands
+            .spawn((
+                HourglassMeshPlate::Bottom,
+                Mesh2d(plate_mesh_handle),
+                MeshMaterial2d(plate_material),
+                Transform::from_xyz(
+                    offset.x,
+                    offset.y - half_total_height - config.height / 2.0,
+                    0.0,
+                ),
+            ))
+            .id();
+
+        (top_plate, bottom_plate)
+    }
+
+    /// Spawns the sand inside the hourglass using the new curve system
+    fn spawn_sand(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+        sand_state: &HourglassMeshSandState,
+    ) -> (Entity, Entity, Entity) {
+        let sand_config = &sand_state.sand_config;
+        let offset = sand_state.center_offset;
+        let sand_z = match self.sand_occlusion {
+            SandOcclusion::InFront => self.part_offsets.sand_z,
+            SandOcclusion::BehindGlass => -self.part_offsets.sand_z.abs().max(MIN_BEHIND_GLASS_Z),
+        };
+
+        // Create materials for sand, one per bulb and one for the stream so they can differ,
+        // blending feathered edges into the glass
+        let feathered = matches!(
+            sand_state.body_config.edge_quality,
+            EdgeQuality::Feathered { .. }
+        );
+        let mut sand_material = |color: Color| {
+            let mut material = ColorMaterial::from(color);
+            if feathered {
+                material.alpha_mode = AlphaMode2d::Blend;
+            }
+            materials.add(material)
+        };
+        let top_material = sand_material(sand_config.bulb_material_color(SandBulb::Top));
+        let bottom_material = sand_material(sand_config.bulb_material_color(SandBulb::Bottom));
+        let stream_material = sand_material(sand_config.stream_material_color());
+
+        // Same meshes as the updates would generate, at the hourglass' quality tier
+        let quality = self
+            .quality
+            .or(self.global_config.as_ref().map(|config| config.quality))
+            .unwrap_or_default();
+        let [top_mesh, bottom_mesh] = generate_sand_meshes(sand_state, quality, None);
+
+        let top_sand_entity = if let Some(mesh) = top_mesh {
+            commands
+                .spawn((
+                    HourglassMeshSand::TopBulb,
+                    Mesh2d(meshes.add(mesh)),
+                    MeshMaterial2d(top_material),
+                    Transform::from_translation(offset.extend(sand_z)),
+                ))
+                .id()
+        } else {
+            // Empty top bulb, keeping the material so refilling reuses it
+            commands
+                .spawn((
+                    HourglassMeshSand::TopBulb,
+                    MeshMaterial2d(top_material),
+                    Transform::from_translation(offset.extend(sand_z)),
+                ))
+                .id()
+        };
+
+        let bottom_sand_entity = if let Some(mesh) = bottom_mesh {
+            commands
+                .spawn((
+                    HourglassMeshSand::BottomBulb,
+                    Mesh2d(meshes.add(mesh)),
+                    MeshMaterial2d(bottom_material),
+                    Transform::from_translation(offset.extend(sand_z)),
+                ))
+                .id()
+        } else {
+            // Empty bottom bulb, keeping the material so refilling reuses it
+            commands
+                .spawn((
+                    HourglassMeshSand::BottomBulb,
+                    MeshMaterial2d(bottom_material),
+                    Transform::from_translation(offset.extend(sand_z)),
+                ))
+                .id()
+        };
+
+        // Falling grains start out empty and are animated by `update_sand_stream_grains`
+        let stream_entity = commands
+            .spawn((
+                HourglassMeshSandStream::default(),
+                Mesh2d(meshes.add(Self::create_quads_mesh(&[], 0.0))),
+                MeshMaterial2d(stream_material),
+                Transform::from_translation(offset.extend(sand_z)),
+            ))
+            .id();
+
+        (top_sand_entity, bottom_sand_entity, stream_entity)
+    }
+}
+
+/// Spawn a mesh-based hourglass with automatic timing and default configuration
+pub fn spawn_mesh_hourglass_with_timer(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    duration: f32,
+    position: Vec3,
+) -> Entity {
+    HourglassMeshBuilder::new(Transform::from_translation(position))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(duration)
+        .build(commands, meshes, materials)
+}
+
+/// Spawn a mesh-based hourglass with flip configuration
+pub fn spawn_mesh_hourglass_with_flip(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    duration: f32,
+    position: Vec3,
+    flip_duration: f32,
+    auto_flip: bool,
+) -> Entity {
+    HourglassMeshBuilder::new(Transform::from_translation(position))
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(duration)
+        .with_flip_duration(flip_duration)
+        .with_auto_flip(auto_flip)
+        .build(commands, meshes, materials)
+}
+
+/// Create a hourglass with a specific bulb and neck style
+pub fn spawn_styled_mesh_hourglass(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    duration: f32,
+    position: Vec3,
+    bulb_style: BulbStyle,
+    neck_style: NeckStyle,
+) -> Entity {
+    let body_config = HourglassMeshBodyConfig {
+        bulb_style,
+        neck_style,
+        ..Default::default()
+    };
+
+    HourglassMeshBuilder::new(Transform::from_translation(position))
+        .with_body(body_config)
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(duration)
+        .build(commands, meshes, materials)
+}
@@ -0,0 +1,209 @@
+//! Components making up a mesh hourglass.
+
+use super::config::{
+    ContentStyle, HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassMeshSandConfig,
+    RenderStyle, StreamStyle,
+};
+use bevy::prelude::*;
+use hourglass_geometry::{SandBulb, SandOutlineParams, SurfaceWave};
+
+/// Marker component for the mesh hourglass container entity
+///
+/// Requires a `Transform` and `Visibility`, so the parts spawned as its children are positioned
+/// and shown with it.
+#[derive(Component)]
+#[require(Transform, Visibility)]
+pub struct HourglassMesh;
+
+/// Component recording the shape an hourglass was built with, used to rebuild its parts
+#[derive(Component, Debug, Clone)]
+pub struct HourglassMeshShape {
+    pub body_config: HourglassMeshBodyConfig,
+    pub plates_config: Option<HourglassMeshPlatesConfig>,
+    pub render_style: RenderStyle,
+}
+
+/// Component on the root of a mesh hourglass giving direct access to its parts
+///
+/// Parts that were not configured are `None`.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HourglassParts {
+    pub body: Option<Entity>,
+    pub top_plate: Option<Entity>,
+    pub bottom_plate: Option<Entity>,
+    pub top_sand: Option<Entity>,
+    pub bottom_sand: Option<Entity>,
+    /// Falling stream between the bulbs
+    pub stream: Option<Entity>,
+    pub dust_motes: Option<Entity>,
+    /// Sand pile spilling around the bottom plate once empty
+    pub overflow: Option<Entity>,
+    pub label: Option<Entity>,
+    /// Numeric badge in a corner, spawned once a number is shown
+    pub badge: Option<Entity>,
+}
+
+impl HourglassParts {
+    /// Sand of the given bulb
+    pub fn sand(&self, bulb: SandBulb) -> Option<Entity> {
+        match bulb {
+            SandBulb::Top => self.top_sand,
+            SandBulb::Bottom => self.bottom_sand,
+        }
+    }
+
+    /// Plate of the given side
+    pub fn plate(&self, plate: &HourglassMeshPlate) -> Option<Entity> {
+        match plate {
+            HourglassMeshPlate::Top => self.top_plate,
+            HourglassMeshPlate::Bottom => self.bottom_plate,
+        }
+    }
+
+    /// Parts drawn with a mesh: body, plates, sand, stream and overflow pile
+    pub fn meshes(&self) -> impl Iterator<Item = Entity> {
+        [
+            self.body,
+            self.top_plate,
+            self.bottom_plate,
+            self.top_sand,
+            self.bottom_sand,
+            self.stream,
+            self.overflow,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Marker component for the hourglass body
+#[derive(Component)]
+pub struct HourglassMeshBody;
+
+/// Marker component for the hourglass plates
+#[derive(Component)]
+pub enum HourglassMeshPlate {
+    Top,
+    Bottom,
+}
+
+/// Marker component for the hourglass sand
+#[derive(Component)]
+pub enum HourglassMeshSand {
+    TopBulb,
+    BottomBulb,
+}
+
+/// Marker component for parts drawn with a material passed to the builder, e.g. with
+/// [`HourglassMeshBuilder::with_sand_material`](crate::HourglassMeshBuilder::with_sand_material)
+///
+/// The plugin doesn't give these parts a `ColorMaterial` again.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct HourglassCustomMaterial;
+
+/// Component for the falling sand grains of an hourglass using [`StreamStyle::Grains`]
+#[derive(Component, Debug, Default)]
+pub struct HourglassMeshSandStream {
+    /// Distance the grains have scrolled down, wraps around the grain spacing
+    pub phase: f32,
+}
+
+/// Component to track sand state for animations
+///
+/// This is the single source of truth for the sand meshes: they are regenerated by
+/// `update_mesh_hourglass_sand` whenever `needs_update` is set or `sand_config` differs from the
+/// configuration they were built from. External drivers should change it through the `set_*`
+/// methods (or call [`Self::request_update`] after editing other fields directly) rather than
+/// relying on changes to the [`Hourglass`] component.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassMeshSandState {
+    pub fill_percent: f32,
+    pub body_config: HourglassMeshBodyConfig,
+    pub sand_config: HourglassMeshSandConfig,
+    /// Rotation of the hourglass relative to gravity, used to keep sand surfaces level
+    pub tilt: f32,
+    /// Phase of the surface waves of liquid contents in radians
+    pub surface_phase: f32,
+    /// Position of the hourglass' center relative to the entity's origin, set by its anchor
+    pub center_offset: Vec2,
+    /// Completed flips of the hourglass the shown fill accounts for, so smoothed fills turn over
+    /// with the sand instead of easing through the flip
+    pub flips: u32,
+    /// Sand configuration the meshes were last built from, so edits to any sand parameter are
+    /// picked up
+    pub built_sand_config: HourglassMeshSandConfig,
+    /// Flag to track if the sand needs to be regenerated
+    pub needs_update: bool,
+}
+
+impl HourglassMeshSandState {
+    /// Mark the sand meshes for regeneration on the next update
+    pub fn request_update(&mut self) {
+        self.needs_update = true;
+    }
+
+    /// Set how full the top bulb is (0.0 - 1.0), only regenerating the sand if it changed
+    pub fn set_fill_percent(&mut self, fill_percent: f32) {
+        let clamped_fill_percent = fill_percent.clamp(0.0, 1.0);
+        if (self.fill_percent - clamped_fill_percent).abs() > f32::EPSILON {
+            self.fill_percent = clamped_fill_percent;
+            self.sand_config.fill_percent = clamped_fill_percent;
+            self.needs_update = true;
+        }
+    }
+
+    /// Replace the body configuration the sand is shaped by
+    pub fn set_body_config(&mut self, body_config: HourglassMeshBodyConfig) {
+        self.body_config = body_config;
+        self.needs_update = true;
+    }
+
+    /// Replace the sand configuration, keeping the current fill percentage
+    pub fn set_sand_config(&mut self, sand_config: HourglassMeshSandConfig) {
+        self.sand_config = HourglassMeshSandConfig {
+            fill_percent: self.fill_percent,
+            ..sand_config
+        };
+        self.needs_update = true;
+    }
+
+    /// Set the rotation relative to gravity used to keep sand surfaces level
+    pub fn set_tilt(&mut self, tilt: f32) {
+        if self.tilt != tilt {
+            self.tilt = tilt;
+            self.needs_update = true;
+        }
+    }
+
+    /// Parameters for generating the sand outline of the given bulb from this state
+    pub fn sand_outline_params(&self, bulb: SandBulb) -> SandOutlineParams {
+        let half_height = self.body_config.total_height / 2.0;
+        SandOutlineParams {
+            fill_percent: self.sand_config.fill_percent,
+            wall_offset: self.sand_config.wall_offset,
+            bulb,
+            neck_height: self.body_config.neck_style.height(),
+            min_y: -half_height,
+            max_y: half_height,
+            tilt: self.tilt,
+            // With split colors the solid stream is drawn by the stream entity in a blended color
+            solid_stream: matches!(self.sand_config.stream_style, StreamStyle::Solid)
+                && self.sand_config.content_style == ContentStyle::Sand
+                && !self.sand_config.has_split_colors(),
+            surface_wave: match self.sand_config.content_style {
+                ContentStyle::Sand => None,
+                ContentStyle::Liquid {
+                    wave_amplitude,
+                    wavelength,
+                    ..
+                } => Some(SurfaceWave {
+                    amplitude: wave_amplitude,
+                    wavelength,
+                    phase: self.surface_phase,
+                }),
+            },
+            funnel_fill: self.sand_config.funnel_fill,
+            robust_geometry: self.sand_config.robust_geometry,
+        }
+    }
+}
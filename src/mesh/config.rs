@@ -0,0 +1,451 @@
+//! Configuration of the parts of a mesh hourglass.
+
+use crate::components::SandSplashConfig;
+use crate::units::SizeUnits;
+use bevy::prelude::*;
+use hourglass_geometry::{hash_f32, BulbStyle, NeckStyle, SandBulb};
+use std::hash::{Hash, Hasher};
+
+/// Configuration for the hourglass body (the glass part)
+#[derive(Clone, Debug, PartialEq)]
+pub struct HourglassMeshBodyConfig {
+    pub total_height: f32,
+    pub bulb_style: BulbStyle,
+    pub neck_style: NeckStyle,
+    pub color: Color,
+    /// HDR brightness boost of the glass, values above 0.0 glow with an HDR camera and bloom
+    pub emissive: f32,
+    /// Distance between the vertices of the glass, evenly spaced instead of following the curve
+    /// resolution (smoother outline strokes)
+    pub outline_spacing: Option<f32>,
+    /// How texture coordinates are laid out on the glass and sand meshes
+    pub uv_mode: UvMode,
+    /// Units of the stroke width of [`RenderStyle::Minimal`] glasses, local by default so the
+    /// stroke scales with the hourglass
+    pub stroke_units: SizeUnits,
+    /// How the edges of the filled glass and the sand are drawn
+    pub edge_quality: EdgeQuality,
+}
+
+impl Default for HourglassMeshBodyConfig {
+    fn default() -> Self {
+        Self {
+            total_height: 200.0,
+            bulb_style: BulbStyle::default(),
+            neck_style: NeckStyle::default(),
+            color: Color::srgba(0.85, 0.95, 1.0, 0.2), // Light blue glass with transparency
+            emissive: 0.0,
+            outline_spacing: None,
+            uv_mode: UvMode::default(),
+            stroke_units: SizeUnits::Local,
+            edge_quality: EdgeQuality::default(),
+        }
+    }
+}
+
+impl Hash for HourglassMeshBodyConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_f32(self.total_height, state);
+        self.bulb_style.hash(state);
+        self.neck_style.hash(state);
+        hash_color(self.color, state);
+        hash_f32(self.emissive, state);
+        self.outline_spacing.is_some().hash(state);
+        hash_f32(self.outline_spacing.unwrap_or_default(), state);
+        self.uv_mode.hash(state);
+        self.stroke_units.hash(state);
+        self.edge_quality.hash(state);
+    }
+}
+
+/// Configuration for the plates at the top and bottom of the hourglass
+#[derive(Clone, Debug, PartialEq)]
+pub struct HourglassMeshPlatesConfig {
+    pub width: f32,
+    pub height: f32,
+    pub color: Color,
+}
+
+impl Default for HourglassMeshPlatesConfig {
+    fn default() -> Self {
+        Self {
+            width: 165.0,
+            height: 10.0,
+            color: Color::srgb(0.6, 0.4, 0.2), // Wood brown color
+        }
+    }
+}
+
+impl Hash for HourglassMeshPlatesConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_f32(self.width, state);
+        hash_f32(self.height, state);
+        hash_color(self.color, state);
+    }
+}
+
+/// Hash a color consistently with `==`, equal colors having the same linear components
+fn hash_color<H: Hasher>(color: Color, state: &mut H) {
+    for component in color.to_linear().to_f32_array() {
+        hash_f32(component, state);
+    }
+}
+
+/// Configuration for the sand inside the hourglass
+#[derive(Clone, Debug, PartialEq)]
+pub struct HourglassMeshSandConfig {
+    pub color: Color,
+    pub fill_percent: f32, // 0.0 to 1.0, how full the top bulb is
+    pub wall_offset: f32,  // Distance in pixels from glass walls
+    pub stream_style: StreamStyle,
+    /// Top bulb fill below which the sand is shown as fully drained and the timer runs out,
+    /// avoiding a long thin trickle of the last few pixels
+    pub min_flow_fill: f32,
+    /// HDR brightness boost of the sand, values above 0.0 glow with an HDR camera and bloom
+    pub emissive: f32,
+    /// Color of the sand in the top bulb, falling back to `color`
+    pub top_color: Option<Color>,
+    /// Color of the sand in the bottom bulb, falling back to `color`
+    pub bottom_color: Option<Color>,
+    /// What the hourglass is filled with
+    pub content_style: ContentStyle,
+    /// Gradient the sand color follows as the top bulb drains, replacing `color`
+    pub color_by_remaining: Option<SandGradient>,
+    /// Top bulb fill below which the sand sinks into a funnel draining through the neck, 0.0
+    /// keeps its surface flat until it is empty
+    pub funnel_fill: f32,
+    /// Clean the sand outlines of degenerate edges before triangulating them, slower but avoids
+    /// sand briefly disappearing at fill levels where the outline folds onto itself
+    pub robust_geometry: bool,
+    /// Easing of the shown drain, mapping the elapsed share of the timer to the share of sand
+    /// that has fallen, e.g. to drain quickly at first (the timer itself stays linear)
+    pub flow_curve: EaseFunction,
+    /// Rate (per second) at which the shown fill eases towards the timer's fill, smoothing out
+    /// the steps of very short timers; 0.0 follows the timer exactly (the timer itself is not
+    /// affected)
+    pub fill_smoothing: f32,
+}
+
+impl Default for HourglassMeshSandConfig {
+    fn default() -> Self {
+        Self {
+            color: Color::srgb(0.9, 0.8, 0.6), // Sand color
+            fill_percent: 1.0,                 // Start with full top bulb
+            wall_offset: 8.0,                  // 8 pixels from glass walls
+            stream_style: StreamStyle::Solid,
+            min_flow_fill: 0.005, // Stop the stream below 0.5%
+            emissive: 0.0,
+            top_color: None,
+            bottom_color: None,
+            content_style: ContentStyle::Sand,
+            color_by_remaining: None,
+            funnel_fill: 0.1, // Funnel the last 10%
+            robust_geometry: false,
+            flow_curve: EaseFunction::Linear,
+            fill_smoothing: 0.0,
+        }
+    }
+}
+
+impl HourglassMeshSandConfig {
+    /// Color of the sand with the given fraction remaining in the top bulb
+    ///
+    /// Follows the `color_by_remaining` gradient if there is one, `color` otherwise.
+    pub fn color_at(&self, remaining: f32) -> Color {
+        self.color_by_remaining
+            .as_ref()
+            .map_or(self.color, |gradient| gradient.sample(remaining))
+    }
+
+    /// Color of the sand material, including the emissive boost
+    pub fn material_color(&self) -> Color {
+        emissive_color(self.color, self.emissive)
+    }
+
+    /// Color of the sand in the given bulb
+    pub fn bulb_color(&self, bulb: SandBulb) -> Color {
+        match bulb {
+            SandBulb::Top => self.top_color,
+            SandBulb::Bottom => self.bottom_color,
+        }
+        .unwrap_or(self.color)
+    }
+
+    /// Color of the falling stream, halfway between the top and bottom colors
+    pub fn stream_color(&self) -> Color {
+        self.bulb_color(SandBulb::Top)
+            .mix(&self.bulb_color(SandBulb::Bottom), 0.5)
+    }
+
+    /// Whether the top and bottom bulbs use different colors
+    pub fn has_split_colors(&self) -> bool {
+        self.bulb_color(SandBulb::Top) != self.bulb_color(SandBulb::Bottom)
+    }
+
+    /// Color of the sand material of the given bulb, including the emissive boost
+    pub fn bulb_material_color(&self, bulb: SandBulb) -> Color {
+        emissive_color(self.bulb_color(bulb), self.emissive)
+    }
+
+    /// Color of the stream material, including the emissive boost
+    pub fn stream_material_color(&self) -> Color {
+        emissive_color(self.stream_color(), self.emissive)
+    }
+}
+
+impl HourglassMeshBodyConfig {
+    /// Color of the glass material, including the emissive boost
+    pub fn material_color(&self) -> Color {
+        emissive_color(self.color, self.emissive)
+    }
+}
+
+/// Scale a color into HDR range, so it is picked up by bloom when `emissive` is above 0.0
+pub fn emissive_color(color: Color, emissive: f32) -> Color {
+    if emissive <= 0.0 {
+        return color;
+    }
+    let linear = color.to_linear();
+    let boost = 1.0 + emissive;
+    Color::LinearRgba(LinearRgba::new(
+        linear.red * boost,
+        linear.green * boost,
+        linear.blue * boost,
+        linear.alpha,
+    ))
+}
+
+/// How the stream of sand falling through the neck is rendered
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StreamStyle {
+    /// A solid column of sand from the neck to the bottom of the glass
+    #[default]
+    Solid,
+    /// Small grains trickling down from the neck
+    Grains {
+        /// Number of grains per 100 pixels of stream
+        density: f32,
+        /// Falling speed in pixels per second at the hourglass' nominal flow rate
+        speed: f32,
+    },
+}
+
+/// What an hourglass is filled with
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ContentStyle {
+    /// Granular sand, falling as configured by the [`StreamStyle`]
+    #[default]
+    Sand,
+    /// Liquid with a gently waving surface, falling through the neck as droplets
+    ///
+    /// The stream style is ignored. Surfaces stay flat while the hourglass is tilted.
+    Liquid {
+        /// Height of the surface waves in pixels
+        wave_amplitude: f32,
+        /// Distance between wave crests in pixels
+        wavelength: f32,
+        /// Speed of the surface waves in radians per second
+        wave_speed: f32,
+        /// Distance between falling droplets in pixels
+        droplet_spacing: f32,
+        /// Falling speed of the droplets in pixels per second at the nominal flow rate
+        droplet_speed: f32,
+    },
+}
+
+impl ContentStyle {
+    /// Liquid with default wave and droplet settings
+    pub fn liquid() -> Self {
+        Self::Liquid {
+            wave_amplitude: 1.5,
+            wavelength: 24.0,
+            wave_speed: 4.0,
+            droplet_spacing: 18.0,
+            droplet_speed: 160.0,
+        }
+    }
+}
+
+/// Colors along a range from 0.0 to 1.0, blended linearly between stops
+#[derive(Clone, Debug, PartialEq)]
+pub struct SandGradient {
+    /// Positions and colors, sorted by position
+    stops: Vec<(f32, Color)>,
+}
+
+impl SandGradient {
+    /// Creates a gradient from positions and colors, in any order
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let mut stops: Vec<_> = stops.into_iter().collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Red when empty, yellow halfway and green when full, the usual colors for timers
+    pub fn traffic_light() -> Self {
+        Self::new([
+            (0.0, Color::srgb(0.9, 0.2, 0.15)),
+            (0.5, Color::srgb(0.95, 0.8, 0.2)),
+            (1.0, Color::srgb(0.3, 0.8, 0.3)),
+        ])
+    }
+
+    /// Color at the given position, clamped to the first and last stops
+    pub fn sample(&self, position: f32) -> Color {
+        let Some(&(first_position, first_color)) = self.stops.first() else {
+            return Color::NONE;
+        };
+        if position <= first_position {
+            return first_color;
+        }
+        for pair in self.stops.windows(2) {
+            let ((start, start_color), (end, end_color)) = (pair[0], pair[1]);
+            if position <= end {
+                let t = if end > start {
+                    (position - start) / (end - start)
+                } else {
+                    1.0
+                };
+                return start_color.mix(&end_color, t);
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// How texture coordinates are laid out on the glass and sand meshes, for textured or custom
+/// materials (`v` points down the texture, like Bevy images)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum UvMode {
+    /// Stretch the texture over the bounds of each mesh
+    #[default]
+    BoundingBox,
+    /// Project the texture in hourglass space, centered on the hourglass and repeating every
+    /// `size` pixels, so the glass and the sand line up
+    Planar { size: f32 },
+    /// `u` follows the distance along the outline (0.0 to 1.0 around it) and `v` goes across the
+    /// stroke with [`RenderStyle::Minimal`], or down the mesh bounds otherwise
+    ArcLength,
+}
+
+impl Hash for UvMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let UvMode::Planar { size } = *self {
+            hash_f32(size, state);
+        }
+    }
+}
+
+/// How the edges of the glass and sand meshes are drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EdgeQuality {
+    /// Hard polygon edges, which alias when the hourglass is small
+    #[default]
+    Aliased,
+    /// A thin fringe fading out around the edges, keeping them smooth at small sizes
+    ///
+    /// The fringe uses vertex colors, which `ColorMaterial` multiplies with its color. Outline
+    /// strokes of [`RenderStyle::Minimal`] are not feathered.
+    Feathered {
+        /// Width of the fringe in local pixels, about one screen pixel looks best
+        width: f32,
+    },
+}
+
+impl Hash for EdgeQuality {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let EdgeQuality::Feathered { width } = *self {
+            hash_f32(width, state);
+        }
+    }
+}
+
+/// How the glass and plates of an hourglass are drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RenderStyle {
+    /// Filled translucent glass with plates at the top and bottom
+    #[default]
+    Filled,
+    /// Icon-like silhouette: only the sand and a stroke along the glass outline, without plates
+    ///
+    /// The stroke uses the body color at full opacity.
+    Minimal {
+        /// Width of the glass outline stroke
+        stroke_width: f32,
+    },
+}
+
+impl Hash for RenderStyle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let RenderStyle::Minimal { stroke_width } = *self {
+            hash_f32(stroke_width, state);
+        }
+    }
+}
+
+/// Whether the sand is drawn in front of or behind the glass
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SandOcclusion {
+    /// Sand is drawn in front of the glass, keeping its colors untouched
+    #[default]
+    InFront,
+    /// Sand is drawn behind the translucent glass, which tints it as if it were inside
+    ///
+    /// The sand is moved `sand_z` behind the glass, and always at least a little, so it doesn't
+    /// z-fight with the glass when `sand_z` is zero.
+    BehindGlass,
+}
+
+/// Adjustments to where the parts of an hourglass are drawn, relative to the body
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartOffsets {
+    /// Vertical offset of the plates, positive values move them away from the glass and negative
+    /// values make them overlap it (in pixels)
+    pub plate_offset: f32,
+    /// Z position of the sand and stream, negative values draw them behind the glass
+    pub sand_z: f32,
+    /// Scale of the glass, e.g. slightly larger than the sand for a thick frosted look
+    pub body_scale: Vec2,
+}
+
+impl Default for PartOffsets {
+    fn default() -> Self {
+        Self {
+            plate_offset: 0.0,
+            sand_z: 0.1, // Slightly in front of body
+            body_scale: Vec2::ONE,
+        }
+    }
+}
+
+/// Complete look of a mesh hourglass, applied in one call with [`HourglassMeshBuilder::styled`]
+///
+/// Defaults to the default glass between wooden plates, filled with sand that doesn't splash.
+/// The builder's `with_*` methods called afterwards override single parts.
+///
+/// [`HourglassMeshBuilder::styled`]: crate::HourglassMeshBuilder::styled
+#[derive(Clone, Debug, PartialEq)]
+pub struct HourglassVisualConfig {
+    pub body: HourglassMeshBodyConfig,
+    /// Plates framing the glass, `None` for bare glass
+    pub plates: Option<HourglassMeshPlatesConfig>,
+    pub sand: HourglassMeshSandConfig,
+    /// Splash of the sand landing in the bottom bulb
+    pub sand_splash: Option<SandSplashConfig>,
+    pub render_style: RenderStyle,
+}
+
+impl Default for HourglassVisualConfig {
+    fn default() -> Self {
+        Self {
+            body: HourglassMeshBodyConfig::default(),
+            plates: Some(HourglassMeshPlatesConfig::default()),
+            sand: HourglassMeshSandConfig::default(),
+            sand_splash: None,
+            render_style: RenderStyle::default(),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+//! Mesh generation for the parts of a mesh hourglass.
+
+use super::builder::HourglassMeshBuilder;
+use super::config::{
+    EdgeQuality, HourglassMeshBodyConfig, HourglassMeshPlatesConfig, RenderStyle, UvMode,
+};
+use crate::warnings::{report_warning, HourglassWarning};
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, VertexAttributeValues},
+        render_resource::PrimitiveTopology,
+    },
+};
+use earcutr::earcut;
+use hourglass_geometry::{outline_normals, HourglassShapeBuilder};
+
+impl HourglassMeshBuilder {
+    /// Create the mesh of the glass, either filled or as an outline stroke
+    pub fn create_body_mesh(config: &HourglassMeshBodyConfig, render_style: RenderStyle) -> Mesh {
+        let _span = info_span!("hourglass_body_outline").entered();
+
+        // Create the hourglass shape builder from the config
+        let shape_builder = HourglassShapeBuilder {
+            total_height: config.total_height,
+            bulb_style: config.bulb_style.clone(),
+            neck_style: config.neck_style.clone(),
+        };
+
+        // Generate the hourglass outline using the composable curve system
+        let points: Vec<[f32; 2]> = match config.outline_spacing {
+            Some(spacing) => shape_builder.generate_outline_with_spacing(spacing),
+            None => shape_builder.generate_outline(),
+        };
+
+        // Create mesh from the generated points, either filled or as an outline stroke
+        match render_style {
+            RenderStyle::Filled => Self::create_mesh_from_points_with_edges(
+                points,
+                config.uv_mode,
+                config.edge_quality,
+            )
+            .expect("Failed to create hourglass body mesh"),
+            RenderStyle::Minimal { stroke_width } => {
+                Self::create_stroke_mesh_with_uv(&points, stroke_width, config.uv_mode)
+            }
+        }
+    }
+
+    /// Create the mesh of a plate, a rectangle centered at the origin
+    pub fn create_plate_mesh(config: &HourglassMeshPlatesConfig) -> Mesh {
+        let mut plate_mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default());
+
+        // Rectangle vertices (centered at origin)
+        let half_width = config.width / 2.0;
+        let half_height = config.height / 2.0;
+        let points_3d = vec![
+            [-half_width, -half_height, 0.0], // bottom left
+            [half_width, -half_height, 0.0],  // bottom right
+            [half_width, half_height, 0.0],   // top right
+            [-half_width, half_height, 0.0],  // top left
+        ];
+
+        // Indices for two triangles making up the rectangle
+        let indices = vec![0, 1, 2, 0, 2, 3];
+
+        plate_mesh.insert_indices(Indices::U32(indices));
+        plate_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, points_3d);
+        plate_mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 4]);
+        plate_mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 4]);
+        plate_mesh
+    }
+
+    /// Create a mesh made of axis-aligned squares centered on the given points
+    pub fn create_quads_mesh(centers: &[[f32; 2]], size: f32) -> Mesh {
+        let half = size / 2.0;
+        let positions: Vec<[f32; 3]> = centers
+            .iter()
+            .flat_map(|c| {
+                [
+                    [c[0] - half, c[1] - half, 0.0],
+                    [c[0] + half, c[1] - half, 0.0],
+                    [c[0] + half, c[1] + half, 0.0],
+                    [c[0] - half, c[1] + half, 0.0],
+                ]
+            })
+            .collect();
+        let indices: Vec<u32> = (0..centers.len() as u32)
+            .flat_map(|i| {
+                let base = i * 4;
+                [base, base + 1, base + 2, base, base + 2, base + 3]
+            })
+            .collect();
+        let num_vertices = positions.len();
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default());
+        mesh.insert_indices(Indices::U32(indices));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; num_vertices]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; num_vertices]);
+        mesh
+    }
+
+    /// Create the mesh of a sand pile, a mound 1.0 wide and 1.0 high standing on the origin
+    pub fn create_sand_pile_mesh() -> Mesh {
+        const STEPS: usize = 16;
+        let mut points = vec![[-0.5, 0.0], [0.5, 0.0]];
+        points.extend((1..STEPS).map(|step| {
+            let x = 0.5 - step as f32 / STEPS as f32;
+            // Rounded top with slopes flattening out towards the edges
+            [x, (std::f32::consts::PI * x).cos().powf(1.5)]
+        }));
+        Self::create_mesh_from_points(points).expect("sand pile is a valid polygon")
+    }
+
+    /// Compute the overall width and height of the hourglass body and plates
+    pub fn visual_size(
+        body_config: &HourglassMeshBodyConfig,
+        plates_config: Option<&HourglassMeshPlatesConfig>,
+    ) -> Vec2 {
+        let bulb_height = (body_config.total_height - body_config.neck_style.height()) / 2.0;
+        let body_size = Vec2::new(
+            2.0 * bulb_height * body_config.bulb_style.width_factor(),
+            body_config.total_height,
+        );
+
+        match plates_config {
+            Some(plates) => Vec2::new(
+                body_size.x.max(plates.width),
+                body_size.y + 2.0 * plates.height,
+            ),
+            None => body_size,
+        }
+    }
+
+    /// Create a mesh stroking the closed outline through the given points
+    pub fn create_stroke_mesh(points: &[[f32; 2]], width: f32) -> Mesh {
+        Self::create_stroke_mesh_with_uv(points, width, UvMode::default())
+    }
+
+    /// Create a mesh stroking the closed outline through the given points, with the given UVs
+    pub fn create_stroke_mesh_with_uv(points: &[[f32; 2]], width: f32, uv_mode: UvMode) -> Mesh {
+        // Skip repeated points, they have no direction to offset along
+        let mut outline: Vec<Vec2> = Vec::with_capacity(points.len());
+        for point in points.iter().map(|p| Vec2::new(p[0], p[1])) {
+            if outline
+                .last()
+                .is_none_or(|last| last.distance(point) > 1e-4)
+            {
+                outline.push(point);
+            }
+        }
+        if outline.len() > 1 && outline[0].distance(outline[outline.len() - 1]) <= 1e-4 {
+            outline.pop();
+        }
+
+        let count = outline.len();
+        let half_width = width / 2.0;
+        let mut positions = Vec::with_capacity(count * 2);
+        for i in 0..count {
+            let prev = outline[(i + count - 1) % count];
+            let current = outline[i];
+            let next = outline[(i + 1) % count];
+
+            // Miter along the average of the adjacent segment normals, limited at sharp corners
+            let normal_in = (current - prev).normalize_or_zero().perp();
+            let normal_out = (next - current).normalize_or_zero().perp();
+            let miter = (normal_in + normal_out).normalize_or(normal_out);
+            let miter_length = half_width / miter.dot(normal_out).max(0.25);
+
+            let inner = current - miter * miter_length;
+            let outer = current + miter * miter_length;
+            positions.push([inner.x, inner.y, 0.0]);
+            positions.push([outer.x, outer.y, 0.0]);
+        }
+
+        let uvs = match uv_mode {
+            UvMode::ArcLength => {
+                let outline: Vec<[f32; 2]> = outline.iter().map(|point| point.to_array()).collect();
+                arc_length_fractions(&outline)
+                    .into_iter()
+                    .flat_map(|u| [[u, 0.0], [u, 1.0]])
+                    .collect()
+            }
+            _ => {
+                let flat: Vec<[f32; 2]> = positions.iter().map(|p| [p[0], p[1]]).collect();
+                mesh_uvs(&flat, uv_mode)
+            }
+        };
+
+        let indices: Vec<u32> = (0..count as u32)
+            .flat_map(|i| {
+                let next = (i + 1) % count as u32;
+                let (a, b, c, d) = (i * 2, i * 2 + 1, next * 2, next * 2 + 1);
+                [a, b, d, a, d, c]
+            })
+            .collect();
+        let num_vertices = positions.len();
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default());
+        mesh.insert_indices(Indices::U32(indices));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; num_vertices]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+
+    /// Create a mesh from a set of 2D points
+    pub fn create_mesh_from_points(points: Vec<[f32; 2]>) -> Option<Mesh> {
+        Self::create_mesh_from_points_with_uv(points, UvMode::default())
+    }
+
+    /// Create a mesh from a set of 2D points, with the given UVs
+    ///
+    /// Normals point out of the silhouette in the plane of the mesh, for materials giving it a
+    /// rounded look such as [`HourglassLitMaterial`](crate::HourglassLitMaterial).
+    pub fn create_mesh_from_points_with_uv(points: Vec<[f32; 2]>, uv_mode: UvMode) -> Option<Mesh> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let points_3d = points.iter().map(|p| [p[0], p[1], 0.0]).collect::<Vec<_>>();
+
+        let coords: Vec<f32> = points.iter().flat_map(|p| vec![p[0], p[1]]).collect();
+        let hole_indices: Vec<usize> = Vec::new();
+
+        match earcut(&coords, &hole_indices, 2) {
+            Ok(triangles) => {
+                let indices: Vec<u32> = triangles.into_iter().map(|i| i as u32).collect();
+
+                let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default());
+                mesh.insert_indices(Indices::U32(indices));
+                mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, points_3d);
+                let normals: Vec<[f32; 3]> = outline_normals(&points)
+                    .into_iter()
+                    .map(|normal| [normal[0], normal[1], 0.0])
+                    .collect();
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, mesh_uvs(&points, uv_mode));
+
+                Some(mesh)
+            }
+            Err(_) => {
+                report_warning(HourglassWarning::TriangulationFailed {
+                    points: points.len(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Create a mesh from a set of 2D points, with the given UVs and edges
+    ///
+    /// With [`EdgeQuality::Feathered`] a fringe of vertices is added around the outline, fading
+    /// from opaque to transparent vertex colors.
+    pub fn create_mesh_from_points_with_edges(
+        points: Vec<[f32; 2]>,
+        uv_mode: UvMode,
+        edge_quality: EdgeQuality,
+    ) -> Option<Mesh> {
+        let EdgeQuality::Feathered { width } = edge_quality else {
+            return Self::create_mesh_from_points_with_uv(points, uv_mode);
+        };
+        let mut mesh = Self::create_mesh_from_points_with_uv(points.clone(), uv_mode)?;
+        add_edge_fringe(&mut mesh, &points, width.max(0.0));
+        Some(mesh)
+    }
+}
+
+/// Extend a mesh filling the outline through `points` with a fringe `width` wide around it
+///
+/// The outline's vertices are opaque and the fringe's outer vertices, offset along the outline
+/// normals, are transparent. They share the UVs and normals of the outline.
+fn add_edge_fringe(mesh: &mut Mesh, points: &[[f32; 2]], width: f32) {
+    let (
+        Some(VertexAttributeValues::Float32x3(positions)),
+        Some(VertexAttributeValues::Float32x3(normals)),
+        Some(VertexAttributeValues::Float32x2(uvs)),
+        Some(Indices::U32(indices)),
+    ) = (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION).cloned(),
+        mesh.attribute(Mesh::ATTRIBUTE_NORMAL).cloned(),
+        mesh.attribute(Mesh::ATTRIBUTE_UV_0).cloned(),
+        mesh.indices().cloned(),
+    )
+    else {
+        return;
+    };
+    let count = points.len();
+    let (mut positions, mut normals, mut uvs, mut indices) = (positions, normals, uvs, indices);
+
+    for i in 0..count {
+        let normal = normals[i];
+        positions.push([
+            points[i][0] + normal[0] * width,
+            points[i][1] + normal[1] * width,
+            0.0,
+        ]);
+        normals.push(normal);
+        uvs.push(uvs[i]);
+    }
+    let mut colors = vec![[1.0; 4]; count];
+    colors.extend(vec![[1.0, 1.0, 1.0, 0.0]; count]);
+
+    // A quad between each edge of the outline and its offset copy
+    let outer = count as u32;
+    indices.extend((0..count as u32).flat_map(|i| {
+        let next = (i + 1) % count as u32;
+        [i, outer + i, outer + next, i, outer + next, next]
+    }));
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+}
+
+/// Texture coordinates of the vertices of a flat mesh
+fn mesh_uvs(points: &[[f32; 2]], uv_mode: UvMode) -> Vec<[f32; 2]> {
+    let min = points
+        .iter()
+        .fold([f32::MAX; 2], |min, p| [min[0].min(p[0]), min[1].min(p[1])]);
+    let max = points
+        .iter()
+        .fold([f32::MIN; 2], |max, p| [max[0].max(p[0]), max[1].max(p[1])]);
+    let size = [
+        (max[0] - min[0]).max(f32::EPSILON),
+        (max[1] - min[1]).max(f32::EPSILON),
+    ];
+    let down = |y: f32| (max[1] - y) / size[1];
+
+    match uv_mode {
+        UvMode::BoundingBox => points
+            .iter()
+            .map(|p| [(p[0] - min[0]) / size[0], down(p[1])])
+            .collect(),
+        UvMode::Planar { size } => {
+            let size = size.max(f32::EPSILON);
+            points
+                .iter()
+                .map(|p| [p[0] / size + 0.5, 0.5 - p[1] / size])
+                .collect()
+        }
+        UvMode::ArcLength => arc_length_fractions(points)
+            .into_iter()
+            .zip(points)
+            .map(|(u, p)| [u, down(p[1])])
+            .collect(),
+    }
+}
+
+/// Distance of each point along the closed outline, as a fraction of its length
+fn arc_length_fractions(points: &[[f32; 2]]) -> Vec<f32> {
+    let mut distance = 0.0;
+    let mut distances = Vec::with_capacity(points.len());
+    for (i, point) in points.iter().enumerate() {
+        if i > 0 {
+            let previous = points[i - 1];
+            distance += (point[0] - previous[0]).hypot(point[1] - previous[1]);
+        }
+        distances.push(distance);
+    }
+    let closing = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (first[0] - last[0]).hypot(first[1] - last[1]),
+        _ => 0.0,
+    };
+    let total = (distance + closing).max(f32::EPSILON);
+    distances.into_iter().map(|d| d / total).collect()
+}
@@ -0,0 +1,27 @@
+//! Mesh-based hourglass implementation with composable parts.
+
+mod builder;
+mod components;
+mod config;
+mod geometry;
+mod systems;
+
+pub use builder::{
+    spawn_mesh_hourglass_with_flip, spawn_mesh_hourglass_with_timer, spawn_styled_mesh_hourglass,
+    HourglassAssetCache, HourglassMeshBuilder,
+};
+pub use components::{
+    HourglassCustomMaterial, HourglassMesh, HourglassMeshBody, HourglassMeshPlate,
+    HourglassMeshSand, HourglassMeshSandState, HourglassMeshSandStream, HourglassMeshShape,
+    HourglassParts,
+};
+pub use config::{
+    emissive_color, ContentStyle, EdgeQuality, HourglassMeshBodyConfig, HourglassMeshPlatesConfig,
+    HourglassMeshSandConfig, HourglassVisualConfig, PartOffsets, RenderStyle, SandGradient,
+    SandOcclusion, StreamStyle, UvMode,
+};
+pub(crate) use systems::sand_update_due;
+pub use systems::{
+    drain_mesh_hourglass_residue, sync_mesh_hourglass_with_timer, update_mesh_hourglass_sand,
+    update_sand_fill_percent, update_sand_stream_grains,
+};
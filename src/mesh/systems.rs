@@ -0,0 +1,548 @@
+//! Systems animating the sand of mesh hourglasses.
+
+use super::builder::HourglassMeshBuilder;
+use super::components::{
+    HourglassCustomMaterial, HourglassMesh, HourglassMeshSand, HourglassMeshSandState,
+    HourglassMeshSandStream, HourglassParts,
+};
+use super::config::{ContentStyle, StreamStyle};
+use crate::components::{Hourglass, HourglassValueSource};
+use crate::culling::HourglassCulled;
+use crate::quality::QualityTier;
+use crate::resources::{
+    HourglassAccessibility, HourglassConfig, HourglassDiagnostics, HourglassGlobalState,
+    HourglassPowerSaving,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, ParallelSlice, TaskPool};
+use hourglass_geometry::{sand_fill_line, HourglassShapeBuilder, SandBulb};
+
+/// Type alias for the complex sand entities query to reduce type complexity
+type SandEntitiesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static HourglassMeshSand,
+        Option<&'static mut Mesh2d>,
+        Option<&'static MeshMaterial2d<ColorMaterial>>,
+        Has<HourglassCustomMaterial>,
+    ),
+>;
+
+/// Type alias for the complex mesh hourglass query to reduce type complexity
+type MeshHourglassQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Hourglass,
+        &'static mut HourglassMeshSandState,
+        Option<&'static QualityTier>,
+    ),
+    (With<HourglassMesh>, Changed<Hourglass>),
+>;
+
+/// Type alias for the sand state of visible hourglasses to reduce type complexity
+type VisibleSandStateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut HourglassMeshSandState,
+        Option<&'static Children>,
+        Option<Ref<'static, QualityTier>>,
+    ),
+    (With<HourglassMesh>, Without<HourglassCulled>),
+>;
+
+/// Phase change of liquid surface waves before the sand is rebuilt, so waves animate in steps
+/// instead of regenerating the outlines every frame
+const WAVE_PHASE_STEP: f32 = std::f32::consts::TAU / 32.0;
+
+/// Update sand fill percentage
+pub fn update_sand_fill_percent(sand_state: &mut HourglassMeshSandState, new_fill_percent: f32) {
+    sand_state.set_fill_percent(new_fill_percent);
+}
+
+/// Run condition batching sand mesh updates every update interval of [`HourglassPowerSaving`]
+pub(crate) fn sand_update_due(
+    time: Res<Time>,
+    power_saving: Res<HourglassPowerSaving>,
+    mut since_update: Local<f32>,
+) -> bool {
+    if !power_saving.enabled {
+        return true;
+    }
+    *since_update += time.delta_secs();
+    if *since_update < power_saving.sand_update_interval {
+        return false;
+    }
+    *since_update = 0.0;
+    true
+}
+
+/// Settings deciding which sand meshes are rebuilt this frame and how
+#[derive(SystemParam)]
+pub struct SandRebuildSchedule<'w, 's> {
+    config: Res<'w, HourglassConfig>,
+    power_saving: Res<'w, HourglassPowerSaving>,
+    diagnostics: ResMut<'w, HourglassDiagnostics>,
+    /// Last hourglass rebuilt while the budget was limiting rebuilds
+    last_rebuilt: Local<'s, Option<Entity>>,
+}
+
+impl SandRebuildSchedule<'_, '_> {
+    /// Hourglasses to rebuild this frame out of the pending ones, continuing after the last
+    /// hourglass rebuilt so they take turns while the budget is exceeded
+    fn schedule(&mut self, mut pending: Vec<Entity>) -> Vec<Entity> {
+        let budget = self
+            .config
+            .max_sand_rebuilds_per_frame
+            .map_or(usize::MAX, |budget| budget.max(1));
+        self.diagnostics.deferred_sand_rebuilds = pending.len().saturating_sub(budget);
+        if pending.len() <= budget {
+            return pending;
+        }
+
+        pending.sort_unstable();
+        let start = self
+            .last_rebuilt
+            .map_or(0, |last| pending.partition_point(|&entity| entity <= last))
+            % pending.len();
+        pending.rotate_left(start);
+        pending.truncate(budget);
+        *self.last_rebuilt = pending.last().copied();
+        pending
+    }
+}
+
+/// Sand meshes of an hourglass generated off the main thread, indexed by [`SandBulb`]
+type GeneratedSandMeshes = (Entity, [Option<Mesh>; 2]);
+
+/// Generate the sand meshes of both bulbs from the sand state alone
+///
+/// Curves follow the quality tier, and are capped to `max_curve_resolution` while saving power.
+pub(crate) fn generate_sand_meshes(
+    sand_state: &HourglassMeshSandState,
+    quality: QualityTier,
+    max_curve_resolution: Option<usize>,
+) -> [Option<Mesh>; 2] {
+    let body_config = &sand_state.body_config;
+    let mut bulb_style = quality.bulb_style(&body_config.bulb_style);
+    let mut neck_style = quality.neck_style(&body_config.neck_style);
+    if let Some(max_curve_resolution) = max_curve_resolution {
+        bulb_style = bulb_style.capped_resolution(max_curve_resolution);
+        neck_style = neck_style.capped_resolution(max_curve_resolution);
+    }
+    let shape_builder = HourglassShapeBuilder {
+        total_height: body_config.total_height,
+        bulb_style,
+        neck_style,
+    };
+
+    let hourglass_outline =
+        shape_builder.generate_outline_with_wall_offset(sand_state.sand_config.wall_offset);
+
+    [SandBulb::Top, SandBulb::Bottom].map(|bulb| {
+        let points = shape_builder
+            .generate_sand_outline(&hourglass_outline, &sand_state.sand_outline_params(bulb));
+        HourglassMeshBuilder::create_mesh_from_points_with_edges(
+            points,
+            body_config.uv_mode,
+            body_config.edge_quality,
+        )
+    })
+}
+
+/// System to update sand meshes when fill percentage changes using the new curve system
+///
+/// At most [`HourglassConfig::max_sand_rebuilds_per_frame`] meshes are rebuilt in a frame. The
+/// meshes are generated in parallel on the compute task pool, then swapped into the assets.
+pub fn update_mesh_hourglass_sand(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut schedule: SandRebuildSchedule,
+    mut sand_query: VisibleSandStateQuery,
+    mut sand_entities_query: SandEntitiesQuery,
+    stream_materials: Query<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshSandStream>>,
+) {
+    // Changing the quality or any sand parameter rebuilds the sand right away
+    let config_changed = schedule.config.is_changed();
+    for (_, mut sand_state, _, quality) in sand_query.iter_mut() {
+        if config_changed
+            || quality.is_some_and(|quality| quality.is_changed())
+            || sand_state.sand_config != sand_state.built_sand_config
+        {
+            sand_state.request_update();
+        }
+    }
+
+    let pending = sand_query
+        .iter()
+        .filter(|(_, sand_state, _, _)| sand_state.needs_update)
+        .map(|(entity, _, _, _)| entity)
+        .collect();
+    let scheduled = schedule.schedule(pending);
+    if scheduled.is_empty() {
+        return;
+    }
+    let _span = info_span!(
+        "hourglass_sand_rebuild",
+        hourglasses = scheduled.len(),
+        deferred = schedule.diagnostics.deferred_sand_rebuilds
+    )
+    .entered();
+    let power_saving = &schedule.power_saving;
+    let max_curve_resolution = power_saving
+        .enabled
+        .then_some(power_saving.max_curve_resolution);
+    let global_quality = schedule.config.quality;
+
+    // Generate the meshes in parallel, the sand states are only read
+    let generated: Vec<GeneratedSandMeshes> = {
+        let sand_query = &sand_query;
+        scheduled
+            .par_splat_map(
+                ComputeTaskPool::get_or_init(TaskPool::default),
+                None,
+                |_, chunk| {
+                    let _span =
+                        info_span!("hourglass_sand_generate", hourglasses = chunk.len()).entered();
+                    chunk
+                        .iter()
+                        .filter_map(|&entity| {
+                            let (_, sand_state, _, quality) = sand_query.get(entity).ok()?;
+                            let quality = quality.map_or(global_quality, |quality| *quality);
+                            Some((
+                                entity,
+                                generate_sand_meshes(sand_state, quality, max_curve_resolution),
+                            ))
+                        })
+                        .collect::<Vec<_>>()
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect()
+    };
+
+    // Apply them to the sand entities
+    for (entity, mut bulb_meshes) in generated {
+        let Ok((_, mut sand_state, children, _)) = sand_query.get_mut(entity) else {
+            continue;
+        };
+
+        sand_state.needs_update = false;
+        sand_state.built_sand_config = sand_state.sand_config.clone();
+
+        // Find sand child entities
+        let Some(children) = children else {
+            continue;
+        };
+
+        // The stream is blended between the bulb colors
+        let stream_color = sand_state.sand_config.stream_material_color();
+        for handle in stream_materials.iter_many(children) {
+            if let Some(material) = materials.get_mut(&handle.0) {
+                if material.color != stream_color {
+                    material.color = stream_color;
+                }
+            }
+        }
+        for child in children.iter() {
+            let Ok((entity, sand_type, mesh_handle_opt, material_opt, custom_material)) =
+                sand_entities_query.get_mut(child)
+            else {
+                continue;
+            };
+
+            let bulb = match sand_type {
+                HourglassMeshSand::TopBulb => SandBulb::Top,
+                HourglassMeshSand::BottomBulb => SandBulb::Bottom,
+            };
+            let sand_color = sand_state.sand_config.bulb_material_color(bulb);
+
+            // Update material color in place if it exists
+            if let Some(material) = material_opt.and_then(|handle| materials.get_mut(&handle.0)) {
+                if material.color != sand_color {
+                    material.color = sand_color;
+                }
+            }
+
+            match (bulb_meshes[bulb as usize].take(), mesh_handle_opt) {
+                (Some(new_mesh), Some(mut existing_mesh)) => {
+                    // Overwrite the existing mesh asset instead of allocating a new one
+                    if let Some(mesh) = meshes.get_mut(&existing_mesh.0) {
+                        *mesh = new_mesh;
+                    } else {
+                        existing_mesh.0 = meshes.add(new_mesh);
+                    }
+                }
+                (Some(new_mesh), None) => {
+                    // Add mesh component back if it was removed
+                    let mut entity_commands = commands.entity(entity);
+                    entity_commands.insert(Mesh2d(meshes.add(new_mesh)));
+                    if material_opt.is_none() && !custom_material {
+                        entity_commands.insert(MeshMaterial2d(materials.add(sand_color)));
+                    }
+                }
+                (None, Some(existing_mesh)) => {
+                    // Empty mesh - drop the stale asset along with the component
+                    meshes.remove(&existing_mesh.0);
+                    commands.entity(entity).remove::<Mesh2d>();
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+/// System that animates the falling grains of hourglasses using [`StreamStyle::Grains`]
+pub fn update_sand_stream_grains(
+    time: Res<Time>,
+    global: Res<HourglassGlobalState>,
+    accessibility: Res<HourglassAccessibility>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    hourglass_query: Query<
+        (&Hourglass, &HourglassMeshSandState, &HourglassParts),
+        Without<HourglassCulled>,
+    >,
+    mut stream_query: Query<(&mut HourglassMeshSandStream, &Mesh2d)>,
+) {
+    for (hourglass, sand_state, parts) in hourglass_query.iter() {
+        let Some(Ok((mut stream, mesh_handle))) =
+            parts.stream.map(|child| stream_query.get_mut(child))
+        else {
+            continue;
+        };
+
+        let mut grains = Vec::new();
+        let mut grain_size = 0.0;
+        let is_flowing = (hourglass.running || hourglass.rewinding)
+            && sand_state.fill_percent > 0.0
+            && !hourglass.flipping
+            && !accessibility.reduced_motion;
+
+        // A solid stream only needs its own mesh when it is colored apart from the top bulb
+        let stream_style = sand_state.sand_config.stream_style;
+        let content_style = sand_state.sand_config.content_style;
+        let is_split_solid = content_style == ContentStyle::Sand
+            && matches!(stream_style, StreamStyle::Solid)
+            && sand_state.sand_config.has_split_colors()
+            && sand_state.fill_percent > 0.0;
+        let has_moving_stream = is_flowing
+            && (matches!(content_style, ContentStyle::Liquid { .. })
+                || matches!(stream_style, StreamStyle::Grains { .. }));
+
+        if is_split_solid || has_moving_stream {
+            let body_config = &sand_state.body_config;
+            let wall_offset = sand_state.sand_config.wall_offset;
+            let half_height = body_config.total_height / 2.0;
+
+            // Grains fall along gravity from the neck to the sand in the bottom bulb
+            let down = Vec2::new(-sand_state.tilt.sin(), -sand_state.tilt.cos());
+            let bottom_fill_line = sand_fill_line(
+                sand_state.fill_percent,
+                SandBulb::Bottom,
+                body_config.neck_style.height(),
+                -half_height,
+                half_height,
+            );
+            let length = if down.y < 0.0 {
+                -bottom_fill_line / (-down.y).max(0.3)
+            } else {
+                0.0
+            };
+
+            let neck_half_width =
+                (body_config.neck_style.width_with_wall_offset(wall_offset) / 2.0 - wall_offset)
+                    .max(0.5);
+
+            // Spacing, speed and sideways jitter of moving grains or droplets
+            let moving = match (content_style, stream_style) {
+                (
+                    ContentStyle::Liquid {
+                        droplet_spacing,
+                        droplet_speed,
+                        ..
+                    },
+                    _,
+                ) => {
+                    grain_size = (neck_half_width * 1.6).clamp(1.5, 4.0);
+                    Some((droplet_spacing.max(1.0), droplet_speed, 0.0))
+                }
+                (ContentStyle::Sand, StreamStyle::Grains { density, speed }) => {
+                    grain_size = (neck_half_width * 1.2).clamp(1.0, 3.0);
+                    Some((100.0 / density.max(0.01), speed, neck_half_width))
+                }
+                (ContentStyle::Sand, StreamStyle::Solid) => {
+                    // Overlapping squares as wide as the neck form a solid column
+                    grain_size = neck_half_width * 2.0;
+                    let spacing = grain_size / 2.0;
+                    let count = (length / spacing).ceil() as usize;
+                    grains.extend((0..=count).map(|i| {
+                        let center = down * (i as f32 * spacing).min(length);
+                        [center.x, center.y]
+                    }));
+                    None
+                }
+            };
+
+            if let Some((spacing, speed, jitter_width)) = moving {
+                // Speed up or slow down with the flow relative to the nominal rate, rising
+                // while the hourglass rewinds
+                let nominal_flow_rate = 1.0 / hourglass.total_time.max(f32::EPSILON);
+                let flow_factor = if hourglass.rewinding {
+                    -hourglass.rewind_flow_rate() / nominal_flow_rate
+                } else {
+                    hourglass.flow_rate / nominal_flow_rate
+                };
+                stream.phase = (stream.phase
+                    + speed
+                        * flow_factor
+                        * hourglass.speed
+                        * global.time_scale()
+                        * time.delta_secs())
+                .rem_euclid(spacing);
+
+                let across = Vec2::new(-down.y, down.x);
+                let count = (length / spacing).ceil() as usize;
+                for i in 0..count {
+                    let distance = i as f32 * spacing + stream.phase;
+                    if distance > length {
+                        break;
+                    }
+                    // Deterministic sideways jitter so grains don't line up perfectly
+                    let jitter = ((i * 7919) % 13) as f32 / 12.0 - 0.5;
+                    let center = down * distance + across * jitter * jitter_width;
+                    grains.push([center.x, center.y]);
+                }
+            }
+        }
+
+        // Leave idle streams alone rather than replacing an empty mesh every frame
+        if grains.is_empty()
+            && meshes
+                .get(&mesh_handle.0)
+                .is_some_and(|mesh| mesh.count_vertices() == 0)
+        {
+            continue;
+        }
+        if let Some(mesh) = meshes.get_mut(&mesh_handle.0) {
+            *mesh = HourglassMeshBuilder::create_quads_mesh(&grains, grain_size);
+        }
+    }
+}
+
+/// System running out the timers of mesh hourglasses once their sand is down to the
+/// `min_flow_fill` residue, so the timer finishes when the sand is shown as drained
+pub fn drain_mesh_hourglass_residue(
+    mut query: Query<(&mut Hourglass, &HourglassMeshSandState), With<HourglassMesh>>,
+) {
+    for (mut hourglass, sand_state) in query.iter_mut() {
+        if !hourglass.running
+            || hourglass.flipping
+            || hourglass.rewinding
+            || hourglass.value_source != HourglassValueSource::Timer
+            || hourglass.upper_chamber <= 0.0
+        {
+            continue;
+        }
+        let fill = 1.0
+            - sand_state
+                .sand_config
+                .flow_curve
+                .sample_clamped(1.0 - hourglass.upper_chamber);
+        if fill < sand_state.sand_config.min_flow_fill {
+            // Reported as empty by the update that follows
+            hourglass.set_fill(0.0);
+        }
+    }
+}
+
+/// System to sync Hourglass component state with HourglassMeshSandState
+pub fn sync_mesh_hourglass_with_timer(
+    time: Res<Time>,
+    accessibility: Res<HourglassAccessibility>,
+    config: Res<HourglassConfig>,
+    mut mesh_query: MeshHourglassQuery,
+) {
+    for (hourglass, mut sand_state, quality) in mesh_query.iter_mut() {
+        // Keep liquid surfaces moving
+        if let ContentStyle::Liquid {
+            wave_amplitude,
+            wave_speed,
+            ..
+        } = sand_state.sand_config.content_style
+        {
+            if wave_amplitude > 0.0 && !accessibility.reduced_motion {
+                let step = (sand_state.surface_phase / WAVE_PHASE_STEP) as u32;
+                sand_state.surface_phase = (sand_state.surface_phase
+                    + wave_speed * time.delta_secs())
+                .rem_euclid(std::f32::consts::TAU);
+                if (sand_state.surface_phase / WAVE_PHASE_STEP) as u32 != step {
+                    sand_state.request_update();
+                }
+            }
+        }
+
+        // Show the top bulb fill easing after time adjustments, along the flow curve and hiding
+        // the last bit of residue
+        let fill = 1.0
+            - sand_state
+                .sand_config
+                .flow_curve
+                .sample_clamped(1.0 - hourglass.displayed_upper_chamber());
+        let fill = if fill < sand_state.sand_config.min_flow_fill {
+            0.0
+        } else {
+            accessibility.displayed_fill(fill)
+        };
+
+        // Ease the shown fill towards the timer's, turning it over with the sand on flips
+        let smoothing = sand_state.sand_config.fill_smoothing;
+        let flipped = sand_state.flips != hourglass.stats.flips;
+        sand_state.flips = hourglass.stats.flips;
+        let fill = if smoothing > 0.0 {
+            let shown = if flipped {
+                1.0 - sand_state.fill_percent
+            } else {
+                sand_state.fill_percent
+            };
+            let eased = shown + (fill - shown) * (1.0 - (-smoothing * time.delta_secs()).exp());
+            if (eased - fill).abs() < 1e-3 {
+                fill
+            } else {
+                eased
+            }
+        } else {
+            fill
+        };
+        // Lower quality tiers skip changes too small to notice
+        let threshold = quality
+            .copied()
+            .unwrap_or(config.quality)
+            .fill_update_threshold();
+        if fill == 0.0 || fill == 1.0 || (fill - sand_state.fill_percent).abs() >= threshold {
+            update_sand_fill_percent(&mut sand_state, fill);
+        }
+
+        // Keep sand surfaces level with gravity for hourglasses resting at an angle
+        sand_state.set_tilt(hourglass.base_rotation);
+
+        // Also sync the sand color, following the gradient if there is one
+        let color = sand_state
+            .sand_config
+            .color_by_remaining
+            .as_ref()
+            .map_or(hourglass.sand_color, |gradient| {
+                gradient.sample(hourglass.upper_chamber)
+            });
+        if sand_state.sand_config.color != color {
+            sand_state.sand_config.color = color;
+            sand_state.request_update();
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Saving hourglass state across app launches, e.g. for real-time timers like daily rewards.
+//!
+//! Requires the `persistence` feature. Snapshots are stored as RON and catch up on the time that
+//! passed while the app was closed when they are restored.
+
+use crate::components::{Hourglass, HourglassStats};
+use crate::events::{HourglassEmptyEvent, HourglassFlipStartEvent};
+use crate::simulation::{step_hourglass, HourglassSimEvent};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use web_time::SystemTime;
+
+/// Runtime state of an [`Hourglass`] saved at a point in time
+///
+/// Flips in progress are not saved, the hourglass is restored as it was before the flip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HourglassSnapshot {
+    pub total_time: f32,
+    pub remaining_time: f32,
+    pub running: bool,
+    pub speed: f32,
+    pub flipped: bool,
+    pub auto_flip_when_empty: bool,
+    pub upper_chamber: f32,
+    pub lower_chamber: f32,
+    pub flow_rate: f32,
+    pub flips: u32,
+    pub empties: u32,
+    pub elapsed: f32,
+    /// Time at which the snapshot was taken (in seconds since the Unix epoch)
+    pub saved_at: f64,
+}
+
+impl HourglassSnapshot {
+    /// Capture the state of an hourglass at `now`
+    pub fn capture(hourglass: &Hourglass, now: SystemTime) -> Self {
+        Self {
+            total_time: hourglass.total_time,
+            remaining_time: hourglass.remaining_time,
+            running: hourglass.running,
+            speed: hourglass.speed,
+            flipped: hourglass.flipped,
+            auto_flip_when_empty: hourglass.auto_flip_when_empty,
+            upper_chamber: hourglass.upper_chamber,
+            lower_chamber: hourglass.lower_chamber,
+            flow_rate: hourglass.flow_rate,
+            flips: hourglass.stats.flips,
+            empties: hourglass.stats.empties,
+            elapsed: hourglass.stats.elapsed,
+            saved_at: now
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64(),
+        }
+    }
+
+    /// Restore the saved state into an hourglass, advancing it by the time that passed until `now`
+    ///
+    /// Returns the events that happened while catching up, e.g. the hourglass running out while
+    /// the app was closed. Time never runs backwards if the clock was changed in between.
+    pub fn restore(&self, hourglass: &mut Hourglass, now: SystemTime) -> Vec<HourglassSimEvent> {
+        hourglass.total_time = self.total_time;
+        hourglass.remaining_time = self.remaining_time;
+        hourglass.running = self.running;
+        hourglass.speed = self.speed;
+        hourglass.flipped = self.flipped;
+        hourglass.auto_flip_when_empty = self.auto_flip_when_empty;
+        hourglass.upper_chamber = self.upper_chamber;
+        hourglass.lower_chamber = self.lower_chamber;
+        hourglass.flow_rate = self.flow_rate;
+        hourglass.stats = HourglassStats {
+            flips: self.flips,
+            empties: self.empties,
+            elapsed: self.elapsed,
+        };
+        hourglass.flipping = false;
+        hourglass.flip_progress = 0.0;
+        hourglass.flip_started = false;
+        hourglass.current_rotation = 0.0;
+        hourglass.target_rotation = 0.0;
+        hourglass.adjust_progress = 1.0;
+        hourglass.pending_time_adjustment = 0.0;
+        hourglass.queued_time_adjustment = 0.0;
+        hourglass.pending_overflow = 0.0;
+
+        let saved_at = SystemTime::UNIX_EPOCH + Duration::from_secs_f64(self.saved_at.max(0.0));
+        let offline = now.duration_since(saved_at).unwrap_or_default();
+        step_hourglass(hourglass, offline.as_secs_f32())
+    }
+
+    /// Serialize the snapshot to a RON string
+    pub fn to_ron_string(&self) -> String {
+        ron::to_string(self).expect("hourglass snapshots always serialize")
+    }
+
+    /// Deserialize a snapshot from a RON string
+    pub fn from_ron_str(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+
+    /// Serialize the snapshot to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_ron_string().into_bytes()
+    }
+
+    /// Deserialize a snapshot from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_bytes(bytes)
+    }
+}
+
+/// Storage for saved hourglasses, keyed by [`PersistentHourglass::key`]
+///
+/// Implement it to save through another backend, e.g. a `bevy_pkv` store.
+pub trait HourglassStore: Send + Sync + 'static {
+    /// Load the value saved under `key`
+    fn load(&self, key: &str) -> Option<String>;
+    /// Save a value under `key`, replacing the previous one
+    fn save(&mut self, key: &str, value: &str);
+}
+
+/// Store keeping saved hourglasses in memory, e.g. for tests
+#[derive(Debug, Clone, Default)]
+pub struct MemoryHourglassStore(pub HashMap<String, String>);
+
+impl HourglassStore for MemoryHourglassStore {
+    fn load(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn save(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+}
+
+/// Store saving every hourglass to a `<key>.ron` file in a directory
+#[derive(Debug, Clone)]
+pub struct FileHourglassStore {
+    pub directory: PathBuf,
+}
+
+impl FileHourglassStore {
+    /// Creates a store in the given directory, created on the first save
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{key}.ron"))
+    }
+}
+
+impl HourglassStore for FileHourglassStore {
+    fn load(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn save(&mut self, key: &str, value: &str) {
+        let result = std::fs::create_dir_all(&self.directory)
+            .and_then(|_| std::fs::write(self.path(key), value));
+        if let Err(error) = result {
+            warn!("Failed to save hourglass '{key}': {error}");
+        }
+    }
+}
+
+/// Resource enabling persistence of [`PersistentHourglass`]es through a store
+///
+/// Hourglasses are saved periodically and when the app exits.
+#[derive(Resource)]
+pub struct HourglassPersistence {
+    pub store: Box<dyn HourglassStore>,
+    /// Time between saves (in seconds of real time)
+    pub save_interval: f32,
+    since_save: f32,
+}
+
+impl HourglassPersistence {
+    /// Persist hourglasses in the given store, saving them every second
+    pub fn new(store: impl HourglassStore) -> Self {
+        Self {
+            store: Box::new(store),
+            save_interval: 1.0,
+            since_save: 0.0,
+        }
+    }
+
+    /// Sets the time between saves (in seconds)
+    pub fn with_save_interval(mut self, save_interval: f32) -> Self {
+        self.save_interval = save_interval;
+        self
+    }
+}
+
+/// Component saving an [`Hourglass`] under a key and restoring it when spawned again
+#[derive(Component, Debug, Clone)]
+pub struct PersistentHourglass {
+    /// Unique key of the hourglass in the store
+    pub key: String,
+}
+
+impl PersistentHourglass {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+}
+
+/// System restoring newly spawned persistent hourglasses from the store
+pub fn restore_persistent_hourglasses(
+    persistence: Res<HourglassPersistence>,
+    mut query: Query<(Entity, &PersistentHourglass, &mut Hourglass), Added<PersistentHourglass>>,
+    mut empty_events: EventWriter<HourglassEmptyEvent>,
+    mut flip_start_events: EventWriter<HourglassFlipStartEvent>,
+) {
+    if query.is_empty() {
+        return;
+    }
+    // `web_time` as `std::time::SystemTime::now` panics on the web
+    let now = SystemTime::now();
+    for (entity, persistent, mut hourglass) in query.iter_mut() {
+        let Some(saved) = persistence.store.load(&persistent.key) else {
+            continue;
+        };
+        let snapshot = match HourglassSnapshot::from_ron_str(&saved) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                warn!("Ignoring saved hourglass '{}': {error}", persistent.key);
+                continue;
+            }
+        };
+
+        for event in snapshot.restore(&mut hourglass, now) {
+            match event {
+                HourglassSimEvent::FlipStart => {
+                    flip_start_events.write(HourglassFlipStartEvent { entity });
+                }
+                HourglassSimEvent::Empty { total_time } => {
+                    empty_events.write(HourglassEmptyEvent { entity, total_time });
+                }
+                // Restoring clears pending adjustments
+                HourglassSimEvent::TimeAdjusted { .. } | HourglassSimEvent::TimeOverflow { .. } => {
+                }
+            }
+        }
+    }
+}
+
+/// System saving persistent hourglasses every save interval and when the app exits
+pub fn save_persistent_hourglasses(
+    time: Res<Time<Real>>,
+    mut persistence: ResMut<HourglassPersistence>,
+    query: Query<(&PersistentHourglass, &Hourglass)>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    let exiting = exit_events.read().count() > 0;
+    persistence.since_save += time.delta_secs();
+    if !exiting && persistence.since_save < persistence.save_interval {
+        return;
+    }
+    persistence.since_save = 0.0;
+
+    let now = SystemTime::now();
+    for (persistent, hourglass) in query.iter() {
+        let snapshot = HourglassSnapshot::capture(hourglass, now);
+        persistence
+            .store
+            .save(&persistent.key, &snapshot.to_ron_string());
+    }
+}
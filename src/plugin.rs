@@ -1,9 +1,35 @@
 //! Defines the hourglass plugin.
 
+use crate::accessibility::update_accessible_hourglasses;
+use crate::attachment::follow_hourglass_attachments;
+use crate::binding::apply_hourglass_bindings;
+use crate::deadline::sync_hourglass_deadlines;
 use crate::events::*;
-use crate::mesh_hourglass::{sync_mesh_hourglass_with_timer, update_mesh_hourglass_sand};
-use crate::resources::HourglassConfig;
-use crate::systems::{update_hourglasses, update_sand_splash};
+use crate::interaction::detect_hourglass_clicks;
+use crate::label::keep_hourglass_labels_upright;
+use crate::link::sync_hourglass_links;
+use crate::loading::animate_loading_indicator_dots;
+#[cfg(feature = "persistence")]
+use crate::persistence::{
+    restore_persistent_hourglasses, save_persistent_hourglasses, HourglassPersistence,
+};
+use crate::render_target::{update_hourglass_image_cameras, HourglassImageLayers};
+use crate::renderer::{HourglassRenderSet, HourglassRendererAppExt, MeshHourglassRenderer};
+use crate::replay::{apply_hourglass_replays, record_hourglasses};
+use crate::resources::{
+    HourglassAccessibility, HourglassConfig, HourglassDiagnostics, HourglassGlobalState,
+    HourglassPowerSaving,
+};
+use crate::spawn_animation::animate_hourglass_spawns;
+use crate::systems::{
+    apply_continuous_rotation, apply_swing_mounts, detect_hourglass_speed_changes,
+    sync_hourglass_empty_markers, update_hourglasses,
+};
+use crate::theme::apply_global_hourglass_config;
+use crate::time_zone::detect_hourglass_time_zones;
+use crate::tipping::detect_tipped_hourglasses;
+use crate::ui_sync::sync_hourglass_to_ui_node;
+use crate::warnings::{apply_hourglass_warnings, sync_hourglass_warnings, HourglassWarnings};
 use bevy::prelude::*;
 
 /// Plugin for adding hourglass functionality to Bevy apps
@@ -13,24 +39,115 @@ pub struct HourglassPlugin;
 impl Plugin for HourglassPlugin {
     fn build(&self, app: &mut App) {
         // Register resources
-        app.init_resource::<HourglassConfig>();
+        app.init_resource::<HourglassConfig>()
+            .init_resource::<HourglassAccessibility>()
+            .init_resource::<HourglassPowerSaving>()
+            .init_resource::<HourglassGlobalState>()
+            .init_resource::<HourglassDiagnostics>()
+            .init_resource::<HourglassWarnings>();
+
+        // Warnings apply to hourglasses built at startup as well
+        apply_hourglass_warnings(app.world().resource::<HourglassWarnings>());
+        app.add_systems(
+            First,
+            sync_hourglass_warnings.run_if(resource_changed::<HourglassWarnings>),
+        );
 
         // Register events
         app.add_event::<HourglassFlipStartEvent>()
-            .add_event::<HourglassEmptyEvent>();
+            .add_event::<HourglassEmptyEvent>()
+            .add_event::<HourglassSpeedChangeEvent>()
+            .add_event::<HourglassTimeAdjustedEvent>()
+            .add_event::<HourglassTimeOverflowEvent>()
+            .add_event::<HourglassShatteredEvent>()
+            .add_event::<HourglassTippedEvent>()
+            .add_event::<HourglassReplayEvent>()
+            .add_event::<HourglassChainCompleteEvent>()
+            .add_event::<HourglassInteractionEvent>();
 
         // Add core hourglass update system
-        app.add_systems(Update, update_hourglasses);
-
-        // Mesh-based visualization systems
         app.add_systems(
             Update,
             (
-                sync_mesh_hourglass_with_timer,
-                update_mesh_hourglass_sand,
-                update_sand_splash,
+                apply_continuous_rotation,
+                apply_swing_mounts,
+                apply_hourglass_replays,
+                sync_hourglass_deadlines,
+                apply_hourglass_bindings,
+                detect_tipped_hourglasses,
+                detect_hourglass_time_zones,
+                update_hourglasses,
+                sync_hourglass_links,
+                sync_hourglass_empty_markers,
+                detect_hourglass_speed_changes,
+                record_hourglasses,
             )
                 .chain(),
         );
+
+        // Restore saved hourglasses before they are updated, and save them at the end of the frame
+        #[cfg(feature = "persistence")]
+        app.add_systems(
+            Update,
+            restore_persistent_hourglasses
+                .before(update_hourglasses)
+                .run_if(resource_exists::<HourglassPersistence>),
+        )
+        .add_systems(
+            Last,
+            save_persistent_hourglasses.run_if(resource_exists::<HourglassPersistence>),
+        );
+
+        // Rendering backends draw the state of the current frame
+        app.configure_sets(Update, HourglassRenderSet.after(sync_hourglass_links));
+        app.add_hourglass_renderer(MeshHourglassRenderer);
+
+        // Spawn and despawn animations, applied on top of what the renderers drew
+        app.add_systems(Update, animate_hourglass_spawns.after(HourglassRenderSet));
+
+        // Re-theme hourglasses when the global configuration changes
+        app.add_systems(
+            Update,
+            apply_global_hourglass_config
+                .after(update_hourglasses)
+                .before(HourglassRenderSet),
+        );
+
+        // Report hourglass state to assistive technologies
+        app.add_systems(
+            Update,
+            update_accessible_hourglasses.after(update_hourglasses),
+        );
+
+        // Keep labels upright once flips have rotated their hourglasses
+        app.add_systems(
+            Update,
+            keep_hourglass_labels_upright.after(update_hourglasses),
+        );
+
+        // Clicks on hourglasses and their parts
+        app.add_systems(
+            Update,
+            detect_hourglass_clicks.run_if(resource_exists::<ButtonInput<MouseButton>>),
+        );
+
+        // Loading indicator animation
+        app.add_systems(Update, animate_loading_indicator_dots);
+
+        // Off-screen cameras rendering hourglasses to images
+        app.init_resource::<HourglassImageLayers>()
+            .add_systems(Update, update_hourglass_image_cameras);
+
+        // Move attached hourglasses to their targets before transforms are propagated
+        app.add_systems(
+            PostUpdate,
+            follow_hourglass_attachments.before(TransformSystem::TransformPropagate),
+        );
+
+        // Keep hourglasses aligned with their UI nodes once layout has been computed
+        app.add_systems(
+            PostUpdate,
+            sync_hourglass_to_ui_node.after(TransformSystem::TransformPropagate),
+        );
     }
 }
@@ -0,0 +1,70 @@
+//! Quality tiers trading the detail of the sand for performance, e.g. behind a graphics setting.
+
+use bevy::prelude::*;
+use hourglass_geometry::{BulbStyle, NeckStyle};
+
+/// Level of detail of the sand of hourglasses
+///
+/// Set for every hourglass with [`HourglassConfig::quality`](crate::HourglassConfig::quality),
+/// and overridden for single hourglasses by adding it as a component, e.g. with
+/// [`HourglassMeshBuilder::with_quality`](crate::HourglassMeshBuilder::with_quality). The glass
+/// is built once and keeps its own curve resolution.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum QualityTier {
+    /// Coarse sand updated in visible steps, with few splash particles
+    Low,
+    /// Smoother sand with half the splash particles
+    Medium,
+    /// The sand and splashes as configured
+    #[default]
+    High,
+    /// Finer sand curves and twice the splash particles
+    Ultra,
+}
+
+impl QualityTier {
+    /// Factor applied to the curve resolution of the bulbs and neck for the sand
+    pub fn curve_resolution_scale(self) -> f32 {
+        match self {
+            QualityTier::Low => 0.3,
+            QualityTier::Medium => 0.6,
+            QualityTier::High => 1.0,
+            QualityTier::Ultra => 1.5,
+        }
+    }
+
+    /// Factor applied to the number of splash particles
+    pub fn splash_scale(self) -> f32 {
+        match self {
+            QualityTier::Low => 0.25,
+            QualityTier::Medium => 0.5,
+            QualityTier::High => 1.0,
+            QualityTier::Ultra => 2.0,
+        }
+    }
+
+    /// Smallest change of the shown fill (0.0 - 1.0) that rebuilds the sand meshes
+    ///
+    /// Full and empty bulbs are always shown right away.
+    pub fn fill_update_threshold(self) -> f32 {
+        match self {
+            QualityTier::Low => 0.01,
+            QualityTier::Medium => 0.004,
+            QualityTier::High | QualityTier::Ultra => 0.0,
+        }
+    }
+
+    /// Bulb style with its curve resolution scaled for this tier
+    pub fn bulb_style(self, bulb_style: &BulbStyle) -> BulbStyle {
+        bulb_style.with_curve_resolution(self.scale_resolution(bulb_style.curve_resolution()))
+    }
+
+    /// Neck style with its curve resolution scaled for this tier
+    pub fn neck_style(self, neck_style: &NeckStyle) -> NeckStyle {
+        neck_style.with_curve_resolution(self.scale_resolution(neck_style.curve_resolution()))
+    }
+
+    fn scale_resolution(self, resolution: usize) -> usize {
+        ((resolution as f32 * self.curve_resolution_scale()).round() as usize).max(2)
+    }
+}
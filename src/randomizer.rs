@@ -0,0 +1,136 @@
+//! Random hourglass styles for varied decorations and stress tests.
+
+use crate::mesh::{HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassMeshSandConfig};
+use bevy::prelude::*;
+use hourglass_geometry::{BulbStyle, NeckStyle};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+/// Generates random hourglass styles within configurable ranges
+///
+/// ```
+/// use bevy_hourglass::HourglassStyleRandomizer;
+///
+/// let randomizer = HourglassStyleRandomizer::default();
+/// let mut rng = HourglassStyleRandomizer::seeded_rng(42);
+/// let (body, plates, sand) = randomizer.random_style(&mut rng);
+/// assert!(randomizer.height.contains(&body.total_height));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HourglassStyleRandomizer {
+    /// Total height of the body
+    pub height: RangeInclusive<f32>,
+    /// Width of the bulbs relative to their height
+    pub bulb_width_factor: RangeInclusive<f32>,
+    /// Curvature of circular bulbs
+    pub bulb_curvature: RangeInclusive<f32>,
+    /// Chance of straight instead of circular bulbs (0.0 - 1.0)
+    pub straight_bulb_chance: f32,
+    /// Width of the neck
+    pub neck_width: RangeInclusive<f32>,
+    /// Height of the neck
+    pub neck_height: RangeInclusive<f32>,
+    /// Curvature of curved necks
+    pub neck_curvature: RangeInclusive<f32>,
+    /// Chance of straight instead of curved necks (0.0 - 1.0)
+    pub straight_neck_chance: f32,
+    /// Width of the plates relative to the body height
+    pub plate_width_factor: RangeInclusive<f32>,
+    /// Height of the plates
+    pub plate_height: RangeInclusive<f32>,
+    /// How full the top bulb starts
+    pub fill_percent: RangeInclusive<f32>,
+    /// Distance between the sand and the glass walls
+    pub wall_offset: RangeInclusive<f32>,
+    /// Opacity of the glass
+    pub glass_alpha: f32,
+}
+
+impl Default for HourglassStyleRandomizer {
+    fn default() -> Self {
+        Self {
+            height: 80.0..=200.0,
+            bulb_width_factor: 0.6..=0.9,
+            bulb_curvature: 0.3..=3.0,
+            straight_bulb_chance: 0.33,
+            neck_width: 6.0..=18.0,
+            neck_height: 4.0..=16.0,
+            neck_curvature: 0.1..=1.0,
+            straight_neck_chance: 0.5,
+            plate_width_factor: 0.7..=1.0,
+            plate_height: 4.0..=12.0,
+            fill_percent: 0.3..=0.7,
+            wall_offset: 3.0..=8.0,
+            glass_alpha: 0.3,
+        }
+    }
+}
+
+impl HourglassStyleRandomizer {
+    /// Random number generator seeded for reproducible styles
+    pub fn seeded_rng(seed: u64) -> StdRng {
+        StdRng::seed_from_u64(seed)
+    }
+
+    /// Generate a random body, plates and sand configuration
+    pub fn random_style<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (
+        HourglassMeshBodyConfig,
+        HourglassMeshPlatesConfig,
+        HourglassMeshSandConfig,
+    ) {
+        let height = rng.random_range(self.height.clone());
+
+        let bulb_style = if rng.random_bool(self.straight_bulb_chance.clamp(0.0, 1.0) as f64) {
+            BulbStyle::Straight {
+                width_factor: rng.random_range(self.bulb_width_factor.clone()),
+            }
+        } else {
+            BulbStyle::Circular {
+                curvature: rng.random_range(self.bulb_curvature.clone()),
+                width_factor: rng.random_range(self.bulb_width_factor.clone()),
+                curve_resolution: rng.random_range(15..=30),
+            }
+        };
+
+        let neck_width = rng.random_range(self.neck_width.clone());
+        let neck_height = rng.random_range(self.neck_height.clone());
+        let neck_style = if rng.random_bool(self.straight_neck_chance.clamp(0.0, 1.0) as f64) {
+            NeckStyle::Straight {
+                width: neck_width,
+                height: neck_height,
+            }
+        } else {
+            NeckStyle::Curved {
+                curvature: rng.random_range(self.neck_curvature.clone()),
+                width: neck_width,
+                height: neck_height,
+                curve_resolution: rng.random_range(3..=10),
+            }
+        };
+
+        let body = HourglassMeshBodyConfig {
+            total_height: height,
+            bulb_style,
+            neck_style,
+            color: Color::hsla(rng.random_range(0.0..360.0), 0.7, 0.8, self.glass_alpha),
+            ..default()
+        };
+        let plates = HourglassMeshPlatesConfig {
+            width: height * rng.random_range(self.plate_width_factor.clone()),
+            height: rng.random_range(self.plate_height.clone()),
+            color: Color::hsla(rng.random_range(0.0..360.0), 0.6, 0.4, 1.0),
+        };
+        let sand = HourglassMeshSandConfig {
+            color: Color::hsla(rng.random_range(0.0..360.0), 0.8, 0.6, 1.0),
+            fill_percent: rng.random_range(self.fill_percent.clone()),
+            wall_offset: rng.random_range(self.wall_offset.clone()),
+            ..default()
+        };
+
+        (body, plates, sand)
+    }
+}
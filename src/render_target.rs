@@ -0,0 +1,180 @@
+//! Renders hourglasses to off-screen images, e.g. for insets in UI corners.
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+use bevy::render::camera::{RenderTarget, ScalingMode};
+use bevy::render::render_resource::{
+    Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::view::RenderLayers;
+use std::collections::HashMap;
+
+/// First render layer handed out to hourglasses rendered to images
+pub const FIRST_HOURGLASS_IMAGE_LAYER: usize = 16;
+
+/// Render layers of the cameras rendering hourglasses to images
+///
+/// Layers are handed out from [`FIRST_HOURGLASS_IMAGE_LAYER`] up, and reused once their camera
+/// is despawned along with its hourglass.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HourglassImageLayers {
+    layers: HashMap<Entity, usize>,
+}
+
+impl HourglassImageLayers {
+    /// Render layer of an image camera, if it has one
+    pub fn get(&self, camera: Entity) -> Option<usize> {
+        self.layers.get(&camera).copied()
+    }
+
+    /// Number of layers in use
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether no layers are in use
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Hand out the lowest free layer to a camera
+    fn allocate(&mut self, camera: Entity) -> usize {
+        let layer = (FIRST_HOURGLASS_IMAGE_LAYER..)
+            .find(|layer| !self.layers.values().any(|used| used == layer))
+            .expect("there is always a free layer");
+        self.layers.insert(camera, layer);
+        layer
+    }
+
+    /// Free the layer of a camera that is gone
+    fn free(&mut self, camera: Entity) {
+        self.layers.remove(&camera);
+    }
+}
+
+/// Component for the off-screen camera rendering an hourglass to an image
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HourglassImageCamera {
+    /// Hourglass being rendered
+    pub hourglass: Entity,
+    /// Render layer shared by the hourglass and this camera
+    pub layer: usize,
+}
+
+/// Render an hourglass to a new image of the given size in pixels
+///
+/// Spawns a camera that follows the hourglass and fits its [`Hourglass::size`] in the image.
+/// The hourglass gets its own render layer on top of its existing ones, so it still shows up
+/// in the main view. The camera is despawned along with the hourglass.
+pub fn render_hourglass_to_image(
+    commands: &mut Commands,
+    images: &mut ResMut<Assets<Image>>,
+    hourglass: Entity,
+    size: UVec2,
+) -> Handle<Image> {
+    let extent = Extent3d {
+        width: size.x.max(1),
+        height: size.y.max(1),
+        ..default()
+    };
+
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size: extent,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(extent);
+    let handle = images.add(image);
+
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                // Render before the main camera so the image is ready when it is displayed
+                order: -1,
+                target: RenderTarget::Image(handle.clone().into()),
+                clear_color: ClearColorConfig::Custom(Color::NONE),
+                ..default()
+            },
+        ))
+        .id();
+    // The layer comes from the allocator once the commands are applied
+    commands.queue(move |world: &mut World| {
+        let layer = world
+            .get_resource_or_init::<HourglassImageLayers>()
+            .allocate(camera);
+        if let Ok(mut camera) = world.get_entity_mut(camera) {
+            camera.insert((
+                RenderLayers::layer(layer),
+                HourglassImageCamera { hourglass, layer },
+            ));
+        }
+    });
+
+    handle
+}
+
+/// System that keeps image cameras on their hourglass and the hourglass on the camera's layer
+///
+/// Render layers are added to every descendant of the hourglass, so parts spawned later (like
+/// sand meshes) are picked up as well. Layers of despawned cameras are freed for reuse.
+pub fn update_hourglass_image_cameras(
+    mut commands: Commands,
+    mut image_layers: ResMut<HourglassImageLayers>,
+    mut removed_cameras: RemovedComponents<HourglassImageCamera>,
+    mut cameras: Query<(
+        Entity,
+        &HourglassImageCamera,
+        &mut Transform,
+        &mut Projection,
+    )>,
+    hourglasses: Query<(&Hourglass, &GlobalTransform)>,
+    children: Query<&Children>,
+    layers: Query<&RenderLayers>,
+) {
+    for camera in removed_cameras.read() {
+        image_layers.free(camera);
+    }
+
+    for (camera_entity, image_camera, mut transform, mut projection) in cameras.iter_mut() {
+        let Ok((hourglass, global_transform)) = hourglasses.get(image_camera.hourglass) else {
+            commands.entity(camera_entity).despawn();
+            continue;
+        };
+
+        let (scale, _, translation) = global_transform.to_scale_rotation_translation();
+        transform.translation.x = translation.x;
+        transform.translation.y = translation.y;
+
+        // Leave a small margin so flips don't clip the corners
+        let world_size = hourglass.size * scale.truncate().abs() * 1.1;
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scaling_mode = ScalingMode::AutoMin {
+                min_width: world_size.x,
+                min_height: world_size.y,
+            };
+        }
+
+        let camera_layer = RenderLayers::layer(image_camera.layer);
+        let entities = std::iter::once(image_camera.hourglass)
+            .chain(children.iter_descendants(image_camera.hourglass));
+        for entity in entities {
+            let current = layers.get(entity).cloned().unwrap_or_default();
+            if !current.intersects(&camera_layer) {
+                commands
+                    .entity(entity)
+                    .insert(current.with(image_camera.layer));
+            }
+        }
+    }
+}
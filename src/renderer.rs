@@ -0,0 +1,113 @@
+//! Rendering backends turning hourglass state into visuals.
+
+use crate::badge::update_hourglass_badges;
+use crate::culling::update_hourglass_culling;
+use crate::mesh::{
+    drain_mesh_hourglass_residue, sand_update_due, sync_mesh_hourglass_with_timer,
+    update_mesh_hourglass_sand, update_sand_stream_grains, HourglassAssetCache,
+};
+use crate::resize::apply_hourglass_resizes;
+use crate::shatter::{animate_shatter_fragments, update_hourglass_shatters};
+use crate::systems::{
+    animate_dust_motes, animate_neck_sparkles, update_hourglasses, update_mesh_sand_impact_points,
+    update_neck_sparkle, update_sand_overflow, update_sand_splash,
+};
+use crate::units::update_world_strokes;
+use bevy::prelude::*;
+
+/// System set running every rendering backend, after hourglass timers have been updated
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HourglassRenderSet;
+
+/// A backend drawing hourglasses, e.g. with 2D meshes, sprites or UI nodes
+///
+/// Backends only read [`Hourglass`](crate::Hourglass) and their own components, so they can be
+/// added with [`HourglassRendererAppExt::add_hourglass_renderer`] without touching the core
+/// timing systems. Systems added in [`HourglassRenderSet`] see the state of the current frame.
+pub trait HourglassRenderer: Send + Sync + 'static {
+    /// Register the resources and systems of the backend
+    fn build(&self, app: &mut App);
+}
+
+/// Rendering backends registered with the app, in registration order
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HourglassRenderers {
+    names: Vec<&'static str>,
+}
+
+impl HourglassRenderers {
+    /// Whether a backend of the given type has been registered
+    pub fn contains<R: HourglassRenderer>(&self) -> bool {
+        self.names.contains(&std::any::type_name::<R>())
+    }
+
+    /// Type names of the registered backends
+    pub fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+}
+
+/// Extension trait for registering hourglass rendering backends
+pub trait HourglassRendererAppExt {
+    /// Register a rendering backend next to the built-in mesh backend
+    ///
+    /// Each backend type is only built once, so registering it again does nothing.
+    fn add_hourglass_renderer<R: HourglassRenderer>(&mut self, renderer: R) -> &mut Self;
+}
+
+impl HourglassRendererAppExt for App {
+    fn add_hourglass_renderer<R: HourglassRenderer>(&mut self, renderer: R) -> &mut Self {
+        let mut renderers = self
+            .world_mut()
+            .get_resource_or_init::<HourglassRenderers>();
+        if renderers.contains::<R>() {
+            return self;
+        }
+        renderers.names.push(std::any::type_name::<R>());
+        renderer.build(self);
+        self
+    }
+}
+
+/// The built-in 2D mesh backend, registered by [`HourglassPlugin`](crate::HourglassPlugin)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MeshHourglassRenderer;
+
+impl HourglassRenderer for MeshHourglassRenderer {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HourglassAssetCache>();
+
+        // Skipped in headless apps without mesh assets
+        app.add_systems(
+            Update,
+            (
+                apply_hourglass_resizes,
+                update_world_strokes,
+                update_hourglass_culling,
+                sync_mesh_hourglass_with_timer,
+                update_mesh_hourglass_sand.run_if(sand_update_due),
+                update_sand_stream_grains,
+                update_mesh_sand_impact_points,
+                update_sand_splash,
+                update_neck_sparkle,
+                animate_neck_sparkles,
+                animate_dust_motes,
+                update_sand_overflow,
+                update_hourglass_shatters,
+                animate_shatter_fragments,
+                update_hourglass_badges,
+            )
+                .chain()
+                .in_set(HourglassRenderSet)
+                .run_if(
+                    resource_exists::<Assets<Mesh>>.and(resource_exists::<Assets<ColorMaterial>>),
+                ),
+        );
+
+        // Timers run out along with the sand shown, so the residue drains before they update
+        app.add_systems(
+            Update,
+            drain_mesh_hourglass_residue.before(update_hourglasses),
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Recording hourglass state over time and replaying it, e.g. for kill-cams or test fixtures.
+
+use crate::components::{Hourglass, HourglassValueSource};
+use crate::events::{HourglassEmptyEvent, HourglassReplayEvent};
+use bevy::prelude::*;
+
+/// Events that can be recorded in a [`HourglassTimeline`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HourglassRecordedEvent {
+    /// The hourglass started flipping
+    FlipStart,
+    /// The hourglass ran out of sand
+    Empty,
+}
+
+/// State of an hourglass at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourglassSample {
+    /// Time since the recording started (in seconds)
+    pub time: f32,
+    pub upper_chamber: f32,
+    pub lower_chamber: f32,
+    pub remaining_time: f32,
+    pub base_rotation: f32,
+    pub current_rotation: f32,
+    pub flipping: bool,
+}
+
+impl HourglassSample {
+    /// Sample the current state of an hourglass
+    pub fn from_hourglass(time: f32, hourglass: &Hourglass) -> Self {
+        Self {
+            time,
+            upper_chamber: hourglass.upper_chamber,
+            lower_chamber: hourglass.lower_chamber,
+            remaining_time: hourglass.remaining_time,
+            base_rotation: hourglass.base_rotation,
+            current_rotation: hourglass.current_rotation,
+            flipping: hourglass.flipping,
+        }
+    }
+
+    /// Interpolate between two samples, `t` going from 0.0 (self) to 1.0 (other)
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            time: self.time.lerp(other.time, t),
+            upper_chamber: self.upper_chamber.lerp(other.upper_chamber, t),
+            lower_chamber: self.lower_chamber.lerp(other.lower_chamber, t),
+            remaining_time: self.remaining_time.lerp(other.remaining_time, t),
+            base_rotation: self.base_rotation.lerp(other.base_rotation, t),
+            current_rotation: self.current_rotation.lerp(other.current_rotation, t),
+            flipping: if t < 0.5 {
+                self.flipping
+            } else {
+                other.flipping
+            },
+        }
+    }
+}
+
+/// Recorded state samples and events of an hourglass
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HourglassTimeline {
+    /// State samples, ordered by time
+    pub samples: Vec<HourglassSample>,
+    /// Events with the time they happened at, ordered by time
+    pub events: Vec<(f32, HourglassRecordedEvent)>,
+}
+
+impl HourglassTimeline {
+    /// Time of the first sample
+    pub fn start_time(&self) -> f32 {
+        self.samples.first().map_or(0.0, |sample| sample.time)
+    }
+
+    /// Time of the last sample
+    pub fn end_time(&self) -> f32 {
+        self.samples.last().map_or(0.0, |sample| sample.time)
+    }
+
+    /// Interpolated state at the given time, clamped to the recorded range
+    pub fn sample_at(&self, time: f32) -> Option<HourglassSample> {
+        let first = self.samples.first()?;
+        let last = self.samples.last()?;
+        if time <= first.time {
+            return Some(*first);
+        }
+        if time >= last.time {
+            return Some(*last);
+        }
+
+        let next = self.samples.partition_point(|sample| sample.time <= time);
+        let (a, b) = (&self.samples[next - 1], &self.samples[next]);
+        let span = b.time - a.time;
+        let t = if span > 0.0 {
+            (time - a.time) / span
+        } else {
+            1.0
+        };
+        Some(a.lerp(b, t))
+    }
+
+    /// Drop everything recorded before the given time
+    fn trim_before(&mut self, time: f32) {
+        let samples = self.samples.partition_point(|sample| sample.time < time);
+        self.samples.drain(..samples);
+        let events = self
+            .events
+            .partition_point(|(event_time, _)| *event_time < time);
+        self.events.drain(..events);
+    }
+}
+
+/// Component that records the state of an hourglass into a [`HourglassTimeline`]
+#[derive(Component, Debug, Clone)]
+pub struct HourglassRecorder {
+    /// Recorded samples and events
+    pub timeline: HourglassTimeline,
+    /// Time between samples (in seconds), 0.0 samples every frame
+    pub sample_interval: f32,
+    /// Only the most recent part of the recording is kept (in seconds), e.g. for kill-cams
+    pub max_duration: Option<f32>,
+    /// Whether the recorder is currently recording
+    pub recording: bool,
+    /// Time since the recording started (in seconds)
+    pub elapsed: f32,
+    time_since_sample: f32,
+    was_flipping: bool,
+}
+
+impl HourglassRecorder {
+    /// Record a sample every `sample_interval` seconds
+    pub fn new(sample_interval: f32) -> Self {
+        Self {
+            timeline: HourglassTimeline::default(),
+            sample_interval,
+            max_duration: None,
+            recording: true,
+            elapsed: 0.0,
+            time_since_sample: f32::INFINITY,
+            was_flipping: false,
+        }
+    }
+
+    /// Only keep the most recent `duration` seconds of the recording
+    pub fn with_max_duration(mut self, duration: f32) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+}
+
+/// Component that plays back a [`HourglassTimeline`] on an hourglass
+///
+/// While replaying, the hourglass is driven entirely by the timeline and its own timer is
+/// paused. Recorded events are sent as [`HourglassReplayEvent`]s. The component is removed and
+/// the hourglass restored to its previous value source once a non-looping replay finishes.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassReplay {
+    /// Timeline being played
+    pub timeline: HourglassTimeline,
+    /// Playback position, relative to the start of the timeline (in seconds)
+    pub time: f32,
+    /// Playback speed multiplier
+    pub speed: f32,
+    /// Whether to start over when reaching the end
+    pub looping: bool,
+    restore: Option<(HourglassValueSource, bool)>,
+}
+
+impl HourglassReplay {
+    /// Replay a timeline from the start at normal speed
+    pub fn new(timeline: HourglassTimeline) -> Self {
+        Self {
+            timeline,
+            time: 0.0,
+            speed: 1.0,
+            looping: false,
+            restore: None,
+        }
+    }
+
+    /// Set the playback speed multiplier
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set whether the replay starts over when reaching the end
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+}
+
+/// System that records the state and events of hourglasses with a [`HourglassRecorder`]
+pub fn record_hourglasses(
+    time: Res<Time>,
+    mut empty_events: EventReader<HourglassEmptyEvent>,
+    mut query: Query<(Entity, &Hourglass, &mut HourglassRecorder), Without<HourglassReplay>>,
+) {
+    let emptied: Vec<Entity> = empty_events.read().map(|event| event.entity).collect();
+
+    for (entity, hourglass, mut recorder) in query.iter_mut() {
+        if !recorder.recording {
+            continue;
+        }
+
+        recorder.elapsed += time.delta_secs();
+        recorder.time_since_sample += time.delta_secs();
+        let now = recorder.elapsed;
+
+        if hourglass.flipping && !recorder.was_flipping {
+            recorder
+                .timeline
+                .events
+                .push((now, HourglassRecordedEvent::FlipStart));
+        }
+        recorder.was_flipping = hourglass.flipping;
+        if emptied.contains(&entity) {
+            recorder
+                .timeline
+                .events
+                .push((now, HourglassRecordedEvent::Empty));
+        }
+
+        if recorder.time_since_sample >= recorder.sample_interval {
+            recorder.time_since_sample = 0.0;
+            recorder
+                .timeline
+                .samples
+                .push(HourglassSample::from_hourglass(now, hourglass));
+        }
+
+        if let Some(max_duration) = recorder.max_duration {
+            recorder.timeline.trim_before(now - max_duration);
+        }
+    }
+}
+
+/// System that drives hourglasses from their [`HourglassReplay`]
+pub fn apply_hourglass_replays(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Hourglass, &mut HourglassReplay)>,
+    mut replay_events: EventWriter<HourglassReplayEvent>,
+) {
+    for (entity, mut hourglass, mut replay) in query.iter_mut() {
+        // Pause the hourglass' own timer for the duration of the replay
+        if replay.restore.is_none() {
+            replay.restore = Some((hourglass.value_source, hourglass.running));
+            hourglass.value_source = HourglassValueSource::External;
+            hourglass.running = false;
+            hourglass.flipping = false;
+        }
+
+        let start = replay.timeline.start_time();
+        let duration = replay.timeline.end_time() - start;
+        let previous = replay.time;
+        replay.time += time.delta_secs() * replay.speed;
+
+        // Send the events passed over this frame
+        for (event_time, event) in &replay.timeline.events {
+            let relative = event_time - start;
+            if relative > previous && relative <= replay.time {
+                replay_events.write(HourglassReplayEvent {
+                    entity,
+                    event: *event,
+                });
+            }
+        }
+
+        let finished = replay.time >= duration;
+        if finished && replay.looping && duration > 0.0 {
+            replay.time = replay.time.rem_euclid(duration);
+        }
+
+        if let Some(sample) = replay.timeline.sample_at(start + replay.time) {
+            hourglass.upper_chamber = sample.upper_chamber;
+            hourglass.lower_chamber = sample.lower_chamber;
+            hourglass.remaining_time = sample.remaining_time;
+            hourglass.base_rotation = sample.base_rotation;
+            hourglass.current_rotation = sample.current_rotation;
+        }
+
+        if finished && !replay.looping {
+            if let Some((value_source, running)) = replay.restore {
+                hourglass.value_source = value_source;
+                hourglass.running = running;
+            }
+            commands.entity(entity).remove::<HourglassReplay>();
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Resizing hourglasses after they have been spawned.
+
+use crate::collider::HourglassColliderBackend;
+use crate::components::Hourglass;
+use crate::label::HourglassLabel;
+use crate::mesh::{
+    HourglassMeshBody, HourglassMeshBuilder, HourglassMeshPlate, HourglassMeshSandConfig,
+    HourglassMeshSandState, HourglassMeshShape,
+};
+use crate::units::HourglassStrokeScale;
+use bevy::prelude::*;
+
+/// Component requesting an hourglass to be resized, removed once applied
+///
+/// Inserted by [`resize_hourglass`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HourglassResize {
+    /// New total height of the body (in pixels)
+    pub total_height: f32,
+}
+
+/// Resize a mesh hourglass to the given body height, keeping its proportions
+///
+/// The body, plates, sand and everything derived from them (splash impact point, labels,
+/// anchor, collider) are rebuilt together on the next update. Hourglasses built without a body are left
+/// alone.
+pub fn resize_hourglass(commands: &mut Commands, hourglass: Entity, total_height: f32) {
+    commands
+        .entity(hourglass)
+        .insert(HourglassResize { total_height });
+}
+
+/// Type alias for the resized hourglass query to reduce type complexity
+type ResizeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static HourglassResize,
+        &'static mut HourglassMeshShape,
+        &'static mut Hourglass,
+        Option<&'static mut HourglassMeshSandState>,
+        Option<&'static mut HourglassColliderBackend>,
+        Option<&'static Children>,
+    ),
+>;
+
+/// Type alias for the parts of resized hourglasses to reduce type complexity
+type ResizePartsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        Has<HourglassMeshBody>,
+        Has<HourglassMeshPlate>,
+        Option<&'static mut HourglassLabel>,
+    ),
+>;
+
+/// System that rebuilds hourglasses at their requested size
+pub fn apply_hourglass_resizes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut hourglasses: ResizeQuery,
+    mut parts: ResizePartsQuery,
+) {
+    for (entity, resize, mut shape, mut hourglass, sand_state, collider, children) in
+        hourglasses.iter_mut()
+    {
+        // World-unit strokes are rebuilt for the new body
+        commands
+            .entity(entity)
+            .remove::<(HourglassResize, HourglassStrokeScale)>();
+
+        let factor = resize.total_height / shape.body_config.total_height;
+        if !factor.is_finite() || factor <= 0.0 {
+            continue;
+        }
+
+        // Scale everything with an absolute size, bulb widths follow the height on their own
+        let body_config = &mut shape.body_config;
+        body_config.total_height = resize.total_height;
+        body_config.neck_style = body_config.neck_style.scaled(factor);
+        if let Some(spacing) = &mut body_config.outline_spacing {
+            *spacing *= factor;
+        }
+        if let Some(plates_config) = &mut shape.plates_config {
+            plates_config.width *= factor;
+            plates_config.height *= factor;
+        }
+        hourglass.size =
+            HourglassMeshBuilder::visual_size(&shape.body_config, shape.plates_config.as_ref());
+
+        // New meshes are created rather than modified, as they may be shared with other hourglasses
+        let body_mesh = meshes.add(HourglassMeshBuilder::create_body_mesh(
+            &shape.body_config,
+            shape.render_style,
+        ));
+        let plate_mesh = shape.plates_config.as_ref().map(|plates_config| {
+            meshes.add(HourglassMeshBuilder::create_plate_mesh(plates_config))
+        });
+
+        // Parts are positioned relative to the anchor, so their offsets scale with the hourglass
+        for child in children.into_iter().flatten() {
+            let Ok((mut transform, is_body, is_plate, label)) = parts.get_mut(*child) else {
+                continue;
+            };
+            if let Some(mut label) = label {
+                label.center *= factor;
+                label.offset = HourglassLabel::upright_offset(&label.config, hourglass.size).y;
+                continue;
+            }
+            transform.translation.x *= factor;
+            transform.translation.y *= factor;
+            if is_body {
+                commands.entity(*child).insert(Mesh2d(body_mesh.clone()));
+            }
+            if let (true, Some(plate_mesh)) = (is_plate, &plate_mesh) {
+                commands.entity(*child).insert(Mesh2d(plate_mesh.clone()));
+            }
+        }
+
+        // Physics engines get a collider matching the new outline
+        if let Some(mut collider) = collider {
+            collider.scale(factor);
+            collider.insert_collider(&mut commands.entity(entity), &shape);
+        }
+
+        if let Some(mut sand_state) = sand_state {
+            let sand_config = HourglassMeshSandConfig {
+                wall_offset: sand_state.sand_config.wall_offset * factor,
+                ..sand_state.sand_config.clone()
+            };
+            sand_state.set_body_config(shape.body_config.clone());
+            sand_state.set_sand_config(sand_config);
+            sand_state.center_offset *= factor;
+        }
+    }
+}
@@ -1,5 +1,7 @@
-//! Defines the global configuration resource for the hourglass plugin.
+//! Defines the global configuration resources for the hourglass plugin.
 
+use crate::components::SandSplashConfig;
+use crate::quality::QualityTier;
 use bevy::prelude::*;
 
 /// Global configuration for the hourglass plugin
@@ -13,6 +15,39 @@ pub struct HourglassConfig {
 
     /// Default size for hourglasses
     pub default_size: Vec2,
+
+    /// Maximum number of sand splash particles and neck sparkles alive at once, across all
+    /// hourglasses
+    pub max_splash_particles: usize,
+
+    /// What happens to new splash particles once the maximum is reached
+    pub splash_overflow: SplashOverflowPolicy,
+
+    /// Level of detail of the sand of hourglasses without a [`QualityTier`] of their own
+    pub quality: QualityTier,
+
+    /// Maximum number of hourglasses whose sand meshes are rebuilt in a frame, `None` for no
+    /// limit
+    ///
+    /// Hourglasses over the budget are rebuilt in the following frames, taking turns so every
+    /// one of them is kept up to date.
+    pub max_sand_rebuilds_per_frame: Option<usize>,
+
+    /// Default duration of flips (in seconds)
+    pub default_flip_duration: f32,
+
+    /// Whether hourglasses flip on their own when empty by default
+    pub default_auto_flip: bool,
+
+    /// Default easing of the shown drain, see `HourglassMeshSandConfig::flow_curve`
+    pub default_flow_curve: EaseFunction,
+
+    /// Sand splash added to hourglasses by default, `None` to spawn them without
+    pub default_sand_splash: Option<SandSplashConfig>,
+
+    /// Whether changes to this resource re-theme hourglasses marked with
+    /// [`FollowGlobalTheme`](crate::FollowGlobalTheme)
+    pub watch: bool,
 }
 
 impl Default for HourglassConfig {
@@ -21,6 +56,15 @@ impl Default for HourglassConfig {
             default_container_color: Color::srgb(0.8, 0.8, 0.8),
             default_sand_color: Color::srgb(0.8, 0.6, 0.2),
             default_size: Vec2::new(100.0, 200.0),
+            max_splash_particles: 1000,
+            splash_overflow: SplashOverflowPolicy::default(),
+            quality: QualityTier::default(),
+            max_sand_rebuilds_per_frame: None,
+            default_flip_duration: 1.0,
+            default_auto_flip: false,
+            default_flow_curve: EaseFunction::Linear,
+            default_sand_splash: None,
+            watch: false,
         }
     }
 }
@@ -48,4 +92,187 @@ impl HourglassConfig {
         self.default_size = size;
         self
     }
+
+    /// Set the maximum number of sand splash particles alive at once
+    pub fn with_max_splash_particles(mut self, max_splash_particles: usize) -> Self {
+        self.max_splash_particles = max_splash_particles;
+        self
+    }
+
+    /// Set what happens to new splash particles once the maximum is reached
+    pub fn with_splash_overflow(mut self, splash_overflow: SplashOverflowPolicy) -> Self {
+        self.splash_overflow = splash_overflow;
+        self
+    }
+
+    /// Set the level of detail of the sand of hourglasses
+    pub fn with_quality(mut self, quality: QualityTier) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// Set the maximum number of sand meshes rebuilt in a frame
+    pub fn with_max_sand_rebuilds_per_frame(mut self, max_sand_rebuilds_per_frame: usize) -> Self {
+        self.max_sand_rebuilds_per_frame = Some(max_sand_rebuilds_per_frame);
+        self
+    }
+
+    /// Set the default flip duration (in seconds)
+    pub fn with_flip_duration(mut self, duration: f32) -> Self {
+        self.default_flip_duration = duration;
+        self
+    }
+
+    /// Set whether hourglasses flip on their own when empty by default
+    pub fn with_auto_flip(mut self, auto_flip: bool) -> Self {
+        self.default_auto_flip = auto_flip;
+        self
+    }
+
+    /// Set the default easing of the shown drain
+    pub fn with_flow_curve(mut self, flow_curve: EaseFunction) -> Self {
+        self.default_flow_curve = flow_curve;
+        self
+    }
+
+    /// Add the given sand splash to hourglasses by default
+    pub fn with_sand_splash(mut self, config: SandSplashConfig) -> Self {
+        self.default_sand_splash = Some(config);
+        self
+    }
+
+    /// Re-theme hourglasses following the global theme whenever this resource changes
+    pub fn with_watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self
+    }
+}
+
+/// What happens to new splash particles when [`HourglassConfig::max_splash_particles`] is reached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplashOverflowPolicy {
+    /// New particles are not spawned
+    #[default]
+    DropNew,
+    /// The particles closest to expiring are despawned to make room for new ones
+    RecycleOldest,
+}
+
+/// Counters describing the plugin's work, updated every frame
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HourglassDiagnostics {
+    /// Number of sand splash particles currently alive
+    pub splash_particles: usize,
+    /// Total number of splash particles not spawned because of the particle cap
+    pub dropped_splash_particles: usize,
+    /// Number of sand meshes waiting for a rebuild because of the per-frame budget
+    pub deferred_sand_rebuilds: usize,
+}
+
+/// Global state applied to every hourglass, e.g. to pause them all while a menu is open
+#[derive(Resource, Debug, Clone)]
+pub struct HourglassGlobalState {
+    /// Whether all hourglasses are paused
+    pub paused: bool,
+    /// Multiplier applied to elapsed time on top of each hourglass' own speed
+    pub speed: f32,
+}
+
+impl Default for HourglassGlobalState {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            speed: 1.0,
+        }
+    }
+}
+
+impl HourglassGlobalState {
+    /// Factor to scale elapsed time by, zero while paused
+    pub fn time_scale(&self) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            self.speed.max(0.0)
+        }
+    }
+}
+
+/// Global power saving settings, e.g. for idle screens on phones
+///
+/// Enabled by default on Android and iOS. While enabled, sand meshes are regenerated at most
+/// once per `sand_update_interval` with a capped curve resolution, and no splash particles are
+/// spawned. Timers are not affected.
+#[derive(Resource, Debug, Clone)]
+pub struct HourglassPowerSaving {
+    /// Whether power saving is enabled
+    pub enabled: bool,
+    /// Time between sand mesh updates while enabled (in seconds)
+    pub sand_update_interval: f32,
+    /// Highest curve resolution of the bulbs and neck used for the sand while enabled
+    pub max_curve_resolution: usize,
+}
+
+impl Default for HourglassPowerSaving {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(any(target_os = "android", target_os = "ios")),
+            sand_update_interval: 0.25,
+            max_curve_resolution: 8,
+        }
+    }
+}
+
+impl HourglassPowerSaving {
+    /// Power saving settings, enabled or not regardless of the platform
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    /// Set the time between sand mesh updates while enabled (in seconds)
+    pub fn with_sand_update_interval(mut self, sand_update_interval: f32) -> Self {
+        self.sand_update_interval = sand_update_interval;
+        self
+    }
+
+    /// Set the highest curve resolution used for the sand while enabled
+    pub fn with_max_curve_resolution(mut self, max_curve_resolution: usize) -> Self {
+        self.max_curve_resolution = max_curve_resolution;
+        self
+    }
+}
+
+/// Global accessibility settings applied to every hourglass
+#[derive(Resource, Debug, Clone)]
+pub struct HourglassAccessibility {
+    /// Disables splash particles, neck sparkles and the animated sand stream, and only updates
+    /// the sand fill in discrete steps
+    pub reduced_motion: bool,
+    /// Size of the fill steps used when `reduced_motion` is enabled (0.0 - 1.0)
+    pub fill_step: f32,
+}
+
+impl Default for HourglassAccessibility {
+    fn default() -> Self {
+        Self {
+            reduced_motion: false,
+            fill_step: 0.05,
+        }
+    }
+}
+
+impl HourglassAccessibility {
+    /// Fill level to display for the given actual fill, taking reduced motion into account
+    ///
+    /// With reduced motion the fill is rounded up to the next step, so the sand only runs out
+    /// once the hourglass is actually empty.
+    pub fn displayed_fill(&self, fill: f32) -> f32 {
+        if !self.reduced_motion || self.fill_step <= 0.0 {
+            return fill;
+        }
+        ((fill / self.fill_step).ceil() * self.fill_step).clamp(0.0, 1.0)
+    }
 }
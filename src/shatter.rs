@@ -0,0 +1,357 @@
+//! Breaking hourglasses apart on demand, e.g. for dramatic "time's up" moments.
+
+use crate::components::Hourglass;
+use crate::events::HourglassShatteredEvent;
+use crate::mesh::{HourglassMeshBuilder, HourglassMeshPlate, HourglassMeshSandState};
+use crate::resources::HourglassAccessibility;
+use bevy::prelude::*;
+use bevy::render::{mesh::Indices, render_resource::PrimitiveTopology};
+use bevy::sprite::AlphaMode2d;
+use earcutr::earcut;
+use hourglass_geometry::{
+    generate_sand_outline_with_params, point_in_polygon, HourglassShapeBuilder, Point2D, SandBulb,
+};
+use rand::prelude::*;
+
+/// Configuration for the shatter animation started with [`shatter_hourglass`]
+#[derive(Debug, Clone)]
+pub struct ShatterConfig {
+    /// Number of shards the glass breaks into
+    pub shard_count: usize,
+    /// Initial speed of the shards in pixels per second
+    pub speed: f32,
+    /// Maximum spin of the shards in radians per second
+    pub spin: f32,
+    /// Downward acceleration of the shards and sand in pixels per second squared
+    pub gravity: f32,
+    /// Number of sand particles spilled
+    pub sand_particle_count: usize,
+    /// Size of each sand particle
+    pub sand_particle_size: f32,
+    /// Duration of the animation in seconds, fragments fade out towards the end
+    pub duration: f32,
+    /// Whether the hourglass is despawned once the animation is over
+    pub despawn_after: bool,
+}
+
+impl Default for ShatterConfig {
+    fn default() -> Self {
+        Self {
+            shard_count: 10,
+            speed: 160.0,
+            spin: 6.0,
+            gravity: 500.0,
+            sand_particle_count: 60,
+            sand_particle_size: 3.0,
+            duration: 1.5,
+            despawn_after: true,
+        }
+    }
+}
+
+/// Component for an hourglass that is shattering or has shattered
+///
+/// Inserted by [`shatter_hourglass`]. The hourglass stops running and its parts are hidden while
+/// shards and sand particles fly off.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassShatter {
+    pub config: ShatterConfig,
+    /// Time since the shatter started (in seconds)
+    pub elapsed: f32,
+    /// Whether the fragments have been spawned
+    pub started: bool,
+    /// Whether the animation is over
+    pub finished: bool,
+    /// Materials of the fragments, faded out together
+    pub materials: Vec<Handle<ColorMaterial>>,
+}
+
+impl HourglassShatter {
+    pub fn new(config: ShatterConfig) -> Self {
+        Self {
+            config,
+            elapsed: 0.0,
+            started: false,
+            finished: false,
+            materials: Vec::new(),
+        }
+    }
+}
+
+/// Component for a flying piece of a shattered hourglass (glass shard, plate or sand grain)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShatterFragment {
+    /// Velocity in the hourglass' local space
+    pub velocity: Vec2,
+    /// Spin in radians per second
+    pub angular_velocity: f32,
+}
+
+/// Shatter an hourglass, breaking the glass into shards and spilling the sand
+///
+/// A [`HourglassShatteredEvent`] is sent when the animation starts. Hourglasses that already
+/// shattered are left alone.
+pub fn shatter_hourglass(commands: &mut Commands, hourglass: Entity, config: ShatterConfig) {
+    commands.queue(move |world: &mut World| {
+        if let Ok(mut entity) = world.get_entity_mut(hourglass) {
+            if !entity.contains::<HourglassShatter>() {
+                entity.insert(HourglassShatter::new(config));
+            }
+        }
+    });
+}
+
+/// Type alias for the shattering hourglass query to reduce type complexity
+type ShatterQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut HourglassShatter,
+        &'static mut Hourglass,
+        Option<&'static HourglassMeshSandState>,
+        Option<&'static Children>,
+    ),
+>;
+
+/// System that starts and finishes shatter animations
+pub fn update_hourglass_shatters(
+    mut commands: Commands,
+    time: Res<Time>,
+    accessibility: Res<HourglassAccessibility>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut shatter_query: ShatterQuery,
+    plates: Query<&HourglassMeshPlate>,
+) {
+    for (entity, mut shatter, mut hourglass, sand_state, children) in shatter_query.iter_mut() {
+        if shatter.finished {
+            continue;
+        }
+
+        if !shatter.started {
+            shatter.started = true;
+            hourglass.running = false;
+            hourglass.flipping = false;
+            commands.send_event(HourglassShatteredEvent { entity });
+
+            // Skip straight to the end with reduced motion
+            if accessibility.reduced_motion {
+                shatter.elapsed = shatter.config.duration;
+            }
+
+            // Plates fly off as they are, everything else is replaced by fragments
+            let mut rng = rand::rng();
+            for child in children.into_iter().flatten() {
+                if let Ok(plate) = plates.get(*child) {
+                    let direction = match plate {
+                        HourglassMeshPlate::Top => 1.0,
+                        HourglassMeshPlate::Bottom => -1.0,
+                    };
+                    commands.entity(*child).insert(ShatterFragment {
+                        velocity: Vec2::new(
+                            rng.random_range(-0.5..=0.5),
+                            direction * rng.random_range(0.3..=0.8),
+                        ) * shatter.config.speed,
+                        angular_velocity: rng.random_range(-1.0..=1.0) * shatter.config.spin,
+                    });
+                } else {
+                    commands.entity(*child).insert(Visibility::Hidden);
+                }
+            }
+
+            if let Some(sand_state) = sand_state {
+                let fragment_materials = spawn_shatter_fragments(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    entity,
+                    sand_state,
+                    &shatter.config,
+                    &mut rng,
+                );
+                shatter.materials = fragment_materials;
+            }
+        }
+
+        shatter.elapsed += time.delta_secs();
+        let progress = shatter.elapsed / shatter.config.duration.max(f32::EPSILON);
+
+        // Fade out over the last third of the animation
+        let alpha = ((1.0 - progress) * 3.0).clamp(0.0, 1.0);
+        for handle in &shatter.materials {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color.set_alpha(material.color.alpha().min(alpha));
+            }
+        }
+
+        if progress >= 1.0 {
+            shatter.finished = true;
+            if shatter.config.despawn_after {
+                commands.entity(entity).despawn();
+            } else {
+                // Only the fragments were still visible
+                for child in children.into_iter().flatten() {
+                    commands.entity(*child).insert(Visibility::Hidden);
+                }
+            }
+        }
+    }
+}
+
+/// System that moves shards and sand particles of shattering hourglasses
+pub fn animate_shatter_fragments(
+    time: Res<Time>,
+    shatters: Query<&HourglassShatter>,
+    mut fragments: Query<(&mut ShatterFragment, &mut Transform, &ChildOf)>,
+) {
+    let delta = time.delta_secs();
+    for (mut fragment, mut transform, child_of) in fragments.iter_mut() {
+        let Ok(shatter) = shatters.get(child_of.parent()) else {
+            continue;
+        };
+        if shatter.finished {
+            continue;
+        }
+
+        fragment.velocity.y -= shatter.config.gravity * delta;
+        transform.translation += (fragment.velocity * delta).extend(0.0);
+        transform.rotate_z(fragment.angular_velocity * delta);
+    }
+}
+
+/// Spawn glass shards and sand particles as children of the hourglass, returning their materials
+fn spawn_shatter_fragments(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    hourglass: Entity,
+    sand_state: &HourglassMeshSandState,
+    config: &ShatterConfig,
+    rng: &mut ThreadRng,
+) -> Vec<Handle<ColorMaterial>> {
+    let shape_builder = HourglassShapeBuilder {
+        total_height: sand_state.body_config.total_height,
+        bulb_style: sand_state.body_config.bulb_style.clone(),
+        neck_style: sand_state.body_config.neck_style.clone(),
+    };
+
+    let glass_material = materials.add(ColorMaterial {
+        color: sand_state.body_config.material_color(),
+        alpha_mode: AlphaMode2d::Blend,
+        ..default()
+    });
+    let sand_material = materials.add(ColorMaterial {
+        color: sand_state.sand_config.material_color(),
+        alpha_mode: AlphaMode2d::Blend,
+        ..default()
+    });
+
+    // Break the glass into shards made of neighbouring triangles, grouped by angle around the
+    // center so each shard is a wedge of the glass
+    let outline = shape_builder.generate_outline();
+    let coords: Vec<f32> = outline.iter().flat_map(|p| [p[0], p[1]]).collect();
+    let triangles = earcut(&coords, &[], 2).unwrap_or_default();
+    let shard_count = config.shard_count.max(1);
+    let mut shards: Vec<Vec<Point2D>> = vec![Vec::new(); shard_count];
+    for triangle in triangles.chunks_exact(3) {
+        let points = [
+            outline[triangle[0]],
+            outline[triangle[1]],
+            outline[triangle[2]],
+        ];
+        let centroid = points
+            .iter()
+            .fold(Vec2::ZERO, |sum, p| sum + Vec2::from(*p))
+            / 3.0;
+        let angle = centroid.y.atan2(centroid.x) + std::f32::consts::PI;
+        let index = ((angle / std::f32::consts::TAU) * shard_count as f32) as usize;
+        shards[index.min(shard_count - 1)].extend(points);
+    }
+
+    for shard in shards.into_iter().filter(|shard| !shard.is_empty()) {
+        let center =
+            shard.iter().fold(Vec2::ZERO, |sum, p| sum + Vec2::from(*p)) / shard.len() as f32;
+        let positions: Vec<[f32; 3]> = shard
+            .iter()
+            .map(|p| [p[0] - center.x, p[1] - center.y, 0.0])
+            .collect();
+        let vertex_count = positions.len();
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default());
+        mesh.insert_indices(Indices::U32((0..vertex_count as u32).collect()));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; vertex_count]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+
+        let direction = center.try_normalize().unwrap_or(Vec2::Y);
+        let shard_entity = commands
+            .spawn((
+                ShatterFragment {
+                    velocity: direction * config.speed * rng.random_range(0.5..=1.0),
+                    angular_velocity: rng.random_range(-1.0..=1.0) * config.spin,
+                },
+                Mesh2d(meshes.add(mesh)),
+                MeshMaterial2d(glass_material.clone()),
+                Transform::from_translation((sand_state.center_offset + center).extend(0.2)),
+            ))
+            .id();
+        commands.entity(hourglass).add_child(shard_entity);
+    }
+
+    // Spill the sand from wherever it currently is
+    let glass = shape_builder.generate_outline_with_wall_offset(sand_state.sand_config.wall_offset);
+    let sand: Vec<Vec<Point2D>> = [SandBulb::Top, SandBulb::Bottom]
+        .into_iter()
+        .map(|bulb| {
+            let mut params = sand_state.sand_outline_params(bulb);
+            params.solid_stream = false;
+            generate_sand_outline_with_params(&glass, &params)
+        })
+        .filter(|polygon| !polygon.is_empty())
+        .collect();
+    let half_width = glass.iter().map(|p| p[0].abs()).fold(0.0, f32::max);
+    let half_height = sand_state.body_config.total_height / 2.0;
+    let grain_mesh = meshes.add(HourglassMeshBuilder::create_quads_mesh(
+        &[[0.0, 0.0]],
+        config.sand_particle_size,
+    ));
+
+    // The amount of sand never changes, so the same number of grains spill at any fill level
+    let mut spilled = 0;
+    let mut attempts = 0;
+    while !sand.is_empty() && spilled < config.sand_particle_count && attempts < 10_000 {
+        attempts += 1;
+        let position = Vec2::new(
+            rng.random_range(-half_width..=half_width),
+            rng.random_range(-half_height..=half_height),
+        );
+        if !sand
+            .iter()
+            .any(|polygon| point_in_polygon([position.x, position.y], polygon))
+        {
+            continue;
+        }
+        spilled += 1;
+
+        // Grains burst sideways out of the broken glass, with a little upward kick
+        let direction = Vec2::new(position.x, position.y * 0.25)
+            .try_normalize()
+            .unwrap_or(Vec2::X);
+        let grain = commands
+            .spawn((
+                ShatterFragment {
+                    velocity: direction * config.speed * rng.random_range(0.2..=0.7)
+                        + Vec2::Y * config.speed * rng.random_range(0.0..=0.4),
+                    angular_velocity: 0.0,
+                },
+                Mesh2d(grain_mesh.clone()),
+                MeshMaterial2d(sand_material.clone()),
+                Transform::from_translation((sand_state.center_offset + position).extend(0.15)),
+            ))
+            .id();
+        commands.entity(hourglass).add_child(grain);
+    }
+
+    vec![glass_material, sand_material]
+}
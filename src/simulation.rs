@@ -0,0 +1,122 @@
+//! Headless hourglass simulation, usable without an `App` or ECS.
+
+use crate::components::{Hourglass, HourglassValueSource};
+
+/// Events produced while stepping an hourglass
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HourglassSimEvent {
+    /// The hourglass started flipping
+    FlipStart,
+    /// The hourglass ran out of sand
+    Empty {
+        /// Total time the hourglass was running (in seconds)
+        total_time: f32,
+    },
+    /// Time was added to or removed from the hourglass
+    TimeAdjusted {
+        /// Time added (in seconds), negative when time was removed
+        seconds: f32,
+    },
+    /// Time did not fit in the hourglass, see [`TimeOverflowPolicy`](crate::TimeOverflowPolicy)
+    TimeOverflow {
+        /// Time beyond the total time (in seconds)
+        seconds: f32,
+    },
+}
+
+/// Advance an hourglass by `delta` seconds and return the events that happened
+///
+/// This is the logic run by the plugin's `update_hourglasses` system. A flip is reported on the
+/// first step after it was started, whether by [`Hourglass::flip`] or by auto-flipping.
+pub fn step_hourglass(hourglass: &mut Hourglass, delta: f32) -> Vec<HourglassSimEvent> {
+    let mut events = Vec::new();
+
+    // Time added or removed since the last step
+    let adjusted = take_time_adjustments(hourglass, &mut events);
+
+    // Check if the hourglass was running and had time remaining before the update, or just had
+    // its last seconds taken away or drained
+    let was_running = hourglass.running
+        && (hourglass.remaining_time > 0.0
+            || adjusted < 0.0
+            || (hourglass.upper_chamber <= 0.0
+                && hourglass.value_source == HourglassValueSource::Timer));
+
+    // Flips started since the last step, reported once even if time stands still
+    if std::mem::take(&mut hourglass.flip_started) {
+        events.push(HourglassSimEvent::FlipStart);
+    }
+
+    hourglass.update(delta);
+
+    // Time queued during a flip or rewind is added by the update once it completes
+    take_time_adjustments(hourglass, &mut events);
+
+    // Check if the hourglass just became empty
+    if was_running && hourglass.remaining_time == 0.0 {
+        events.push(HourglassSimEvent::Empty {
+            total_time: hourglass.total_time,
+        });
+    }
+
+    events
+}
+
+/// Report the time added, removed or overflowing since the last report, returning the time added
+fn take_time_adjustments(hourglass: &mut Hourglass, events: &mut Vec<HourglassSimEvent>) -> f32 {
+    let adjusted = std::mem::take(&mut hourglass.pending_time_adjustment);
+    if adjusted != 0.0 {
+        events.push(HourglassSimEvent::TimeAdjusted { seconds: adjusted });
+    }
+    let overflow = std::mem::take(&mut hourglass.pending_overflow);
+    if overflow != 0.0 {
+        events.push(HourglassSimEvent::TimeOverflow { seconds: overflow });
+    }
+    adjusted
+}
+
+/// Hourglass logic without Bevy schedules, for testing game logic that depends on timers
+///
+/// ```
+/// use bevy_hourglass::{Hourglass, HourglassSim, HourglassSimEvent};
+///
+/// let mut sim = HourglassSim::new(Hourglass::new(2.0));
+/// assert!(sim.step(1.0).is_empty());
+/// assert_eq!(sim.step(1.0), vec![HourglassSimEvent::Empty { total_time: 2.0 }]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HourglassSim {
+    /// Simulated hourglass state
+    pub hourglass: Hourglass,
+    /// Total simulated time (in seconds)
+    pub elapsed: f32,
+}
+
+impl HourglassSim {
+    /// Simulate the given hourglass
+    pub fn new(hourglass: Hourglass) -> Self {
+        Self {
+            hourglass,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advance the simulation by `delta` seconds, returning the events that happened
+    pub fn step(&mut self, delta: f32) -> Vec<HourglassSimEvent> {
+        self.elapsed += delta;
+        step_hourglass(&mut self.hourglass, delta)
+    }
+
+    /// Advance the simulation in fixed steps until `duration` seconds have passed
+    pub fn run_for(&mut self, duration: f32, step: f32) -> Vec<(f32, HourglassSimEvent)> {
+        let mut events = Vec::new();
+        let mut remaining = duration;
+        while remaining > 0.0 && step > 0.0 {
+            let delta = step.min(remaining);
+            remaining -= delta;
+            let elapsed = self.elapsed + delta;
+            events.extend(self.step(delta).into_iter().map(|event| (elapsed, event)));
+        }
+        events
+    }
+}
@@ -0,0 +1,208 @@
+//! Animations played when hourglasses appear or are despawned.
+
+use crate::mesh::HourglassParts;
+use bevy::prelude::*;
+
+/// How an hourglass appears when spawned, played in reverse when it is despawned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnAnim {
+    /// Grow from nothing to full size
+    ScaleIn { duration: f32, easing: EaseFunction },
+    /// Fade the glass, plates and sand in from transparent
+    FadeIn { duration: f32 },
+    /// Fall from `height` pixels above its position and bounce into place
+    DropIn { duration: f32, height: f32 },
+}
+
+impl Default for SpawnAnim {
+    fn default() -> Self {
+        Self::ScaleIn {
+            duration: 0.3,
+            easing: EaseFunction::BackOut,
+        }
+    }
+}
+
+impl SpawnAnim {
+    /// Duration of the animation (in seconds)
+    pub fn duration(&self) -> f32 {
+        match *self {
+            Self::ScaleIn { duration, .. }
+            | Self::FadeIn { duration }
+            | Self::DropIn { duration, .. } => duration,
+        }
+    }
+
+    fn easing(&self) -> EaseFunction {
+        match *self {
+            Self::ScaleIn { easing, .. } => easing,
+            Self::FadeIn { .. } => EaseFunction::Linear,
+            Self::DropIn { .. } => EaseFunction::BounceOut,
+        }
+    }
+}
+
+/// Component playing a [`SpawnAnim`] on an hourglass, removed once it is over
+///
+/// Added by [`HourglassMeshBuilder::with_spawn_animation`](crate::HourglassMeshBuilder::with_spawn_animation)
+/// and [`despawn_with_animation`].
+#[derive(Component, Debug, Clone)]
+pub struct HourglassSpawnAnimation {
+    pub anim: SpawnAnim,
+    /// Time since the animation started (in seconds)
+    pub elapsed: f32,
+    /// Whether the animation plays in reverse and despawns the hourglass at the end
+    pub despawn: bool,
+    /// Scale of the hourglass before the animation started
+    pub base_scale: Option<Vec3>,
+    /// Vertical offset currently applied by [`SpawnAnim::DropIn`]
+    pub drop_offset: f32,
+    /// Materials faded by [`SpawnAnim::FadeIn`], along with their original alpha
+    pub materials: Option<Vec<(Handle<ColorMaterial>, f32)>>,
+    /// Shared glass and plate materials swapped out by [`SpawnAnim::FadeIn`], given back to
+    /// their parts once the hourglass has faded in
+    pub shared_materials: Vec<(Entity, Handle<ColorMaterial>)>,
+}
+
+impl HourglassSpawnAnimation {
+    /// Animation making the hourglass appear
+    pub fn appear(anim: SpawnAnim) -> Self {
+        Self {
+            anim,
+            elapsed: 0.0,
+            despawn: false,
+            base_scale: None,
+            drop_offset: 0.0,
+            materials: None,
+            shared_materials: Vec::new(),
+        }
+    }
+
+    /// Animation making the hourglass disappear before despawning it
+    pub fn disappear(anim: SpawnAnim) -> Self {
+        Self {
+            despawn: true,
+            ..Self::appear(anim)
+        }
+    }
+
+    /// How much of the hourglass is shown, from 0.0 (gone) to 1.0 (fully shown)
+    pub fn shown(&self) -> f32 {
+        let progress = (self.elapsed / self.anim.duration().max(f32::EPSILON)).min(1.0);
+        let progress = if self.despawn {
+            1.0 - progress
+        } else {
+            progress
+        };
+        EasingCurve::new(0.0, 1.0, self.anim.easing()).sample_clamped(progress)
+    }
+
+    /// Whether the animation is over
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.anim.duration()
+    }
+}
+
+/// Despawn an hourglass once it has played the given animation in reverse
+pub fn despawn_with_animation(commands: &mut Commands, hourglass: Entity, anim: SpawnAnim) {
+    commands
+        .entity(hourglass)
+        .insert(HourglassSpawnAnimation::disappear(anim));
+}
+
+/// System that plays spawn and despawn animations
+pub fn animate_hourglass_spawns(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: Option<ResMut<Assets<ColorMaterial>>>,
+    mut hourglasses: Query<(
+        Entity,
+        &mut HourglassSpawnAnimation,
+        &mut Transform,
+        Option<&HourglassParts>,
+    )>,
+    part_materials: Query<&MeshMaterial2d<ColorMaterial>>,
+) {
+    for (entity, mut animation, mut transform, parts) in hourglasses.iter_mut() {
+        animation.elapsed += time.delta_secs();
+        let shown = animation.shown();
+
+        match animation.anim {
+            SpawnAnim::ScaleIn { .. } => {
+                let base_scale = *animation.base_scale.get_or_insert(transform.scale);
+                // Avoid a degenerate transform at zero scale
+                transform.scale = base_scale * shown.max(0.001);
+            }
+            SpawnAnim::DropIn { height, .. } => {
+                // Offsets are applied relatively, so the hourglass can still be moved meanwhile
+                let offset = height * (1.0 - shown);
+                transform.translation.y += offset - animation.drop_offset;
+                animation.drop_offset = offset;
+            }
+            SpawnAnim::FadeIn { .. } => {
+                let Some(materials) = materials.as_deref_mut() else {
+                    continue;
+                };
+                if animation.materials.is_none() {
+                    unshare_materials(
+                        &mut commands,
+                        materials,
+                        parts,
+                        &part_materials,
+                        &mut animation,
+                    );
+                }
+                for (handle, alpha) in animation.materials.iter().flatten() {
+                    if let Some(material) = materials.get_mut(handle) {
+                        material.color.set_alpha(alpha * shown);
+                    }
+                }
+            }
+        }
+
+        if animation.is_finished() {
+            if animation.despawn {
+                commands.entity(entity).despawn();
+            } else {
+                for (part, handle) in animation.shared_materials.drain(..) {
+                    commands.entity(part).try_insert(MeshMaterial2d(handle));
+                }
+                commands.entity(entity).remove::<HourglassSpawnAnimation>();
+            }
+        }
+    }
+}
+
+/// Give the parts of the hourglass their own copies of their materials, so fading them doesn't
+/// affect other hourglasses sharing the same assets
+///
+/// The copies are recorded with their original alpha, along with the glass and plate materials
+/// they replaced, which are shared through the asset cache.
+fn unshare_materials(
+    commands: &mut Commands,
+    materials: &mut Assets<ColorMaterial>,
+    parts: Option<&HourglassParts>,
+    part_materials: &Query<&MeshMaterial2d<ColorMaterial>>,
+    animation: &mut HourglassSpawnAnimation,
+) {
+    let faded = animation.materials.get_or_insert_with(Vec::new);
+    let Some(parts) = parts else {
+        return;
+    };
+    let cached = [parts.body, parts.top_plate, parts.bottom_plate];
+    for part in parts.meshes() {
+        let Ok(original) = part_materials.get(part) else {
+            continue;
+        };
+        let Some(material) = materials.get(&original.0).cloned() else {
+            continue;
+        };
+        if cached.contains(&Some(part)) {
+            animation.shared_materials.push((part, original.0.clone()));
+        }
+        let alpha = material.color.alpha();
+        let handle = materials.add(material);
+        commands.entity(part).insert(MeshMaterial2d(handle.clone()));
+        faded.push((handle, alpha));
+    }
+}
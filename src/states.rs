@@ -0,0 +1,96 @@
+//! Tying hourglasses to Bevy `States`, e.g. only running while the game is being played.
+
+use crate::components::Hourglass;
+use crate::systems::update_hourglasses;
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Component that only lets an hourglass run while the app is in the given state
+///
+/// The hourglass is paused when the state is exited and picks up where it left off when the state
+/// is entered again. Requires [`HourglassStatePlugin`] for the state type.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassActiveInState<S: States> {
+    /// State in which the hourglass runs
+    pub state: S,
+    /// Whether the hourglass is also hidden outside of the state
+    pub hide_outside: bool,
+    /// Whether the hourglass is currently paused by this component
+    pub suspended: bool,
+    /// Whether the hourglass was running when it was paused
+    pub was_running: bool,
+    /// Whether the hourglass was flipping when it was paused
+    pub was_flipping: bool,
+}
+
+impl<S: States> HourglassActiveInState<S> {
+    /// Only run the hourglass in the given state
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            hide_outside: false,
+            suspended: false,
+            was_running: false,
+            was_flipping: false,
+        }
+    }
+
+    /// Set whether the hourglass is hidden outside of the state
+    pub fn with_hide_outside(mut self, hide_outside: bool) -> Self {
+        self.hide_outside = hide_outside;
+        self
+    }
+}
+
+/// Plugin pausing and resuming hourglasses scoped to states of type `S`
+///
+/// Must be added for every state type used with [`HourglassActiveInState`], after the state has
+/// been initialized with `init_state` or `insert_state`.
+pub struct HourglassStatePlugin<S: States>(PhantomData<S>);
+
+impl<S: States> Default for HourglassStatePlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: States> Plugin for HourglassStatePlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            sync_hourglass_active_states::<S>
+                .before(update_hourglasses)
+                .run_if(resource_exists::<State<S>>),
+        );
+    }
+}
+
+/// System that pauses hourglasses outside of their state and resumes them on re-entry
+pub fn sync_hourglass_active_states<S: States>(
+    mut commands: Commands,
+    state: Res<State<S>>,
+    mut hourglasses: Query<(Entity, &mut Hourglass, &mut HourglassActiveInState<S>)>,
+) {
+    for (entity, mut hourglass, mut scope) in hourglasses.iter_mut() {
+        let active = *state.get() == scope.state;
+
+        if !active && !scope.suspended {
+            // Flips are frozen too, as finishing a flip restarts the timer
+            scope.suspended = true;
+            scope.was_running = hourglass.running;
+            scope.was_flipping = hourglass.flipping;
+            hourglass.running = false;
+            hourglass.flipping = false;
+            if scope.hide_outside {
+                commands.entity(entity).insert(Visibility::Hidden);
+            }
+        } else if active && scope.suspended {
+            scope.suspended = false;
+            hourglass.running |= scope.was_running;
+            hourglass.flipping |= scope.was_flipping;
+            if scope.hide_outside {
+                commands.entity(entity).insert(Visibility::Inherited);
+            }
+        }
+    }
+}
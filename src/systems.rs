@@ -1,81 +1,259 @@
 //! Systems for updating hourglass state.
 
-use crate::components::{Hourglass, SandSplash, SandSplashParticle};
-use crate::events::{HourglassEmptyEvent, HourglassFlipStartEvent};
-use crate::{HourglassMeshSandState, SandSplashConfig};
+use crate::components::{
+    ContinuousRotation, DustMote, DustMotes, Hourglass, HourglassEmpty, NeckSparkle,
+    NeckSparkleConfig, NeckSparkleParticle, SandImpactPoint, SandOverflow, SandSplash,
+    SandSplashParticle, SandSplashRng, SwingMount,
+};
+use crate::culling::HourglassCulled;
+use crate::events::{
+    HourglassEmptyEvent, HourglassFlipStartEvent, HourglassSpeedChangeEvent,
+    HourglassTimeAdjustedEvent, HourglassTimeOverflowEvent,
+};
+use crate::quality::QualityTier;
+use crate::resources::{
+    HourglassAccessibility, HourglassConfig, HourglassDiagnostics, HourglassGlobalState,
+    HourglassPowerSaving, SplashOverflowPolicy,
+};
+use crate::simulation::{step_hourglass, HourglassSimEvent};
+use crate::time_zone::HourglassInTimeZone;
+use crate::tipping::HourglassTipDetection;
+use crate::{
+    HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshSandState, HourglassMeshShape,
+    HourglassParts, SandSplashConfig,
+};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::sprite::AlphaMode2d;
+use hourglass_geometry::{
+    generate_sand_outline_with_params, point_in_polygon, polygon_area, sand_fill_line,
+    HourglassShapeBuilder, Point2D, SandBulb, SandOutlineParams,
+};
 use rand::prelude::*;
+use std::collections::HashMap;
+
+/// Type alias for the hourglass update query to reduce type complexity
+type HourglassUpdateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Hourglass,
+        Option<&'static mut Transform>,
+        Option<&'static HourglassInTimeZone>,
+        Option<&'static mut HourglassTipDetection>,
+    ),
+>;
 
 /// System that updates all hourglasses
+///
+/// Every [`Hourglass`] goes through this system, whether or not it has a visual, so events are
+/// emitted consistently. The flip rotation is only applied when the entity has a [`Transform`],
+/// on top of the rotation given from outside to hourglasses with [`HourglassTipDetection`].
+/// Hourglasses inside time zones run at the zones' speed.
 pub fn update_hourglasses(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Hourglass, &mut Transform)>,
+    global: Res<HourglassGlobalState>,
+    mut query: HourglassUpdateQuery,
     mut empty_events: EventWriter<HourglassEmptyEvent>,
     mut flip_start_events: EventWriter<HourglassFlipStartEvent>,
+    mut time_adjusted_events: EventWriter<HourglassTimeAdjustedEvent>,
+    mut time_overflow_events: EventWriter<HourglassTimeOverflowEvent>,
 ) {
-    let delta = time.delta_secs();
+    let delta = time.delta_secs() * global.time_scale();
 
-    for (entity, mut hourglass, mut transform) in query.iter_mut() {
-        // Check if the hourglass was running and had time remaining before the update
-        let was_running = hourglass.running && hourglass.remaining_time > 0.0;
+    for (entity, mut hourglass, transform, in_zone, tip_detection) in query.iter_mut() {
+        let delta = delta * in_zone.map_or(1.0, |in_zone| in_zone.speed.max(0.0));
+        for event in step_hourglass(&mut hourglass, delta) {
+            match event {
+                HourglassSimEvent::FlipStart => {
+                    flip_start_events.write(HourglassFlipStartEvent { entity });
+                }
+                HourglassSimEvent::Empty { total_time } => {
+                    empty_events.write(HourglassEmptyEvent { entity, total_time });
+                }
+                HourglassSimEvent::TimeAdjusted { seconds } => {
+                    time_adjusted_events.write(HourglassTimeAdjustedEvent {
+                        entity,
+                        seconds,
+                        remaining_time: hourglass.remaining_time,
+                    });
+                }
+                HourglassSimEvent::TimeOverflow { seconds } => {
+                    time_overflow_events.write(HourglassTimeOverflowEvent { entity, seconds });
+                }
+            }
+        }
 
-        // Handle flip events if the hourglass is starting to flip
-        if hourglass.flipping {
-            // Send flip start event
-            flip_start_events.write(HourglassFlipStartEvent { entity });
+        // Apply the rotation to the transform, on top of the tilt of hourglasses that can be
+        // knocked over
+        if let Some(mut transform) = transform {
+            let rotation = hourglass.base_rotation + hourglass.current_rotation;
+            match tip_detection {
+                Some(mut tip_detection) => tip_detection.apply_rotation(&mut transform, rotation),
+                None => transform.rotation = Quat::from_rotation_z(rotation),
+            }
         }
+    }
+}
 
-        // Normal update
-        hourglass.update(delta);
+/// System marking hourglasses with an empty upper chamber with [`HourglassEmpty`]
+pub fn sync_hourglass_empty_markers(
+    mut commands: Commands,
+    query: Query<(Entity, &Hourglass, Has<HourglassEmpty>), Changed<Hourglass>>,
+) {
+    for (entity, hourglass, marked) in query.iter() {
+        let empty = hourglass.upper_chamber <= 0.0;
+        if empty && !marked {
+            commands.entity(entity).insert(HourglassEmpty);
+        } else if !empty && marked {
+            commands.entity(entity).remove::<HourglassEmpty>();
+        }
+    }
+}
 
-        // Apply the rotation to the transform
-        transform.rotation = Quat::from_rotation_z(hourglass.current_rotation);
+/// System that sends an event whenever the speed of an hourglass changes
+///
+/// The speed an hourglass was spawned with does not count as a change.
+pub fn detect_hourglass_speed_changes(
+    mut known_speeds: Local<HashMap<Entity, f32>>,
+    query: Query<(Entity, &Hourglass), Changed<Hourglass>>,
+    mut removed: RemovedComponents<Hourglass>,
+    mut speed_change_events: EventWriter<HourglassSpeedChangeEvent>,
+) {
+    for entity in removed.read() {
+        known_speeds.remove(&entity);
+    }
 
-        // Check if the hourglass just became empty
-        if was_running && hourglass.remaining_time == 0.0 {
-            empty_events.write(HourglassEmptyEvent {
-                entity,
-                total_time: hourglass.total_time,
-            });
+    for (entity, hourglass) in query.iter() {
+        let speed = hourglass.speed;
+        if let Some(previous_speed) = known_speeds.insert(entity, speed) {
+            if previous_speed != speed {
+                speed_change_events.write(HourglassSpeedChangeEvent {
+                    entity,
+                    previous_speed,
+                    speed,
+                });
+            }
         }
     }
 }
 
-/// System that handles sand splash animation for mesh hourglasses
+/// System that advances the base rotation of continuously rotating hourglasses
+pub fn apply_continuous_rotation(
+    time: Res<Time>,
+    global: Res<HourglassGlobalState>,
+    mut query: Query<(&ContinuousRotation, &mut Hourglass)>,
+) {
+    let delta = time.delta_secs() * global.time_scale();
+
+    for (rotation, mut hourglass) in query.iter_mut() {
+        hourglass.base_rotation = (hourglass.base_rotation + rotation.angular_velocity * delta)
+            .rem_euclid(std::f32::consts::TAU);
+    }
+}
+
+/// System that swings hourglasses with a [`SwingMount`] around their pivot
+pub fn apply_swing_mounts(
+    time: Res<Time>,
+    global: Res<HourglassGlobalState>,
+    mut query: Query<(&mut SwingMount, &mut Hourglass, Option<&mut Transform>)>,
+) {
+    let delta = time.delta_secs() * global.time_scale();
+
+    for (mut swing, mut hourglass, transform) in query.iter_mut() {
+        swing.elapsed += delta;
+        let angle =
+            swing.amplitude * (std::f32::consts::TAU * swing.frequency * swing.elapsed).sin();
+        let previous = swing.angle;
+        swing.angle = angle;
+
+        // Turning around the pivot above also moves the hourglass sideways and up
+        hourglass.base_rotation += angle - previous;
+        if let Some(mut transform) = transform {
+            transform.translation +=
+                (swing.offset_at(angle) - swing.offset_at(previous)).extend(0.0);
+        }
+    }
+}
+
+/// Type alias for the sand splash query to reduce type complexity
+type SandSplashQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Hourglass,
+        &'static SandImpactPoint,
+        &'static mut SandSplash,
+        Option<&'static mut SandSplashRng>,
+        Has<HourglassCulled>,
+        Option<&'static QualityTier>,
+        Option<&'static GlobalTransform>,
+    ),
+>;
+
+/// Live splash particles of all hourglasses, along with the global cap on their number
+#[derive(SystemParam)]
+pub struct SplashParticles<'w, 's> {
+    config: Res<'w, HourglassConfig>,
+    power_saving: Res<'w, HourglassPowerSaving>,
+    diagnostics: ResMut<'w, HourglassDiagnostics>,
+    particles: Query<'w, 's, (Entity, &'static mut SandSplashParticle)>,
+}
+
+/// System that handles sand splash animation at the [`SandImpactPoint`] of hourglasses
 pub fn update_sand_splash(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
-    mut hourglass_query: Query<(
-        &Hourglass,
-        &HourglassMeshSandState,
-        &mut SandSplash,
-        &GlobalTransform,
-    )>,
-    mut particle_query: Query<(Entity, &mut SandSplashParticle)>,
+    accessibility: Res<HourglassAccessibility>,
+    mut hourglass_query: SandSplashQuery,
+    mut splash_particles: SplashParticles,
 ) {
+    let _span = info_span!(
+        "hourglass_sand_splash",
+        hourglasses = hourglass_query.iter().len(),
+        particles = splash_particles.particles.iter().len()
+    )
+    .entered();
     let delta = time.delta_secs();
+    let mut thread_rng = rand::rng();
 
-    // Update existing splash particles
-    for (entity, mut particle) in particle_query.iter_mut() {
+    // Update existing splash particles, keeping the live ones closest to expiring first so they
+    // are recycled first
+    let mut live = Vec::new();
+    for (entity, mut particle) in splash_particles.particles.iter_mut() {
         particle.lifetime -= delta;
         if particle.lifetime <= 0.0 {
             commands.entity(entity).despawn();
+        } else {
+            live.push((entity, particle.lifetime));
         }
     }
+    let mut budget = SplashBudget::new(live, &splash_particles.config);
 
     // Process hourglasses with sand splash
-    for (hourglass, sand_state, mut sand_splash, global_transform) in hourglass_query.iter_mut() {
-        let is_currently_flowing =
-            hourglass.running && hourglass.upper_chamber > 0.0 && !hourglass.flipping;
+    for (hourglass, impact_point, mut sand_splash, mut splash_rng, is_culled, quality, transform) in
+        hourglass_query.iter_mut()
+    {
+        let impact_point = impact_point
+            .0
+            .filter(|_| hourglass.running && !hourglass.flipping);
+        let is_currently_flowing = impact_point.is_some();
+
+        // Nobody would see splashes of hidden hourglasses
+        if accessibility.reduced_motion || splash_particles.power_saving.enabled || is_culled {
+            sand_splash.was_flowing = is_currently_flowing;
+            continue;
+        }
 
         // Update spawn timer
         sand_splash.spawn_timer -= delta;
 
         // Check if sand is actively flowing and hitting the bottom
-        if is_currently_flowing && sand_splash.spawn_timer <= 0.0 && hourglass.lower_chamber > 0.01
+        if let Some(impact_point) = impact_point
+            .filter(|_| sand_splash.spawn_timer <= 0.0 && hourglass.lower_chamber > 0.01)
         {
             // Reset spawn timer
             sand_splash.spawn_timer = sand_splash.config.spawn_interval;
@@ -93,28 +271,11 @@ pub fn update_sand_splash(
                 0.05
             };
 
-            // Calculate the impact point based on sand level in bottom bulb
-            let hourglass_pos = global_transform.translation();
-
-            // Calculate the actual sand surface position in the bottom bulb
-            let total_height = sand_state.body_config.total_height;
-            let half_height = total_height / 2.0;
-            let neck_height = sand_state.body_config.neck_style.height();
-            let neck_bottom = -neck_height / 2.0;
-
-            // Bottom sand fill line calculation (from curves.rs logic)
-            // When fill_percent = 0.0 (empty top), bottom is full (at neck_bottom)
-            // When fill_percent = 1.0 (full top), bottom is empty (at min_y)
-            let min_y = -half_height;
-            let bottom_fill_line =
-                min_y + ((1.0 - sand_state.fill_percent) * (neck_bottom - min_y));
-
-            // Apply to global position
-            let impact_y = hourglass_pos.y + bottom_fill_line + sand_splash.config.vertical_offset;
-
-            // Scale particle count based on remaining sand
+            // Scale particle count based on remaining sand and the quality tier
+            let quality = quality.copied().unwrap_or(splash_particles.config.quality);
             let scaled_particle_count =
-                (sand_splash.config.particle_count as f32 * scale_factor).round() as u32;
+                (sand_splash.config.particle_count as f32 * scale_factor * quality.splash_scale())
+                    .round() as u32;
 
             // Create scaled config for this spawn
             let scaled_config = SandSplashConfig {
@@ -126,44 +287,154 @@ pub fn update_sand_splash(
                 particle_color: sand_splash.config.particle_color,
                 particle_size: sand_splash.config.particle_size * (0.5 + scale_factor * 0.5), // Minimum 50% size
                 vertical_offset: sand_splash.config.vertical_offset,
+                size_units: sand_splash.config.size_units,
             };
+            let size_scale = transform.map_or(1.0, |transform| {
+                sand_splash.config.size_units.scale(transform)
+            });
 
-            // Spawn splash particles with scaled parameters
+            // Spawn splash particles with scaled parameters, reproducibly if the hourglass is seeded
+            let rng: &mut dyn RngCore = match splash_rng.as_mut() {
+                Some(splash_rng) => &mut splash_rng.0,
+                None => &mut thread_rng,
+            };
             for _ in 0..scaled_particle_count {
-                spawn_splash_particle(
+                if !budget.reserve(&mut commands, &mut splash_particles.diagnostics) {
+                    continue;
+                }
+                let particle = spawn_splash_particle(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    Vec3::new(hourglass_pos.x, impact_y, hourglass_pos.z + 0.2),
+                    impact_point + Vec3::new(0.0, 0.0, 0.2),
                     &scaled_config,
+                    size_scale,
+                    rng,
                 );
+                budget.push(particle, scaled_config.particle_duration);
             }
         }
 
         sand_splash.was_flowing = is_currently_flowing;
     }
+
+    splash_particles.diagnostics.splash_particles = budget.live_count;
+}
+
+/// Room left under [`HourglassConfig::max_splash_particles`] for the particles spawned in a frame
+struct SplashBudget {
+    /// Live particles, the ones closest to expiring first so they are recycled first
+    live: Vec<(Entity, f32)>,
+    live_count: usize,
+    recycled: usize,
+    max_particles: usize,
+    overflow: SplashOverflowPolicy,
+}
+
+impl SplashBudget {
+    fn new(mut live: Vec<(Entity, f32)>, config: &HourglassConfig) -> Self {
+        live.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Self {
+            live_count: live.len(),
+            live,
+            recycled: 0,
+            max_particles: config.max_splash_particles,
+            overflow: config.splash_overflow,
+        }
+    }
+
+    /// Makes room for a new particle, recycling the oldest one if the overflow policy allows it,
+    /// and returns whether the particle can be spawned
+    fn reserve(&mut self, commands: &mut Commands, diagnostics: &mut HourglassDiagnostics) -> bool {
+        if self.live_count < self.max_particles {
+            return true;
+        }
+        match (self.overflow, self.live.get(self.recycled)) {
+            (SplashOverflowPolicy::RecycleOldest, Some((oldest, _))) => {
+                commands.entity(*oldest).despawn();
+                self.recycled += 1;
+                self.live_count -= 1;
+                true
+            }
+            _ => {
+                diagnostics.dropped_splash_particles += 1;
+                false
+            }
+        }
+    }
+
+    /// Records a spawned particle, the youngest so it is recycled last
+    fn push(&mut self, particle: Entity, lifetime: f32) {
+        self.live.push((particle, lifetime));
+        self.live_count += 1;
+    }
 }
 
-/// Spawns a single sand splash particle at the given position
+/// System placing the [`SandImpactPoint`] of mesh hourglasses on the sand in their bottom bulb
+pub fn update_mesh_sand_impact_points(
+    mut query: Query<(
+        &HourglassMeshSandState,
+        &SandSplash,
+        &GlobalTransform,
+        &mut SandImpactPoint,
+    )>,
+) {
+    for (sand_state, sand_splash, global_transform, mut impact_point) in query.iter_mut() {
+        let position = (sand_state.fill_percent > 0.0)
+            .then(|| splash_impact_point(global_transform, sand_state, &sand_splash.config));
+        impact_point.set_if_neq(SandImpactPoint(position));
+    }
+}
+
+/// Calculates the world position where falling sand hits the sand in the bottom bulb
+///
+/// The sand falls from the neck along gravity, and the hourglass' global transform is applied so
+/// the splash follows it when rotated, scaled or parented.
+pub fn splash_impact_point(
+    global_transform: &GlobalTransform,
+    sand_state: &HourglassMeshSandState,
+    config: &SandSplashConfig,
+) -> Vec3 {
+    // Calculate the actual sand surface position in the bottom bulb
+    let half_height = sand_state.body_config.total_height / 2.0;
+    let bottom_fill_line = sand_fill_line(
+        sand_state.fill_percent,
+        SandBulb::Bottom,
+        sand_state.body_config.neck_style.height(),
+        -half_height,
+        half_height,
+    );
+
+    // Fall from the neck along gravity in the hourglass' local frame
+    let tilt = sand_state.tilt;
+    let down = Vec2::new(-tilt.sin(), -tilt.cos());
+    let local = sand_state.center_offset + down * -(bottom_fill_line + config.vertical_offset);
+
+    // Apply to global position
+    global_transform.transform_point(local.extend(0.0))
+}
+
+/// Spawns a single sand splash particle at the given position, with its sizes multiplied by
+/// `size_scale`
 fn spawn_splash_particle(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     impact_position: Vec3,
     config: &crate::components::SandSplashConfig,
-) {
-    let mut rng = rand::rng();
-
+    size_scale: f32,
+    rng: &mut dyn RngCore,
+) -> Entity {
     // Random offset within splash radius
     let angle = rng.random::<f32>() * 2.0 * std::f32::consts::PI;
     let distance = rng.random::<f32>() * config.splash_radius;
     let offset_x = angle.cos() * distance;
     let offset_y = rng.random::<f32>() * 10.0 - 5.0; // Small vertical variation
 
-    let particle_position = impact_position + Vec3::new(offset_x, offset_y, 0.0);
+    let particle_position = impact_position + Vec3::new(offset_x, offset_y, 0.0) * size_scale;
 
     // Create a simple rectangle mesh for the particle
-    let size = config.particle_size;
+    let size = config.particle_size * size_scale;
     let mesh = meshes.add(Rectangle::new(size, size));
     let material = materials.add(ColorMaterial {
         color: config.particle_color,
@@ -171,12 +442,405 @@ fn spawn_splash_particle(
         ..default()
     });
 
-    commands.spawn((
-        SandSplashParticle {
-            lifetime: config.particle_duration,
-        },
-        Mesh2d(mesh),
-        MeshMaterial2d(material),
-        Transform::from_translation(particle_position),
-    ));
+    commands
+        .spawn((
+            SandSplashParticle {
+                lifetime: config.particle_duration,
+            },
+            Mesh2d(mesh),
+            MeshMaterial2d(material),
+            Transform::from_translation(particle_position),
+        ))
+        .id()
+}
+
+/// Type alias for the neck sparkle query to reduce type complexity
+type NeckSparkleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Hourglass,
+        &'static HourglassMeshSandState,
+        &'static mut NeckSparkle,
+        Option<&'static mut SandSplashRng>,
+        Has<HourglassCulled>,
+        &'static GlobalTransform,
+    ),
+>;
+
+/// System that spawns twinkling sparkles at the neck where the sand accelerates
+pub fn update_neck_sparkle(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+    accessibility: Res<HourglassAccessibility>,
+    mut hourglass_query: NeckSparkleQuery,
+    mut splash_particles: SplashParticles,
+) {
+    // Sparkles share the splash particle budget, so they are gated the same way
+    if accessibility.reduced_motion || splash_particles.power_saving.enabled {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    let mut thread_rng = rand::rng();
+    let live = splash_particles
+        .particles
+        .iter()
+        .filter(|(_, particle)| particle.lifetime > 0.0)
+        .map(|(entity, particle)| (entity, particle.lifetime))
+        .collect();
+    let mut budget = SplashBudget::new(live, &splash_particles.config);
+
+    for (hourglass, sand_state, mut sparkle, mut splash_rng, is_culled, global_transform) in
+        hourglass_query.iter_mut()
+    {
+        if is_culled {
+            continue;
+        }
+
+        let is_currently_flowing =
+            hourglass.running && sand_state.fill_percent > 0.0 && !hourglass.flipping;
+
+        sparkle.spawn_timer -= delta;
+
+        if is_currently_flowing && sparkle.spawn_timer <= 0.0 {
+            sparkle.spawn_timer = sparkle.config.spawn_interval;
+
+            let rng: &mut dyn RngCore = match splash_rng.as_mut() {
+                Some(splash_rng) => &mut splash_rng.0,
+                None => &mut thread_rng,
+            };
+            for _ in 0..sparkle.config.particle_count {
+                if !budget.reserve(&mut commands, &mut splash_particles.diagnostics) {
+                    continue;
+                }
+                let particle = spawn_sparkle_particle(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    global_transform,
+                    sand_state,
+                    &sparkle.config,
+                    rng,
+                );
+                budget.push(particle, sparkle.config.particle_duration);
+            }
+        }
+    }
+
+    splash_particles.diagnostics.splash_particles = budget.live_count;
+}
+
+/// Spawns a single sparkle just below the neck of the hourglass
+fn spawn_sparkle_particle(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    global_transform: &GlobalTransform,
+    sand_state: &HourglassMeshSandState,
+    config: &NeckSparkleConfig,
+    rng: &mut dyn RngCore,
+) -> Entity {
+    // Keep sparkles within the opening of the neck
+    let wall_offset = sand_state.sand_config.wall_offset;
+    let neck_half_width = (sand_state
+        .body_config
+        .neck_style
+        .width_with_wall_offset(wall_offset)
+        / 2.0
+        - wall_offset)
+        .max(0.5);
+
+    // Sparkles follow the falling sand, which always falls along gravity
+    let tilt = sand_state.tilt;
+    let down = Vec2::new(-tilt.sin(), -tilt.cos());
+    let across = Vec2::new(-down.y, down.x);
+    let local = sand_state.center_offset
+        + down * rng.random::<f32>() * config.spread
+        + across * (rng.random::<f32>() * 2.0 - 1.0) * neck_half_width;
+    let position = global_transform.transform_point(local.extend(0.3));
+
+    let size = config.particle_size * config.size_units.scale(global_transform);
+    let mesh = meshes.add(Rectangle::new(size, size));
+    let material = materials.add(ColorMaterial {
+        color: config.particle_color.with_alpha(0.0),
+        alpha_mode: AlphaMode2d::Blend,
+        ..default()
+    });
+
+    commands
+        .spawn((
+            SandSplashParticle {
+                lifetime: config.particle_duration,
+            },
+            NeckSparkleParticle {
+                duration: config.particle_duration,
+                color: config.particle_color,
+            },
+            Mesh2d(mesh),
+            MeshMaterial2d(material),
+            Transform::from_translation(position),
+        ))
+        .id()
+}
+
+/// System that makes neck sparkles twinkle by fading them in and out
+pub fn animate_neck_sparkles(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    particle_query: Query<(
+        &SandSplashParticle,
+        &NeckSparkleParticle,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+) {
+    for (particle, sparkle, material_handle) in particle_query.iter() {
+        let progress = 1.0 - (particle.lifetime / sparkle.duration.max(f32::EPSILON));
+        let alpha = (progress.clamp(0.0, 1.0) * std::f32::consts::PI).sin();
+
+        if let Some(material) = materials.get_mut(&material_handle.0) {
+            material.color = sparkle.color.with_alpha(alpha);
+        }
+    }
+}
+
+/// Glass and sand outlines that dust motes drift between, cached per dust motes entity
+pub struct DustMoteSpace {
+    body_config: HourglassMeshBodyConfig,
+    sand_params: SandOutlineParams,
+    glass: Vec<Point2D>,
+    sand: Vec<Vec<Point2D>>,
+    empty_area: f32,
+    half_width: f32,
+}
+
+impl DustMoteSpace {
+    fn new(body_config: &HourglassMeshBodyConfig, sand_params: SandOutlineParams) -> Self {
+        let shape_builder = HourglassShapeBuilder {
+            total_height: body_config.total_height,
+            bulb_style: body_config.bulb_style.clone(),
+            neck_style: body_config.neck_style.clone(),
+        };
+        let glass = shape_builder.generate_outline_with_wall_offset(sand_params.wall_offset);
+        let sand: Vec<Vec<Point2D>> = [SandBulb::Top, SandBulb::Bottom]
+            .into_iter()
+            .map(|bulb| {
+                // Sand spanning the whole glass, so motes stay above the surfaces rather
+                // than in the gap along the walls
+                let params = SandOutlineParams {
+                    bulb,
+                    solid_stream: false,
+                    wall_offset: 0.0,
+                    ..sand_params
+                };
+                generate_sand_outline_with_params(&glass, &params)
+            })
+            .collect();
+        let empty_area = (polygon_area(&glass)
+            - sand
+                .iter()
+                .map(|polygon| polygon_area(polygon))
+                .sum::<f32>())
+        .max(0.0);
+        let half_width = glass.iter().map(|p| p[0].abs()).fold(0.0, f32::max);
+
+        Self {
+            body_config: body_config.clone(),
+            sand_params,
+            glass,
+            sand,
+            empty_area,
+            half_width,
+        }
+    }
+}
+
+/// System that lets dust motes drift through the empty parts of the bulbs
+///
+/// The number of motes follows the amount of empty glass, and motes swallowed by the sand
+/// reappear elsewhere. Motes are hidden with reduced motion. The outlines are only rebuilt
+/// when the sand or glass changes, and empty meshes are left alone.
+pub fn animate_dust_motes(
+    time: Res<Time>,
+    accessibility: Res<HourglassAccessibility>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    hourglass_query: Query<(&HourglassMeshSandState, &HourglassParts)>,
+    mut motes_query: Query<(&mut DustMotes, &Mesh2d)>,
+    mut spaces: Local<HashMap<Entity, DustMoteSpace>>,
+) {
+    let delta = time.delta_secs();
+    let mut rng = rand::rng();
+    spaces.retain(|entity, _| motes_query.contains(*entity));
+
+    for (sand_state, parts) in hourglass_query.iter() {
+        let Some((motes_entity, Ok((mut dust_motes, mesh_handle)))) = parts
+            .dust_motes
+            .map(|child| (child, motes_query.get_mut(child)))
+        else {
+            continue;
+        };
+        let dust_motes = &mut *dust_motes;
+
+        if accessibility.reduced_motion {
+            dust_motes.motes.clear();
+        } else {
+            let sand_params = sand_state.sand_outline_params(SandBulb::Top);
+            let space = spaces
+                .entry(motes_entity)
+                .or_insert_with(|| DustMoteSpace::new(&sand_state.body_config, sand_params));
+            if space.sand_params != sand_params || space.body_config != sand_state.body_config {
+                *space = DustMoteSpace::new(&sand_state.body_config, sand_params);
+            }
+            let DustMoteSpace {
+                glass,
+                sand,
+                empty_area,
+                half_width,
+                ..
+            } = &*space;
+            let is_empty_space = |position: Vec2| {
+                let point = [position.x, position.y];
+                point_in_polygon(point, glass)
+                    && !sand.iter().any(|polygon| point_in_polygon(point, polygon))
+            };
+
+            // Keep the number of motes in line with the empty glass
+            let target = (dust_motes.config.density * *empty_area / 10_000.0).round() as usize;
+            dust_motes.motes.truncate(target);
+
+            let half_width = *half_width;
+            let half_height = sand_state.body_config.total_height / 2.0;
+            let speed = dust_motes.config.speed;
+            let random_mote = |rng: &mut ThreadRng| {
+                // Rejection sampling, giving up on glass that is (almost) full
+                (0..20).find_map(|_| {
+                    let position = Vec2::new(
+                        rng.random_range(-half_width..=half_width),
+                        rng.random_range(-half_height..=half_height),
+                    );
+                    is_empty_space(position).then(|| DustMote {
+                        position,
+                        velocity: Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU))
+                            * speed,
+                    })
+                })
+            };
+
+            while dust_motes.motes.len() < target {
+                match random_mote(&mut rng) {
+                    Some(mote) => dust_motes.motes.push(mote),
+                    None => break,
+                }
+            }
+
+            let mut index = 0;
+            while index < dust_motes.motes.len() {
+                let mote = &mut dust_motes.motes[index];
+                // Wander slowly in a random walk at a steady speed
+                let turn = rng.random_range(-1.0..=1.0) * 2.0 * delta;
+                mote.velocity = Vec2::from_angle(turn).rotate(mote.velocity);
+                let next = mote.position + mote.velocity * delta;
+
+                if is_empty_space(next) {
+                    mote.position = next;
+                    index += 1;
+                } else if let Some(replacement) = random_mote(&mut rng) {
+                    dust_motes.motes[index] = replacement;
+                    index += 1;
+                } else {
+                    dust_motes.motes.swap_remove(index);
+                }
+            }
+        }
+
+        let centers: Vec<[f32; 2]> = dust_motes
+            .motes
+            .iter()
+            .map(|mote| [mote.position.x, mote.position.y])
+            .collect();
+        if centers.is_empty()
+            && meshes
+                .get(&mesh_handle.0)
+                .is_some_and(|mesh| mesh.count_vertices() == 0)
+        {
+            continue;
+        }
+        if let Some(mesh) = meshes.get_mut(&mesh_handle.0) {
+            *mesh = HourglassMeshBuilder::create_quads_mesh(&centers, dust_motes.config.size);
+        }
+    }
+}
+
+type SandOverflowQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut SandOverflow,
+        &'static mut Transform,
+        &'static mut Visibility,
+    ),
+    Without<Hourglass>,
+>;
+
+/// System that grows the sand pile around the bottom plate while the hourglass is empty
+///
+/// The pile rests on the outside of the bottom plate, or below the glass without plates, and
+/// shrinks away once sand flows again or the hourglass flips.
+pub fn update_sand_overflow(
+    time: Res<Time>,
+    hourglass_query: Query<(
+        &Hourglass,
+        &HourglassParts,
+        &HourglassMeshSandState,
+        Option<&HourglassMeshShape>,
+    )>,
+    plate_query: Query<&Transform, (Without<Hourglass>, Without<SandOverflow>)>,
+    mut overflow_query: SandOverflowQuery,
+) {
+    let delta = time.delta_secs();
+
+    for (hourglass, parts, sand_state, shape) in hourglass_query.iter() {
+        let Some(Ok((mut overflow, mut transform, mut visibility))) =
+            parts.overflow.map(|child| overflow_query.get_mut(child))
+        else {
+            continue;
+        };
+
+        let grow_rate = delta / overflow.config.grow_duration.max(f32::EPSILON);
+        overflow.growth = if hourglass.flipping {
+            0.0
+        } else if hourglass.upper_chamber <= 0.0 {
+            (overflow.growth + grow_rate).min(1.0)
+        } else {
+            // Clear the pile faster than it grew
+            (overflow.growth - 4.0 * grow_rate).max(0.0)
+        };
+
+        if overflow.growth <= 0.0 {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let plates = shape.and_then(|shape| shape.plates_config.as_ref());
+        let bottom_plate = parts
+            .bottom_plate
+            .and_then(|plate| plate_query.get(plate).ok());
+        let (base, width) = match (plates, bottom_plate) {
+            (Some(plates), Some(plate)) => {
+                (plate.translation.y - plates.height / 2.0, plates.width)
+            }
+            _ => (
+                sand_state.center_offset.y - sand_state.body_config.total_height / 2.0,
+                HourglassMeshBuilder::visual_size(&sand_state.body_config, None).x,
+            ),
+        };
+
+        // Ease out so the pile quickly appears and then settles
+        let size = 1.0 - (1.0 - overflow.growth).powi(2);
+        let width = width * overflow.config.overflow_factor * size;
+        transform.translation = Vec3::new(sand_state.center_offset.x, base, 0.1);
+        transform.scale = Vec3::new(width, width * 0.3 * size, 1.0);
+        *visibility = Visibility::Inherited;
+    }
 }
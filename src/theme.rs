@@ -0,0 +1,271 @@
+//! Color themes for hourglasses, including high contrast and color-blind safe palettes.
+
+use crate::components::Hourglass;
+use crate::mesh::{
+    HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassMeshSandConfig,
+    HourglassMeshShape, HourglassParts,
+};
+use crate::resources::HourglassConfig;
+use bevy::prelude::*;
+
+/// Colors used for the parts of an hourglass
+///
+/// Themes can enforce a minimum contrast between the sand, the glass, the plates and the
+/// background they are shown on. Use [`HourglassTheme::effective_colors`] to get the colors
+/// after that enforcement, or apply the theme with `HourglassMeshBuilder::with_theme`.
+#[derive(Debug, Clone)]
+pub struct HourglassTheme {
+    /// Color of the glass body
+    pub glass_color: Color,
+    /// Color of the sand
+    pub sand_color: Color,
+    /// Color of the top and bottom plates
+    pub plate_color: Color,
+    /// Color of whatever the hourglass is drawn on, used for contrast checks
+    pub background_hint: Option<Color>,
+    /// Minimum contrast ratio (1.0 - 21.0) between the sand and the glass and background, and
+    /// between the plates and the background
+    pub min_contrast: Option<f32>,
+    /// HDR brightness boost of the glass and sand, see [`crate::emissive_color`]
+    pub emissive: f32,
+}
+
+impl Default for HourglassTheme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+/// Colors of an hourglass after applying a theme's contrast requirements
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourglassThemeColors {
+    pub glass_color: Color,
+    pub sand_color: Color,
+    pub plate_color: Color,
+}
+
+impl HourglassTheme {
+    /// The default look: light blue glass, pale sand and wooden plates
+    pub fn classic() -> Self {
+        Self {
+            glass_color: HourglassMeshBodyConfig::default().color,
+            sand_color: HourglassMeshSandConfig::default().color,
+            plate_color: HourglassMeshPlatesConfig::default().color,
+            background_hint: None,
+            min_contrast: None,
+            emissive: 0.0,
+        }
+    }
+
+    /// Bright sand and plates on a dark background, readable at a glance
+    ///
+    /// Uses yellow and white which stay distinguishable under the common forms of color
+    /// blindness.
+    pub fn high_contrast() -> Self {
+        Self {
+            glass_color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+            sand_color: Color::srgb(1.0, 0.85, 0.0),
+            plate_color: Color::WHITE,
+            background_hint: Some(Color::BLACK),
+            min_contrast: Some(7.0),
+            emissive: 0.0,
+        }
+    }
+
+    /// Palette based on the Okabe-Ito colors, which are distinguishable for color-blind players
+    pub fn color_blind_safe() -> Self {
+        Self {
+            glass_color: Color::srgba(0.34, 0.71, 0.91, 0.25),
+            sand_color: Color::srgb(0.9, 0.62, 0.0),
+            plate_color: Color::srgb(0.0, 0.45, 0.7),
+            background_hint: None,
+            min_contrast: Some(3.0),
+            emissive: 0.0,
+        }
+    }
+
+    /// Sci-fi neon look with glowing cyan glass and magenta sand on a dark background
+    ///
+    /// Needs a camera with HDR and bloom enabled to actually glow.
+    pub fn neon() -> Self {
+        Self {
+            glass_color: Color::srgba(0.0, 0.9, 1.0, 0.25),
+            sand_color: Color::srgb(1.0, 0.1, 0.8),
+            plate_color: Color::srgb(0.0, 0.9, 1.0),
+            background_hint: Some(Color::srgb(0.02, 0.0, 0.06)),
+            min_contrast: None,
+            emissive: 2.0,
+        }
+    }
+
+    /// Set the HDR brightness boost of the glass and sand
+    pub fn with_emissive(mut self, emissive: f32) -> Self {
+        self.emissive = emissive;
+        self
+    }
+
+    /// Set the color of the background the hourglass is shown on
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background_hint = Some(background);
+        self
+    }
+
+    /// Set the minimum contrast ratio enforced between the parts
+    pub fn with_min_contrast(mut self, ratio: f32) -> Self {
+        self.min_contrast = Some(ratio);
+        self
+    }
+
+    /// Colors to render with, adjusted so the theme's minimum contrast is met where possible
+    pub fn effective_colors(&self) -> HourglassThemeColors {
+        let Some(min_contrast) = self.min_contrast else {
+            return HourglassThemeColors {
+                glass_color: self.glass_color,
+                sand_color: self.sand_color,
+                plate_color: self.plate_color,
+            };
+        };
+
+        // Translucent glass is seen on top of the background
+        let glass_color = match self.background_hint {
+            Some(background) => blend_over(self.glass_color, background),
+            None => self.glass_color.with_alpha(1.0),
+        };
+
+        let mut sand_against = vec![glass_color];
+        sand_against.extend(self.background_hint);
+        let sand_color = ensure_contrast(self.sand_color, &sand_against, min_contrast);
+
+        let plate_color = match self.background_hint {
+            Some(background) => ensure_contrast(self.plate_color, &[background], min_contrast),
+            None => self.plate_color,
+        };
+
+        HourglassThemeColors {
+            glass_color: self.glass_color,
+            sand_color,
+            plate_color,
+        }
+    }
+}
+
+/// WCAG relative luminance of a color, ignoring alpha
+pub fn relative_luminance(color: Color) -> f32 {
+    let linear = color.to_linear();
+    0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 (identical) to 21.0 (black on white)
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Lighten or darken a color until it reaches the minimum contrast against all given colors
+///
+/// Moves toward white or black, whichever gets closer to the requirement. Returns the best
+/// attempt if the requirement can't be met.
+pub fn ensure_contrast(color: Color, against: &[Color], min_contrast: f32) -> Color {
+    let worst_contrast = |candidate: Color| {
+        against
+            .iter()
+            .map(|other| contrast_ratio(candidate, *other))
+            .fold(f32::MAX, f32::min)
+    };
+
+    if against.is_empty() || worst_contrast(color) >= min_contrast {
+        return color;
+    }
+
+    let alpha = color.alpha();
+    let base = color.to_srgba();
+    let mix = |target: Srgba, amount: f32| -> Color {
+        Color::Srgba(base.mix(&target, amount).with_alpha(alpha))
+    };
+
+    let mut best = color;
+    for step in 1..=20 {
+        let amount = step as f32 / 20.0;
+        let lighter = mix(Srgba::WHITE, amount);
+        let darker = mix(Srgba::BLACK, amount);
+        let candidate = if worst_contrast(lighter) >= worst_contrast(darker) {
+            lighter
+        } else {
+            darker
+        };
+        if worst_contrast(candidate) > worst_contrast(best) {
+            best = candidate;
+        }
+        if worst_contrast(best) >= min_contrast {
+            break;
+        }
+    }
+    best
+}
+
+/// Color seen when a translucent color is drawn over an opaque background
+fn blend_over(color: Color, background: Color) -> Color {
+    let alpha = color.alpha();
+    Color::Srgba(
+        background
+            .to_srgba()
+            .with_alpha(1.0)
+            .mix(&color.to_srgba().with_alpha(1.0), alpha),
+    )
+}
+
+/// Marker for hourglasses re-themed with the colors of [`HourglassConfig`] when it changes
+///
+/// Only applies while [`HourglassConfig::watch`] is enabled.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct FollowGlobalTheme;
+
+type FollowGlobalThemeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Ref<'static, FollowGlobalTheme>,
+        &'static mut Hourglass,
+        Option<&'static HourglassParts>,
+        Option<&'static mut HourglassMeshShape>,
+    ),
+>;
+
+/// System applying the colors of a changed [`HourglassConfig`] to hourglasses following it
+///
+/// The sand follows the hourglass' sand color on its own. The glass gets a material of its own,
+/// since its previous one may be shared with other hourglasses.
+pub fn apply_global_hourglass_config(
+    config: Res<HourglassConfig>,
+    mut materials: Option<ResMut<Assets<ColorMaterial>>>,
+    mut hourglass_query: FollowGlobalThemeQuery,
+    mut body_query: Query<&mut MeshMaterial2d<ColorMaterial>>,
+) {
+    if !config.watch {
+        return;
+    }
+
+    for (follow, mut hourglass, parts, shape) in hourglass_query.iter_mut() {
+        if !config.is_changed() && !follow.is_added() {
+            continue;
+        }
+
+        hourglass.container_color = config.default_container_color;
+        hourglass.sand_color = config.default_sand_color;
+        if let Some(mut shape) = shape {
+            shape.body_config.color = config.default_container_color;
+        }
+
+        let body_material = parts
+            .and_then(|parts| parts.body)
+            .and_then(|body| body_query.get_mut(body).ok());
+        if let (Some(mut body_material), Some(materials)) = (body_material, materials.as_mut()) {
+            let current = materials
+                .get(&body_material.0)
+                .map(|material| material.color);
+            if current != Some(config.default_container_color) {
+                body_material.0 = materials.add(config.default_container_color);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Areas speeding up or slowing down the hourglasses inside them, e.g. for puzzles about moving
+//! timers around.
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+
+/// Area covered by an [`HourglassTimeZone`], in the zone entity's local space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZoneShape {
+    /// Circle around the zone's origin
+    Circle { radius: f32 },
+    /// Rectangle centered on the zone's origin
+    Rect { half_size: Vec2 },
+}
+
+impl TimeZoneShape {
+    /// Whether a point in the zone's local space is inside the area
+    pub fn contains(&self, point: Vec2) -> bool {
+        match *self {
+            TimeZoneShape::Circle { radius } => point.length_squared() <= radius * radius,
+            TimeZoneShape::Rect { half_size } => {
+                point.x.abs() <= half_size.x && point.y.abs() <= half_size.y
+            }
+        }
+    }
+}
+
+/// Component for an area scaling the speed of every hourglass whose center is inside it
+///
+/// The area follows the entity's [`GlobalTransform`], including its rotation and scale.
+/// Overlapping zones multiply their speeds.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassTimeZone {
+    /// Area of the zone
+    pub shape: TimeZoneShape,
+    /// Speed multiplier for hourglasses inside, e.g. 2.0 for twice as fast or 0.0 to freeze them
+    pub speed: f32,
+}
+
+impl HourglassTimeZone {
+    /// Circular zone of the given radius
+    pub fn circle(radius: f32, speed: f32) -> Self {
+        Self {
+            shape: TimeZoneShape::Circle { radius },
+            speed,
+        }
+    }
+
+    /// Rectangular zone of the given size
+    pub fn rect(size: Vec2, speed: f32) -> Self {
+        Self {
+            shape: TimeZoneShape::Rect {
+                half_size: size / 2.0,
+            },
+            speed,
+        }
+    }
+
+    /// Whether a world-space point is inside the zone placed at `transform`
+    pub fn contains(&self, transform: &GlobalTransform, point: Vec3) -> bool {
+        let local = transform.affine().inverse().transform_point3(point);
+        self.shape.contains(local.truncate())
+    }
+}
+
+/// Component added to hourglasses inside time zones, with the combined speed of the zones
+///
+/// It scales time on top of the hourglass' own speed and is removed once the hourglass leaves.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HourglassInTimeZone {
+    /// Product of the speeds of the zones the hourglass is in
+    pub speed: f32,
+}
+
+/// System finding the time zones every hourglass is in
+pub fn detect_hourglass_time_zones(
+    mut commands: Commands,
+    zones: Query<(&HourglassTimeZone, &GlobalTransform)>,
+    hourglasses: Query<(Entity, &GlobalTransform, Option<&HourglassInTimeZone>), With<Hourglass>>,
+) {
+    for (entity, transform, in_zone) in hourglasses.iter() {
+        let position = transform.translation();
+        let speed = zones
+            .iter()
+            .filter(|(zone, zone_transform)| zone.contains(zone_transform, position))
+            .map(|(zone, _)| zone.speed)
+            .reduce(|a, b| a * b);
+
+        match (speed, in_zone) {
+            (Some(speed), Some(in_zone)) if in_zone.speed == speed => {}
+            (Some(speed), _) => {
+                commands
+                    .entity(entity)
+                    .insert(HourglassInTimeZone { speed });
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<HourglassInTimeZone>();
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Pausing hourglasses lying on their side, e.g. after being knocked over by physics.
+
+use crate::components::Hourglass;
+use crate::events::HourglassTippedEvent;
+use bevy::prelude::*;
+
+/// Component stopping the sand of an hourglass while it lies on its side
+///
+/// The orientation is taken from the [`GlobalTransform`], so rotations of parents, the base
+/// rotation and physics engines all count. Rotations set on the hourglass' own [`Transform`] are
+/// kept, with the base rotation and flips applied on top. Flips are ignored even though they pass
+/// through the side. The timer is paused while tipped over and resumes once the hourglass stands
+/// again.
+#[derive(Component, Debug, Clone)]
+pub struct HourglassTipDetection {
+    /// Angle from horizontal (in radians) within which the hourglass counts as lying on its side
+    pub tolerance: f32,
+    /// Whether the hourglass currently lies on its side
+    pub tipped: bool,
+    /// Whether the timer was running when the hourglass tipped over
+    resume: bool,
+    /// Base and flip rotation last applied to the transform
+    applied_rotation: Option<f32>,
+}
+
+impl Default for HourglassTipDetection {
+    fn default() -> Self {
+        Self::new(20f32.to_radians())
+    }
+}
+
+impl HourglassTipDetection {
+    /// Detect hourglasses within the given angle from horizontal (in radians)
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance,
+            tipped: false,
+            resume: false,
+            applied_rotation: None,
+        }
+    }
+
+    /// Rotate the transform to the given base and flip rotation, keeping whatever rotation it was
+    /// given from outside, e.g. by a physics engine knocking the hourglass over
+    ///
+    /// The rotation found on the first call is assumed to already include the hourglass' own.
+    pub(crate) fn apply_rotation(&mut self, transform: &mut Transform, rotation: f32) {
+        let applied = self.applied_rotation.replace(rotation).unwrap_or(rotation);
+        transform.rotation *= Quat::from_rotation_z(rotation - applied);
+    }
+
+    /// Whether an hourglass with the given world-space rotation lies on its side
+    pub fn is_tipped_at(&self, rotation: Quat) -> bool {
+        let up = (rotation * Vec3::Y).truncate().normalize_or_zero();
+        up.y.abs() < self.tolerance.sin()
+    }
+}
+
+/// System pausing hourglasses that tipped over and resuming them once they stand again
+pub fn detect_tipped_hourglasses(
+    mut query: Query<(
+        Entity,
+        &mut Hourglass,
+        &mut HourglassTipDetection,
+        &GlobalTransform,
+    )>,
+    mut tipped_events: EventWriter<HourglassTippedEvent>,
+) {
+    for (entity, mut hourglass, mut detection, transform) in query.iter_mut() {
+        let tipped = !hourglass.flipping && detection.is_tipped_at(transform.rotation());
+
+        if tipped != detection.tipped {
+            detection.tipped = tipped;
+            if !tipped && detection.resume {
+                detection.resume = false;
+                hourglass.running = true;
+            }
+            tipped_events.write(HourglassTippedEvent { entity, tipped });
+        }
+
+        // Keep the timer paused, even when started while lying on its side
+        if tipped && hourglass.running {
+            detection.resume = true;
+            hourglass.running = false;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Lenses for animating hourglass properties with tweening libraries.
+//!
+//! The lenses can be applied directly with `apply`. With the `tweening` feature they also
+//! implement `bevy_tweening::Lens<Hourglass>`, so they can drive a `Tween` on the hourglass entity.
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+
+/// Lens animating the fill of the upper chamber (0.0 - 1.0)
+///
+/// Meant for [`HourglassValueSource::External`](crate::HourglassValueSource::External)
+/// hourglasses, as a running timer keeps flowing from the tweened level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourglassFillLens {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl HourglassFillLens {
+    /// Set the fill of the hourglass at `ratio` (0.0 - 1.0) between start and end
+    pub fn apply(&self, hourglass: &mut Hourglass, ratio: f32) {
+        hourglass.set_fill(self.start.lerp(self.end, ratio));
+    }
+}
+
+/// Lens animating the resting rotation of the hourglass (in radians)
+///
+/// Flips keep working while the rotation is tweened, as they are relative to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourglassRotationLens {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl HourglassRotationLens {
+    /// Set the base rotation of the hourglass at `ratio` (0.0 - 1.0) between start and end
+    pub fn apply(&self, hourglass: &mut Hourglass, ratio: f32) {
+        hourglass.base_rotation = self.start.lerp(self.end, ratio);
+    }
+}
+
+/// Lens animating the sand color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourglassSandColorLens {
+    pub start: Color,
+    pub end: Color,
+}
+
+impl HourglassSandColorLens {
+    /// Set the sand color of the hourglass at `ratio` (0.0 - 1.0) between start and end
+    pub fn apply(&self, hourglass: &mut Hourglass, ratio: f32) {
+        hourglass.sand_color = self.start.mix(&self.end, ratio);
+    }
+}
+
+#[cfg(feature = "tweening")]
+mod lens {
+    use super::*;
+    use bevy_tweening::{Lens, Targetable};
+
+    impl Lens<Hourglass> for HourglassFillLens {
+        fn lerp(&mut self, target: &mut dyn Targetable<Hourglass>, ratio: f32) {
+            self.apply(target.target_mut(), ratio);
+        }
+    }
+
+    impl Lens<Hourglass> for HourglassRotationLens {
+        fn lerp(&mut self, target: &mut dyn Targetable<Hourglass>, ratio: f32) {
+            self.apply(target.target_mut(), ratio);
+        }
+    }
+
+    impl Lens<Hourglass> for HourglassSandColorLens {
+        fn lerp(&mut self, target: &mut dyn Targetable<Hourglass>, ratio: f32) {
+            self.apply(target.target_mut(), ratio);
+        }
+    }
+}
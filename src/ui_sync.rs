@@ -0,0 +1,79 @@
+//! Keeps world-space hourglasses aligned with UI nodes.
+
+use crate::components::Hourglass;
+use bevy::prelude::*;
+
+/// Component that keeps a world-space hourglass positioned and scaled to fit a UI [`Node`]
+///
+/// The hourglass is centered on the node's computed layout rect and uniformly scaled so that
+/// its [`Hourglass::size`] fits inside it. The camera used to map the node into the world is, in
+/// order of preference: the hourglass' [`HourglassTargetCamera`], the node's [`UiTargetCamera`],
+/// the camera marked with [`IsDefaultUiCamera`], then the first active camera.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SyncToUiNode(pub Entity);
+
+/// Component selecting the camera (and therefore window) an hourglass belongs to
+///
+/// Needed in multi-window setups where the first active camera is not necessarily the one
+/// rendering the hourglass.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HourglassTargetCamera(pub Entity);
+
+/// System that moves and scales hourglasses to match the layout rect of their target UI node
+///
+/// Runs after transform propagation so it sees this frame's UI layout; the hourglass itself
+/// follows on the next frame.
+pub fn sync_hourglass_to_ui_node(
+    mut hourglasses: Query<(
+        &SyncToUiNode,
+        &Hourglass,
+        &mut Transform,
+        Option<&HourglassTargetCamera>,
+    )>,
+    nodes: Query<(&ComputedNode, &GlobalTransform, Option<&UiTargetCamera>)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, Has<IsDefaultUiCamera>)>,
+) {
+    for (sync, hourglass, mut transform, hourglass_camera) in hourglasses.iter_mut() {
+        let Ok((node, node_transform, target_camera)) = nodes.get(sync.0) else {
+            continue;
+        };
+        if node.is_empty() {
+            continue;
+        }
+
+        let camera = match (hourglass_camera, target_camera) {
+            (Some(HourglassTargetCamera(entity)), _) | (None, Some(UiTargetCamera(entity))) => {
+                cameras.get(*entity).ok()
+            }
+            (None, None) => cameras
+                .iter()
+                .find(|(_, camera, _, is_default)| camera.is_active && *is_default)
+                .or_else(|| cameras.iter().find(|(_, camera, _, _)| camera.is_active)),
+        };
+        let Some((_, camera, camera_transform, _)) = camera else {
+            continue;
+        };
+        let viewport_min = camera
+            .logical_viewport_rect()
+            .map(|rect| rect.min)
+            .unwrap_or_default();
+
+        // UI layout is in physical pixels relative to the viewport's top-left corner
+        let center = node_transform.translation().truncate() * node.inverse_scale_factor;
+        let half_size = node.size() * node.inverse_scale_factor / 2.0;
+        let (Ok(top_left), Ok(bottom_right)) = (
+            camera.viewport_to_world_2d(camera_transform, viewport_min + center - half_size),
+            camera.viewport_to_world_2d(camera_transform, viewport_min + center + half_size),
+        ) else {
+            continue;
+        };
+
+        let world_size = (bottom_right - top_left).abs();
+        let world_center = (top_left + bottom_right) / 2.0;
+        let scale = (world_size.x / hourglass.size.x).min(world_size.y / hourglass.size.y);
+
+        transform.translation.x = world_center.x;
+        transform.translation.y = world_center.y;
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
@@ -0,0 +1,89 @@
+//! Sizes given in world or local units, e.g. to keep strokes and particles readable on scaled
+//! hourglasses.
+
+use crate::mesh::{HourglassMeshBuilder, HourglassMeshShape, HourglassParts, RenderStyle};
+use bevy::prelude::*;
+
+/// Units in which a size is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SizeUnits {
+    /// Sizes in world units, the same whatever the scale of the hourglass
+    World,
+    /// Sizes relative to the hourglass, growing and shrinking with its scale
+    Local,
+}
+
+impl SizeUnits {
+    /// Factor applied to a size for an hourglass placed at `transform`
+    pub fn scale(self, transform: &GlobalTransform) -> f32 {
+        match self {
+            SizeUnits::World => 1.0,
+            SizeUnits::Local => world_scale(transform),
+        }
+    }
+}
+
+/// Average scale of the x and y axes of a transform
+pub fn world_scale(transform: &GlobalTransform) -> f32 {
+    let scale = transform.scale().truncate().abs();
+    (scale.x + scale.y) / 2.0
+}
+
+/// Component recording the world scale the outline stroke of an hourglass was built for
+///
+/// Added to [`RenderStyle::Minimal`] hourglasses with strokes in [`SizeUnits::World`]. Removing
+/// it rebuilds the stroke.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HourglassStrokeScale(pub f32);
+
+/// Relative change of scale below which world strokes are not rebuilt
+const STROKE_SCALE_TOLERANCE: f32 = 0.01;
+
+/// System rebuilding outline strokes given in world units whenever their hourglass is rescaled
+///
+/// Strokes are rebuilt once the scale changes by more than 1%. The first rebuild gives the body
+/// its own mesh instead of the shared one, later rebuilds update it in place.
+pub fn update_world_strokes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    query: Query<(
+        Entity,
+        &HourglassMeshShape,
+        &HourglassParts,
+        &GlobalTransform,
+        Option<&HourglassStrokeScale>,
+    )>,
+    body_meshes: Query<&Mesh2d>,
+) {
+    for (entity, shape, parts, transform, stroke_scale) in query.iter() {
+        let (RenderStyle::Minimal { stroke_width }, SizeUnits::World, Some(body)) = (
+            shape.render_style,
+            shape.body_config.stroke_units,
+            parts.body,
+        ) else {
+            continue;
+        };
+        let scale = world_scale(transform);
+        if scale <= f32::EPSILON
+            || stroke_scale
+                .is_some_and(|built| (built.0 - scale).abs() <= built.0 * STROKE_SCALE_TOLERANCE)
+        {
+            continue;
+        }
+
+        let render_style = RenderStyle::Minimal {
+            stroke_width: stroke_width / scale,
+        };
+        let mesh = HourglassMeshBuilder::create_body_mesh(&shape.body_config, render_style);
+        let own_mesh = stroke_scale
+            .and_then(|_| body_meshes.get(body).ok())
+            .and_then(|handle| meshes.get_mut(&handle.0));
+        match own_mesh {
+            Some(own_mesh) => *own_mesh = mesh,
+            None => {
+                commands.entity(body).insert(Mesh2d(meshes.add(mesh)));
+            }
+        }
+        commands.entity(entity).insert(HourglassStrokeScale(scale));
+    }
+}
@@ -0,0 +1,115 @@
+//! Warnings for recoverable issues, such as invalid configurations the plugin works around.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Global handling of [`HourglassWarning`]s
+///
+/// Every distinct warning is logged once. Changes are picked up at the start of the next frame,
+/// or right away when the resource is inserted before adding the plugin.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HourglassWarnings {
+    /// Panic instead of logging in debug builds, to catch misconfigured hourglasses early
+    pub panic_in_debug: bool,
+}
+
+impl HourglassWarnings {
+    /// Warnings panicking in debug builds
+    pub fn strict() -> Self {
+        Self {
+            panic_in_debug: true,
+        }
+    }
+}
+
+/// Issue the plugin recovered from, e.g. by clamping a value or skipping a part
+#[derive(Debug, Clone, PartialEq)]
+pub enum HourglassWarning {
+    /// A configuration value was out of its valid range and clamped into it
+    ClampedConfig {
+        field: &'static str,
+        value: f32,
+        clamped: f32,
+    },
+    /// Triangulating an outline failed, so nothing is drawn for it
+    TriangulationFailed { points: usize },
+    /// Sand was configured without a body to hold it, so it is not spawned
+    SandWithoutBody,
+}
+
+impl fmt::Display for HourglassWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HourglassWarning::ClampedConfig {
+                field,
+                value,
+                clamped,
+            } => write!(
+                f,
+                "Hourglass {field} of {value} is invalid, using {clamped}"
+            ),
+            HourglassWarning::TriangulationFailed { points } => write!(
+                f,
+                "Failed to triangulate an hourglass outline of {points} points, skipping its mesh"
+            ),
+            HourglassWarning::SandWithoutBody => {
+                write!(f, "Hourglass sand needs a body to fill, skipping the sand")
+            }
+        }
+    }
+}
+
+/// Whether warnings panic in debug builds, mirrored from [`HourglassWarnings`]
+static PANIC_IN_DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// Warnings logged so far
+static LOGGED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+/// Log a warning the first time it is reported, or panic if strict warnings are enabled in a
+/// debug build
+pub(crate) fn report_warning(warning: HourglassWarning) {
+    let message = warning.to_string();
+    if cfg!(debug_assertions) && PANIC_IN_DEBUG.load(Ordering::Relaxed) {
+        panic!("{message}");
+    }
+
+    let mut logged = LOGGED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if logged.insert(message.clone()) {
+        warn!("{message}");
+    }
+}
+
+/// Clamp a configuration value into `min..=max`, reporting values out of range
+///
+/// NaN values are replaced by `min`.
+pub(crate) fn clamp_config(field: &'static str, value: f32, min: f32, max: f32) -> f32 {
+    let clamped = if value.is_nan() {
+        min
+    } else {
+        value.clamp(min, max)
+    };
+    if clamped != value {
+        report_warning(HourglassWarning::ClampedConfig {
+            field,
+            value,
+            clamped,
+        });
+    }
+    clamped
+}
+
+/// Mirror the warning settings for code running outside of systems, such as builders
+pub(crate) fn apply_hourglass_warnings(warnings: &HourglassWarnings) {
+    PANIC_IN_DEBUG.store(warnings.panic_in_debug, Ordering::Relaxed);
+}
+
+/// System applying changes to [`HourglassWarnings`]
+pub fn sync_hourglass_warnings(warnings: Res<HourglassWarnings>) {
+    apply_hourglass_warnings(&warnings);
+}
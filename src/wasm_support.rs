@@ -0,0 +1,45 @@
+//! Window settings for running hourglasses in the browser.
+//!
+//! The settings only affect web builds, so the same plugin can be used on every platform:
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_hourglass::{wasm_support, HourglassPlugin};
+//!
+//! App::new()
+//!     .add_plugins((
+//!         DefaultPlugins.set(wasm_support::recommended_window_plugin()),
+//!         HourglassPlugin,
+//!     ))
+//!     .run();
+//! ```
+
+use bevy::prelude::*;
+
+/// Selector of the canvas used by [`recommended_window_plugin`]
+///
+/// Without a matching element, Bevy creates a canvas at the end of the page's body.
+pub const DEFAULT_CANVAS_SELECTOR: &str = "#bevy";
+
+/// Window plugin rendering into the `#bevy` canvas, see [`window_plugin_for_canvas`]
+pub fn recommended_window_plugin() -> WindowPlugin {
+    window_plugin_for_canvas(DEFAULT_CANVAS_SELECTOR)
+}
+
+/// Window plugin rendering into the canvas matching a CSS selector
+///
+/// The canvas is resized to fit its parent element, which must not be sized by its children.
+/// Browser events are left to the page rather than having their default handling prevented: the
+/// page can still scroll and use its shortcuts, and winit does not hit its `RefCell` borrow panic
+/// when the page handles events while the app is running.
+pub fn window_plugin_for_canvas(selector: impl Into<String>) -> WindowPlugin {
+    WindowPlugin {
+        primary_window: Some(Window {
+            canvas: Some(selector.into()),
+            fit_canvas_to_parent: true,
+            prevent_default_event_handling: false,
+            ..default()
+        }),
+        ..default()
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_hourglass::*;
+
+fn bake(fill_percent: f32) -> Image {
+    bake_hourglass_image(
+        &HourglassMeshBodyConfig::default(),
+        Some(&HourglassMeshPlatesConfig::default()),
+        &HourglassMeshSandConfig::default(),
+        fill_percent,
+        &HourglassBakeConfig::new(64),
+    )
+}
+
+fn alpha_at(image: &Image, x: u32, y: u32) -> f32 {
+    image.get_color_at(x, y).unwrap().alpha()
+}
+
+#[test]
+fn baked_image_has_requested_size_and_transparent_corners() {
+    let image = bake(0.5);
+    assert_eq!(image.size(), UVec2::splat(64));
+    assert_eq!(alpha_at(&image, 0, 0), 0.0);
+    assert_eq!(alpha_at(&image, 63, 63), 0.0);
+}
+
+#[test]
+fn baked_sand_follows_fill_percent() {
+    let full = bake(1.0);
+    let empty = bake(0.0);
+
+    // Sand is opaque while the glass is translucent
+    let top_bulb = (32, 18);
+    let bottom_bulb = (32, 46);
+    assert!(alpha_at(&full, top_bulb.0, top_bulb.1) > alpha_at(&empty, top_bulb.0, top_bulb.1));
+    assert!(
+        alpha_at(&empty, bottom_bulb.0, bottom_bulb.1)
+            > alpha_at(&full, bottom_bulb.0, bottom_bulb.1)
+    );
+}
+
+#[test]
+fn batch_bake_returns_one_image_per_fill() {
+    let images = bake_hourglass_images(
+        &HourglassMeshBodyConfig::default(),
+        None,
+        &HourglassMeshSandConfig::default(),
+        &DEFAULT_BAKE_FILLS,
+        &HourglassBakeConfig::new(16),
+    );
+    assert_eq!(images.len(), DEFAULT_BAKE_FILLS.len());
+}
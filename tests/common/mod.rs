@@ -0,0 +1,102 @@
+//! Shared helpers for integration tests running the plugin headless.
+
+#![allow(dead_code)]
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_hourglass::*;
+use std::time::Duration;
+
+/// Time advanced by every call to `App::update`
+pub const STEP: f32 = 0.1;
+
+/// Headless app with the hourglass plugin and a fixed time step
+///
+/// Mesh and material assets are mocked with plain asset collections so the visual systems
+/// run as well.
+pub fn test_app() -> App {
+    let mut app = headless_app();
+    app.init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<ColorMaterial>>();
+    app
+}
+
+/// Headless app with only the core hourglass logic, without any mesh assets
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HourglassPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            STEP,
+        )))
+        .init_resource::<EventLog>()
+        .add_systems(PostUpdate, log_events);
+    // The first update only initializes time
+    app.update();
+    app
+}
+
+/// Every hourglass event sent so far
+#[derive(Resource, Default, Debug)]
+pub struct EventLog {
+    pub flip_starts: Vec<Entity>,
+    pub empties: Vec<Entity>,
+    pub chain_completes: Vec<(Entity, Entity)>,
+    pub time_adjustments: Vec<(Entity, f32)>,
+}
+
+fn log_events(
+    mut log: ResMut<EventLog>,
+    mut flip_starts: EventReader<HourglassFlipStartEvent>,
+    mut empties: EventReader<HourglassEmptyEvent>,
+    mut chain_completes: EventReader<HourglassChainCompleteEvent>,
+    mut time_adjustments: EventReader<HourglassTimeAdjustedEvent>,
+) {
+    log.flip_starts
+        .extend(flip_starts.read().map(|event| event.entity));
+    log.empties.extend(empties.read().map(|event| event.entity));
+    log.chain_completes.extend(
+        chain_completes
+            .read()
+            .map(|event| (event.first, event.last)),
+    );
+    log.time_adjustments.extend(
+        time_adjustments
+            .read()
+            .map(|event| (event.entity, event.seconds)),
+    );
+}
+
+/// Spawn an hourglass from a builder
+pub fn spawn(app: &mut App, builder: HourglassMeshBuilder) -> Entity {
+    let mut builder = Some(builder);
+    app.world_mut()
+        .run_system_once(
+            move |mut commands: Commands,
+                  mut meshes: ResMut<Assets<Mesh>>,
+                  mut materials: ResMut<Assets<ColorMaterial>>| {
+                builder
+                    .take()
+                    .expect("builder system only runs once")
+                    .build(&mut commands, &mut meshes, &mut materials)
+            },
+        )
+        .expect("builder system should run")
+}
+
+/// Builder for a default hourglass running for the given duration
+pub fn default_builder(duration: f32) -> HourglassMeshBuilder {
+    HourglassMeshBuilder::new(Transform::IDENTITY)
+        .with_body(HourglassMeshBodyConfig::default())
+        .with_plates(HourglassMeshPlatesConfig::default())
+        .with_sand(HourglassMeshSandConfig::default())
+        .with_timing(duration)
+}
+
+/// Advance the app by the given number of seconds
+pub fn advance(app: &mut App, seconds: f32) {
+    let steps = (seconds / STEP).round() as usize;
+    for _ in 0..steps {
+        app.update();
+    }
+}
@@ -0,0 +1,2755 @@
+//! Integration tests running the hourglass plugin with `MinimalPlugins`.
+
+mod common;
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::render::mesh::VertexAttributeValues;
+use bevy_hourglass::*;
+use common::*;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn builder_spawns_hourglass_with_parts() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(5.0));
+    app.update();
+
+    let world = app.world();
+    assert!(world.get::<Hourglass>(entity).is_some());
+    assert!(world.get::<HourglassMeshSandState>(entity).is_some());
+
+    let children = world
+        .get::<Children>(entity)
+        .expect("hourglass has children");
+    let sand_parts = children
+        .iter()
+        .filter(|child| world.get::<HourglassMeshSand>(*child).is_some())
+        .count();
+    assert_eq!(sand_parts, 2);
+    assert!(children
+        .iter()
+        .any(|child| world.get::<HourglassMeshBody>(child).is_some()));
+}
+
+#[test]
+fn minimal_render_style_skips_plates() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_render_style(RenderStyle::Minimal { stroke_width: 2.0 }),
+    );
+    app.update();
+
+    let world = app.world();
+    let children = world
+        .get::<Children>(entity)
+        .expect("hourglass has children");
+    assert!(!children
+        .iter()
+        .any(|child| world.get::<HourglassMeshPlate>(child).is_some()));
+    assert!(children
+        .iter()
+        .any(|child| world.get::<HourglassMeshBody>(child).is_some()));
+}
+
+#[test]
+fn world_unit_strokes_keep_their_width_when_scaled() {
+    let mut app = test_app();
+    app.add_plugins(TransformPlugin);
+    let stroke = |stroke_width, stroke_units| {
+        default_builder(5.0)
+            .with_body(HourglassMeshBodyConfig {
+                stroke_units,
+                ..default()
+            })
+            .with_render_style(RenderStyle::Minimal { stroke_width })
+    };
+    let world_units = spawn(&mut app, stroke(4.0, SizeUnits::World));
+    let local_units = spawn(&mut app, stroke(2.0, SizeUnits::Local));
+    app.world_mut()
+        .get_mut::<Transform>(world_units)
+        .unwrap()
+        .scale = Vec3::splat(2.0);
+    advance(&mut app, 0.2);
+
+    // Twice as wide a stroke on an hourglass twice as big is as wide in world units
+    let world = app.world();
+    assert_eq!(
+        world.get::<HourglassStrokeScale>(world_units),
+        Some(&HourglassStrokeScale(2.0))
+    );
+    assert!(world.get::<HourglassStrokeScale>(local_units).is_none());
+    let body_width = |entity| {
+        let body = world.get::<HourglassParts>(entity).unwrap().body.unwrap();
+        let mesh = world
+            .resource::<Assets<Mesh>>()
+            .get(&world.get::<Mesh2d>(body).unwrap().0)
+            .unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("stroke has positions");
+        };
+        positions.iter().fold(0.0f32, |width, p| width.max(p[0]))
+    };
+    assert!((body_width(world_units) - body_width(local_units)).abs() < 1e-3);
+
+    // Later rescales update the stroke in place, ignoring changes too small to see
+    let body = world
+        .get::<HourglassParts>(world_units)
+        .unwrap()
+        .body
+        .unwrap();
+    let stroke_mesh = world.get::<Mesh2d>(body).unwrap().id();
+    for scale in [3.0, 3.01] {
+        app.world_mut()
+            .get_mut::<Transform>(world_units)
+            .unwrap()
+            .scale = Vec3::splat(scale);
+        advance(&mut app, 0.2);
+    }
+    let world = app.world();
+    assert_eq!(
+        world.get::<HourglassStrokeScale>(world_units),
+        Some(&HourglassStrokeScale(3.0))
+    );
+    assert_eq!(world.get::<Mesh2d>(body).unwrap().id(), stroke_mesh);
+}
+
+#[test]
+fn sand_state_follows_the_timer() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(10.0));
+
+    advance(&mut app, 5.0);
+
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!((hourglass.upper_chamber - 0.5).abs() < 0.02);
+    assert!((hourglass.remaining_time - 5.0).abs() < 0.2);
+
+    let sand_state = app.world().get::<HourglassMeshSandState>(entity).unwrap();
+    assert!((sand_state.fill_percent - hourglass.upper_chamber).abs() < f32::EPSILON);
+    assert!(!sand_state.needs_update);
+}
+
+#[test]
+fn sand_stops_flowing_below_min_flow_fill() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        HourglassMeshBuilder::new(Transform::IDENTITY)
+            .with_body(HourglassMeshBodyConfig::default())
+            .with_sand(HourglassMeshSandConfig {
+                min_flow_fill: 0.1,
+                ..default()
+            })
+            .with_timing(10.0),
+    );
+
+    advance(&mut app, 8.5);
+    assert!(app.world().get::<Hourglass>(entity).unwrap().running);
+    assert!(app.world().resource::<EventLog>().empties.is_empty());
+
+    // The timer runs out with the sand once it is below the threshold
+    advance(&mut app, 1.0);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!(!hourglass.running);
+    assert_eq!(hourglass.remaining_time, 0.0);
+    assert_eq!(hourglass.lower_chamber, 1.0);
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![entity]);
+    let sand_state = app.world().get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.fill_percent, 0.0);
+}
+
+#[test]
+fn empty_event_is_sent_once() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(1.0));
+
+    advance(&mut app, 3.0);
+
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.remaining_time, 0.0);
+    assert!(!hourglass.running);
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![entity]);
+}
+
+#[test]
+fn empty_hourglasses_are_marked_until_flipped() {
+    let mut app = headless_app();
+    let entity = app.world_mut().spawn(Hourglass::new(1.0)).id();
+
+    advance(&mut app, 0.5);
+    assert!(app.world().get::<HourglassEmpty>(entity).is_none());
+
+    advance(&mut app, 1.0);
+    assert!(app.world().get::<HourglassEmpty>(entity).is_some());
+
+    app.world_mut().get_mut::<Hourglass>(entity).unwrap().flip();
+    advance(&mut app, 1.2);
+    assert!(app.world().get::<HourglassEmpty>(entity).is_none());
+}
+
+#[test]
+fn auto_flip_sends_one_flip_start_per_flip() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(1.0)
+            .with_flip_duration(0.5)
+            .with_auto_flip(true),
+    );
+
+    // Empty after 1s, flip for 0.5s, then run again
+    advance(&mut app, 2.0);
+
+    let log = app.world().resource::<EventLog>();
+    assert_eq!(log.empties, vec![entity]);
+    assert_eq!(log.flip_starts, vec![entity]);
+
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!(!hourglass.flipping);
+    assert!(hourglass.running);
+    assert!(hourglass.upper_chamber > 0.0);
+}
+
+#[test]
+fn hourglasses_run_without_mesh_assets() {
+    let mut app = headless_app();
+    let entity = app.world_mut().spawn(Hourglass::new(1.0)).id();
+
+    advance(&mut app, 1.5);
+
+    assert_eq!(
+        app.world().get::<Hourglass>(entity).unwrap().upper_chamber,
+        0.0
+    );
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![entity]);
+}
+
+#[test]
+fn added_and_removed_time_eases_the_sand() {
+    let mut app = headless_app();
+    let entity = app.world_mut().spawn(Hourglass::new(10.0)).id();
+    advance(&mut app, 4.0);
+
+    // Time is capped by the capacity of the hourglass
+    let mut hourglass = app.world_mut().get_mut::<Hourglass>(entity).unwrap();
+    let added = hourglass.add_time(5.0);
+    assert!((added - 4.0).abs() < 0.01);
+    assert_eq!(hourglass.remaining_time, 10.0);
+    assert!(hourglass.displayed_upper_chamber() < 0.61);
+
+    advance(&mut app, 0.5);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.displayed_upper_chamber(), hourglass.upper_chamber);
+    assert_eq!(
+        app.world().resource::<EventLog>().time_adjustments,
+        vec![(entity, added)]
+    );
+
+    // Taking away the remaining time empties the hourglass
+    let removed = app
+        .world_mut()
+        .get_mut::<Hourglass>(entity)
+        .unwrap()
+        .remove_time(20.0);
+    assert!(removed > 9.0);
+    app.update();
+    let log = app.world().resource::<EventLog>();
+    assert_eq!(log.time_adjustments[1], (entity, -removed));
+    assert_eq!(log.empties, vec![entity]);
+}
+
+#[test]
+fn time_adjusted_during_a_flip_is_applied_after_it() {
+    let mut sim = HourglassSim::new(Hourglass {
+        flip_duration: 1.0,
+        ..Hourglass::new(10.0)
+    });
+    sim.step(6.0);
+    sim.hourglass.flip();
+    sim.step(0.5);
+
+    // Queued until the flip completes
+    assert_eq!(sim.hourglass.add_time(2.0), 0.0);
+    assert_eq!(sim.hourglass.queued_time_adjustment, 2.0);
+    assert!((sim.hourglass.remaining_time - 4.0).abs() < 1e-4);
+    sim.step(0.5);
+    assert!(!sim.hourglass.flipping);
+    let remaining = sim.hourglass.remaining_time;
+    let events = sim.step(0.0);
+    assert!((sim.hourglass.remaining_time - remaining - 2.0).abs() < 1e-3);
+    assert_eq!(sim.hourglass.queued_time_adjustment, 0.0);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        HourglassSimEvent::TimeAdjusted { seconds } if (seconds - 2.0).abs() < 1e-3
+    )));
+
+    // Rewinds queue time the same way
+    sim.hourglass.rewind(1.0);
+    assert_eq!(sim.hourglass.remove_time(3.0), 0.0);
+    sim.step(1.0);
+    assert_eq!(sim.hourglass.remaining_time, 10.0);
+    sim.step(0.0);
+    assert!((sim.hourglass.remaining_time - 7.0).abs() < 1e-3);
+}
+
+#[test]
+fn overflowing_time_follows_the_policy() {
+    let overflow = |policy| {
+        let mut sim = HourglassSim::new(Hourglass {
+            overflow_policy: policy,
+            ..Hourglass::new(10.0)
+        });
+        sim.step(4.0);
+        let added = sim.hourglass.add_time(6.0);
+        let mut events = sim.step(0.0);
+        sim.hourglass.remove_time(30.0);
+        events.extend(sim.step(0.0));
+        (added, sim.hourglass, events)
+    };
+
+    let (added, hourglass, events) = overflow(TimeOverflowPolicy::ClampSilently);
+    assert!((added - 4.0).abs() < 1e-4);
+    assert_eq!(hourglass.total_time, 10.0);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, HourglassSimEvent::TimeOverflow { .. })));
+
+    let (_, _, events) = overflow(TimeOverflowPolicy::EmitOverflowEvent);
+    let overflows: Vec<f32> = events
+        .iter()
+        .filter_map(|event| match event {
+            HourglassSimEvent::TimeOverflow { seconds } => Some(*seconds),
+            _ => None,
+        })
+        .collect();
+    // Time removed beyond zero is dropped without being reported
+    assert_eq!(overflows.len(), 1);
+    assert!((overflows[0] - 2.0).abs() < 1e-4);
+
+    let (added, hourglass, _) = overflow(TimeOverflowPolicy::ExtendTotalTime);
+    assert_eq!(added, 6.0);
+    assert!((hourglass.total_time - 12.0).abs() < 1e-4);
+    assert!((hourglass.flow_rate - 1.0 / 12.0).abs() < 1e-6);
+
+    // Chambers pushed out of range, e.g. by float error, are brought back on the next step
+    let mut sim = HourglassSim::new(Hourglass {
+        upper_chamber: 1.000_001,
+        lower_chamber: 0.2,
+        remaining_time: f32::NAN,
+        ..Hourglass::new(10.0)
+    });
+    sim.step(0.0);
+    assert_eq!(sim.hourglass.upper_chamber, 1.0);
+    assert_eq!(sim.hourglass.lower_chamber, 0.0);
+    assert_eq!(sim.hourglass.remaining_time, 10.0);
+}
+
+#[derive(Resource)]
+struct Stamina(f32);
+
+#[test]
+fn bound_hourglasses_flow_towards_their_value() {
+    let mut app = test_app();
+    app.insert_resource(Stamina(0.2));
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0)
+            .with_binding(HourglassBinding::resource(|stamina: &Stamina| stamina.0)),
+    );
+
+    // The sand flows down at the maximum rate instead of jumping
+    advance(&mut app, 1.0);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.value_source, HourglassValueSource::External);
+    assert!(hourglass.running);
+    assert!((hourglass.upper_chamber - 0.5).abs() < 0.06);
+    advance(&mut app, 1.0);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.upper_chamber, 0.2);
+    assert!(!hourglass.running);
+
+    // And back up, with the stream rising
+    app.world_mut().resource_mut::<Stamina>().0 = 0.6;
+    advance(&mut app, 0.5);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!(hourglass.upper_chamber > 0.3 && hourglass.upper_chamber < 0.6);
+    assert!(hourglass.flow_rate < 0.0);
+    let fill = app
+        .world()
+        .get::<HourglassMeshSandState>(entity)
+        .unwrap()
+        .fill_percent;
+    assert_eq!(fill, hourglass.upper_chamber);
+}
+
+#[test]
+fn linked_hourglasses_run_one_after_the_other() {
+    let mut app = headless_app();
+    let stages: Vec<Entity> = (0..3)
+        .map(|_| app.world_mut().spawn(Hourglass::new(1.0)).id())
+        .collect();
+    for link in HourglassLink::chain(&stages) {
+        app.world_mut().spawn(link);
+    }
+
+    // The second hourglass fills as the first drains, the third waits its turn
+    advance(&mut app, 0.5);
+    let world = app.world();
+    let second = world.get::<Hourglass>(stages[1]).unwrap();
+    assert!(!second.running);
+    assert!((second.upper_chamber - 0.5).abs() < 0.11);
+    assert_eq!(
+        world.get::<Hourglass>(stages[2]).unwrap().upper_chamber,
+        0.0
+    );
+
+    advance(&mut app, 1.0);
+    let world = app.world();
+    let second = world.get::<Hourglass>(stages[1]).unwrap();
+    assert!(second.running);
+    assert!(second.upper_chamber > 0.3 && second.upper_chamber < 1.0);
+    assert!(world.resource::<EventLog>().chain_completes.is_empty());
+
+    advance(&mut app, 2.5);
+    assert_eq!(
+        app.world().resource::<EventLog>().chain_completes,
+        vec![(stages[0], stages[2])]
+    );
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn persistent_hourglasses_catch_up_after_restarts() {
+    let saved_at = SystemTime::now() - Duration::from_secs(30);
+    let snapshot = HourglassSnapshot::capture(&Hourglass::new(60.0), saved_at);
+    assert_eq!(
+        HourglassSnapshot::from_bytes(&snapshot.to_bytes()).unwrap(),
+        snapshot
+    );
+    let expired = HourglassSnapshot::capture(&Hourglass::new(10.0), saved_at);
+    let mut store = MemoryHourglassStore::default();
+    store.save("daily", &snapshot.to_ron_string());
+    store.save("expired", &expired.to_ron_string());
+
+    let mut app = headless_app();
+    app.insert_resource(HourglassPersistence::new(store).with_save_interval(0.5));
+    let daily = app
+        .world_mut()
+        .spawn((Hourglass::new(60.0), PersistentHourglass::new("daily")))
+        .id();
+    let expired = app
+        .world_mut()
+        .spawn((Hourglass::new(10.0), PersistentHourglass::new("expired")))
+        .id();
+    app.update();
+
+    let remaining = app.world().get::<Hourglass>(daily).unwrap().remaining_time;
+    assert!((remaining - 30.0).abs() < 0.5);
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![expired]);
+}
+
+#[test]
+fn grid_hourglasses_share_body_meshes() {
+    let mut app = test_app();
+    let entities = app
+        .world_mut()
+        .run_system_once(
+            |mut commands: Commands,
+             mut meshes: ResMut<Assets<Mesh>>,
+             mut materials: ResMut<Assets<ColorMaterial>>| {
+                let grid = GridConfig::new(2, 2, Vec2::new(100.0, 100.0), |cell| {
+                    HourglassMeshBuilder::new(cell.transform)
+                        .with_body(HourglassMeshBodyConfig::default())
+                        .with_sand(HourglassMeshSandConfig::default())
+                        .with_timing(5.0)
+                });
+                spawn_hourglass_grid(&mut commands, &mut meshes, &mut materials, grid)
+            },
+        )
+        .expect("grid system should run");
+    assert_eq!(entities.len(), 4);
+
+    let world = app.world();
+    let body_meshes: Vec<AssetId<Mesh>> = entities
+        .iter()
+        .flat_map(|entity| world.get::<Children>(*entity).unwrap().iter())
+        .filter(|child| world.get::<HourglassMeshBody>(*child).is_some())
+        .map(|child| world.get::<Mesh2d>(child).unwrap().id())
+        .collect();
+    assert_eq!(body_meshes.len(), 4);
+    assert!(body_meshes.iter().all(|id| *id == body_meshes[0]));
+}
+
+#[test]
+fn global_asset_cache_shares_identical_parts() {
+    let mut app = test_app();
+    let entities = app
+        .world_mut()
+        .run_system_once(
+            |mut commands: Commands,
+             mut meshes: ResMut<Assets<Mesh>>,
+             mut materials: ResMut<Assets<ColorMaterial>>,
+             mut cache: ResMut<HourglassAssetCache>| {
+                [0.0, 200.0].map(|x| {
+                    HourglassMeshBuilder::new(Transform::from_xyz(x, 0.0, 0.0))
+                        .with_body(HourglassMeshBodyConfig::default())
+                        .with_plates(HourglassMeshPlatesConfig::default())
+                        .build_with_cache(&mut commands, &mut meshes, &mut materials, &mut cache)
+                })
+            },
+        )
+        .expect("spawn system should run");
+
+    let world = app.world();
+    let plate_meshes: Vec<AssetId<Mesh>> = entities
+        .iter()
+        .flat_map(|entity| world.get::<Children>(*entity).unwrap().iter())
+        .filter(|child| world.get::<HourglassMeshPlate>(*child).is_some())
+        .map(|child| world.get::<Mesh2d>(child).unwrap().id())
+        .collect();
+    assert_eq!(plate_meshes.len(), 4);
+    assert!(plate_meshes.iter().all(|id| *id == plate_meshes[0]));
+}
+
+#[test]
+fn plain_builds_share_parts_through_the_global_cache() {
+    let mut app = test_app();
+    let entities = [
+        spawn(&mut app, default_builder(5.0)),
+        spawn(&mut app, default_builder(10.0)),
+    ];
+
+    let world = app.world();
+    let parts: Vec<HourglassParts> = entities
+        .iter()
+        .map(|entity| *world.get::<HourglassParts>(*entity).unwrap())
+        .collect();
+    let mesh = |part: Option<Entity>| world.get::<Mesh2d>(part.unwrap()).unwrap().id();
+    let material = |part: Option<Entity>| {
+        world
+            .get::<MeshMaterial2d<ColorMaterial>>(part.unwrap())
+            .unwrap()
+            .id()
+    };
+    assert_eq!(mesh(parts[0].body), mesh(parts[1].body));
+    assert_eq!(material(parts[0].body), material(parts[1].body));
+    assert_eq!(mesh(parts[0].top_plate), mesh(parts[1].bottom_plate));
+    assert_ne!(mesh(parts[0].top_sand), mesh(parts[1].top_sand));
+    assert!(!world.resource::<HourglassAssetCache>().is_empty());
+}
+
+#[test]
+fn image_render_layers_are_reused_after_despawn() {
+    let mut app = test_app();
+    app.init_resource::<Assets<Image>>();
+    let render = |app: &mut App, hourglass: Entity| {
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands, mut images: ResMut<Assets<Image>>| {
+                    render_hourglass_to_image(
+                        &mut commands,
+                        &mut images,
+                        hourglass,
+                        UVec2::splat(64),
+                    );
+                },
+            )
+            .expect("render system should run");
+    };
+    let layers = |app: &mut App| {
+        let world = app.world_mut();
+        let mut layers: Vec<usize> = world
+            .query::<&HourglassImageCamera>()
+            .iter(world)
+            .map(|camera| camera.layer)
+            .collect();
+        layers.sort();
+        layers
+    };
+
+    let first = spawn(&mut app, default_builder(5.0));
+    let second = spawn(&mut app, default_builder(5.0));
+    render(&mut app, first);
+    render(&mut app, second);
+    assert_eq!(
+        layers(&mut app),
+        vec![FIRST_HOURGLASS_IMAGE_LAYER, FIRST_HOURGLASS_IMAGE_LAYER + 1]
+    );
+
+    // The camera goes with its hourglass, and its layer is handed out again
+    app.world_mut().entity_mut(first).despawn();
+    advance(&mut app, 0.2);
+    assert_eq!(app.world().resource::<HourglassImageLayers>().len(), 1);
+    let third = spawn(&mut app, default_builder(5.0));
+    render(&mut app, third);
+    assert_eq!(
+        layers(&mut app),
+        vec![FIRST_HOURGLASS_IMAGE_LAYER, FIRST_HOURGLASS_IMAGE_LAYER + 1]
+    );
+}
+
+#[test]
+fn uv_modes_map_glass_and_sand_textures() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        HourglassMeshBuilder::new(Transform::IDENTITY)
+            .with_body(HourglassMeshBodyConfig {
+                uv_mode: UvMode::Planar { size: 100.0 },
+                ..default()
+            })
+            .with_sand(HourglassMeshSandConfig::default())
+            .with_timing(5.0),
+    );
+    advance(&mut app, 1.0);
+
+    let world = app.world();
+    let parts = world.get::<HourglassParts>(entity).unwrap();
+    let meshes = world.resource::<Assets<Mesh>>();
+    for part in [parts.body.unwrap(), parts.top_sand.unwrap()] {
+        let mesh = meshes.get(&world.get::<Mesh2d>(part).unwrap().0).unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh has positions");
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("mesh has UVs");
+        };
+        for (position, uv) in positions.iter().zip(uvs) {
+            assert!((uv[0] - (position[0] / 100.0 + 0.5)).abs() < 1e-5);
+            assert!((uv[1] - (0.5 - position[1] / 100.0)).abs() < 1e-5);
+        }
+    }
+
+    // Outlines are stretched over the whole texture by default
+    let mesh = HourglassMeshBuilder::create_body_mesh(
+        &HourglassMeshBodyConfig::default(),
+        RenderStyle::Filled,
+    );
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        panic!("mesh has UVs");
+    };
+    let max = uvs.iter().fold([0.0f32; 2], |max, uv| {
+        [max[0].max(uv[0]), max[1].max(uv[1])]
+    });
+    assert_eq!(max, [1.0, 1.0]);
+}
+
+#[test]
+fn feathered_edges_fade_out_around_glass_and_sand() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_body(HourglassMeshBodyConfig {
+            edge_quality: EdgeQuality::Feathered { width: 1.5 },
+            ..default()
+        }),
+    );
+    advance(&mut app, 1.0);
+
+    let world = app.world();
+    let parts = world.get::<HourglassParts>(entity).unwrap();
+    let meshes = world.resource::<Assets<Mesh>>();
+    for part in [parts.body.unwrap(), parts.top_sand.unwrap()] {
+        let mesh = meshes.get(&world.get::<Mesh2d>(part).unwrap().0).unwrap();
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("feathered mesh has vertex colors");
+        };
+        // Opaque outline vertices followed by a transparent fringe around them
+        let (outline, fringe) = colors.split_at(colors.len() / 2);
+        assert!(outline.iter().all(|color| color[3] == 1.0));
+        assert!(fringe.iter().all(|color| color[3] == 0.0));
+    }
+    let sand_material = &world
+        .get::<MeshMaterial2d<ColorMaterial>>(parts.top_sand.unwrap())
+        .unwrap()
+        .0;
+    assert_eq!(
+        world
+            .resource::<Assets<ColorMaterial>>()
+            .get(sand_material)
+            .unwrap()
+            .alpha_mode,
+        bevy::sprite::AlphaMode2d::Blend
+    );
+
+    // Hard edges by default, without vertex colors
+    let mesh = HourglassMeshBuilder::create_body_mesh(
+        &HourglassMeshBodyConfig::default(),
+        RenderStyle::Filled,
+    );
+    assert!(mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_none());
+}
+
+#[test]
+fn sand_updates_reuse_mesh_and_material_assets() {
+    let mut app = test_app();
+    spawn(&mut app, default_builder(2.0));
+    app.update();
+
+    let meshes = app.world().resource::<Assets<Mesh>>().len();
+    let materials = app.world().resource::<Assets<ColorMaterial>>().len();
+
+    // Run the sand through completely, emptying the top bulb
+    advance(&mut app, 2.5);
+
+    assert!(app.world().resource::<Assets<Mesh>>().len() <= meshes);
+    assert_eq!(
+        app.world().resource::<Assets<ColorMaterial>>().len(),
+        materials
+    );
+}
+
+#[test]
+fn editing_any_sand_parameter_rebuilds_the_sand() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(100.0));
+    // Spawned sand is built like the updates build it, at the hourglass' quality
+    let low = spawn(
+        &mut app,
+        default_builder(100.0).with_quality(QualityTier::Low),
+    );
+    app.world_mut()
+        .get_mut::<Hourglass>(entity)
+        .unwrap()
+        .running = false;
+
+    let top_sand_width = |app: &App, entity: Entity| {
+        let world = app.world();
+        let top_sand = world
+            .get::<HourglassParts>(entity)
+            .unwrap()
+            .top_sand
+            .unwrap();
+        let handle = &world.get::<Mesh2d>(top_sand).unwrap().0;
+        let mesh = world.resource::<Assets<Mesh>>().get(handle).unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("sand mesh without positions");
+        };
+        (
+            positions.iter().map(|p| p[0]).fold(f32::MIN, f32::max),
+            positions.len(),
+        )
+    };
+    let (width, vertices) = top_sand_width(&app, entity);
+    assert!(top_sand_width(&app, low).1 < vertices);
+
+    // Edit a field directly, without requesting an update
+    app.world_mut()
+        .get_mut::<HourglassMeshSandState>(entity)
+        .unwrap()
+        .sand_config
+        .wall_offset += 10.0;
+    app.update();
+
+    assert!(top_sand_width(&app, entity).0 < width - 5.0);
+    let sand_state = app.world().get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.built_sand_config, sand_state.sand_config);
+}
+
+#[test]
+fn deadline_tracks_wall_clock_and_empties_once() {
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let mut deadline =
+        HourglassDeadline::new(start + Duration::from_secs(100)).with_bound_at(start);
+    let mut hourglass = Hourglass::new(5.0);
+
+    assert!(!deadline.sync(&mut hourglass, start + Duration::from_secs(25)));
+    assert_eq!(hourglass.total_time, 100.0);
+    assert!((hourglass.upper_chamber - 0.75).abs() < 1e-4);
+
+    // Jumping far past the deadline still reports it exactly once
+    assert!(deadline.sync(&mut hourglass, start + Duration::from_secs(10_000)));
+    assert!(!deadline.sync(&mut hourglass, start + Duration::from_secs(10_001)));
+    assert_eq!(hourglass.upper_chamber, 0.0);
+}
+
+#[test]
+fn past_deadline_sends_empty_event() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(5.0));
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(HourglassDeadline::new(
+            SystemTime::now() - Duration::from_secs(1),
+        ));
+
+    advance(&mut app, 0.5);
+
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![entity]);
+}
+
+#[test]
+fn speed_multiplier_scales_sand_flow() {
+    let mut app = test_app();
+    let fast = spawn(&mut app, default_builder(2.0).with_speed(2.0));
+    let slow = spawn(&mut app, default_builder(2.0).with_speed(0.5));
+
+    advance(&mut app, 1.0);
+
+    let remaining = |entity| app.world().get::<Hourglass>(entity).unwrap().remaining_time;
+    assert_eq!(remaining(fast), 0.0);
+    assert!((remaining(slow) - 1.5).abs() < 1e-3);
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![fast]);
+}
+
+#[test]
+fn flips_started_while_paused_are_reported_once() {
+    let mut app = headless_app();
+    let entity = app.world_mut().spawn(Hourglass::new(5.0)).id();
+    app.world_mut()
+        .resource_mut::<HourglassGlobalState>()
+        .paused = true;
+
+    app.world_mut().get_mut::<Hourglass>(entity).unwrap().flip();
+    advance(&mut app, 0.5);
+    assert_eq!(app.world().resource::<EventLog>().flip_starts, vec![entity]);
+
+    app.world_mut()
+        .resource_mut::<HourglassGlobalState>()
+        .paused = false;
+    advance(&mut app, 1.5);
+    assert!(!app.world().get::<Hourglass>(entity).unwrap().flipping);
+    assert_eq!(app.world().resource::<EventLog>().flip_starts, vec![entity]);
+}
+
+#[test]
+fn global_state_pauses_and_scales_all_hourglasses() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(10.0));
+    let remaining = |app: &App| app.world().get::<Hourglass>(entity).unwrap().remaining_time;
+
+    app.world_mut()
+        .resource_mut::<HourglassGlobalState>()
+        .paused = true;
+    advance(&mut app, 1.0);
+    assert_eq!(remaining(&app), 10.0);
+
+    let mut global = app.world_mut().resource_mut::<HourglassGlobalState>();
+    global.paused = false;
+    global.speed = 2.0;
+    advance(&mut app, 1.0);
+    assert!((remaining(&app) - 8.0).abs() < 1e-3);
+}
+
+#[test]
+fn state_scoped_hourglass_pauses_outside_its_state() {
+    #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+    enum GameState {
+        #[default]
+        Playing,
+        Menu,
+    }
+
+    let mut app = test_app();
+    app.add_plugins(bevy::state::app::StatesPlugin)
+        .init_state::<GameState>()
+        .add_plugins(HourglassStatePlugin::<GameState>::default());
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0).with_state_scope(
+            HourglassActiveInState::new(GameState::Playing).with_hide_outside(true),
+        ),
+    );
+    let remaining = |app: &App| app.world().get::<Hourglass>(entity).unwrap().remaining_time;
+
+    advance(&mut app, 1.0);
+    assert!((remaining(&app) - 9.0).abs() < 1e-3);
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    advance(&mut app, 0.5);
+    let paused_at = remaining(&app);
+    advance(&mut app, 1.0);
+    assert_eq!(remaining(&app), paused_at);
+    assert!(paused_at > 8.5);
+    assert_eq!(
+        app.world().get::<Visibility>(entity),
+        Some(&Visibility::Hidden)
+    );
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    advance(&mut app, 1.0);
+    assert!(app.world().get::<Hourglass>(entity).unwrap().running);
+    assert!(remaining(&app) < paused_at);
+    assert_eq!(
+        app.world().get::<Visibility>(entity),
+        Some(&Visibility::Inherited)
+    );
+}
+
+#[test]
+fn speed_change_sends_event() {
+    #[derive(Resource, Default)]
+    struct SpeedChanges(Vec<(f32, f32)>);
+
+    let mut app = test_app();
+    app.init_resource::<SpeedChanges>().add_systems(
+        PostUpdate,
+        |mut changes: ResMut<SpeedChanges>, mut events: EventReader<HourglassSpeedChangeEvent>| {
+            changes.0.extend(
+                events
+                    .read()
+                    .map(|event| (event.previous_speed, event.speed)),
+            );
+        },
+    );
+    let entity = spawn(&mut app, default_builder(5.0).with_speed(1.5));
+    advance(&mut app, 0.2);
+    assert!(app.world().resource::<SpeedChanges>().0.is_empty());
+
+    app.world_mut().get_mut::<Hourglass>(entity).unwrap().speed = 0.25;
+    advance(&mut app, 0.2);
+
+    assert_eq!(app.world().resource::<SpeedChanges>().0, vec![(1.5, 0.25)]);
+}
+
+#[test]
+fn rewind_refills_upper_chamber_and_restarts_timer() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(2.0));
+    advance(&mut app, 2.5);
+    assert_eq!(app.world().resource::<EventLog>().empties, vec![entity]);
+
+    app.world_mut()
+        .get_mut::<Hourglass>(entity)
+        .unwrap()
+        .rewind(1.0);
+    advance(&mut app, 0.5);
+    {
+        let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+        assert!(hourglass.rewinding && !hourglass.running);
+        assert!((hourglass.upper_chamber - 0.5).abs() < 1e-3);
+    }
+
+    advance(&mut app, 0.5);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!(!hourglass.rewinding && hourglass.running);
+    assert_eq!(hourglass.remaining_time, hourglass.total_time);
+
+    // The timer runs again from the top, emptying a second time
+    advance(&mut app, 2.5);
+    assert_eq!(
+        app.world().resource::<EventLog>().empties,
+        vec![entity, entity]
+    );
+}
+
+#[test]
+fn split_sand_colors_use_separate_materials() {
+    let top = Color::srgb(0.2, 0.4, 1.0);
+    let bottom = Color::srgb(1.0, 0.2, 0.2);
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_sand(HourglassMeshSandConfig {
+            top_color: Some(top),
+            bottom_color: Some(bottom),
+            ..default()
+        }),
+    );
+    advance(&mut app, 0.5);
+
+    let world = app.world();
+    let materials = world.resource::<Assets<ColorMaterial>>();
+    let material_color = |child: Entity| {
+        let handle = world.get::<MeshMaterial2d<ColorMaterial>>(child).unwrap();
+        materials.get(&handle.0).unwrap().color
+    };
+
+    let children = world.get::<Children>(entity).unwrap();
+    for child in children.iter() {
+        match world.get::<HourglassMeshSand>(child) {
+            Some(HourglassMeshSand::TopBulb) => assert_eq!(material_color(child), top),
+            Some(HourglassMeshSand::BottomBulb) => assert_eq!(material_color(child), bottom),
+            None => {}
+        }
+        if world.get::<HourglassMeshSandStream>(child).is_some() {
+            assert_eq!(material_color(child), top.mix(&bottom, 0.5));
+        }
+    }
+}
+
+#[test]
+fn sand_color_follows_remaining_gradient() {
+    let gradient = SandGradient::traffic_light();
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(4.0).with_sand(HourglassMeshSandConfig {
+            color_by_remaining: Some(gradient.clone()),
+            ..default()
+        }),
+    );
+    advance(&mut app, 3.0);
+
+    let world = app.world();
+    let remaining = world.get::<Hourglass>(entity).unwrap().upper_chamber;
+    assert!((remaining - 0.25).abs() < 1e-3);
+    let expected = gradient.sample(remaining);
+    assert_ne!(expected, gradient.sample(1.0));
+
+    let materials = world.resource::<Assets<ColorMaterial>>();
+    let children = world.get::<Children>(entity).unwrap();
+    let top = children
+        .iter()
+        .find(|child| {
+            matches!(
+                world.get::<HourglassMeshSand>(*child),
+                Some(HourglassMeshSand::TopBulb)
+            )
+        })
+        .unwrap();
+    let handle = world.get::<MeshMaterial2d<ColorMaterial>>(top).unwrap();
+    assert_eq!(materials.get(&handle.0).unwrap().color, expected);
+}
+
+#[test]
+fn lenses_interpolate_hourglass_properties() {
+    let mut hourglass = Hourglass::new(10.0);
+
+    HourglassFillLens {
+        start: 1.0,
+        end: 0.2,
+    }
+    .apply(&mut hourglass, 0.5);
+    assert!((hourglass.upper_chamber - 0.6).abs() < 1e-6);
+    assert!((hourglass.remaining_time - 6.0).abs() < 1e-5);
+
+    HourglassRotationLens {
+        start: 0.0,
+        end: 1.0,
+    }
+    .apply(&mut hourglass, 0.25);
+    assert_eq!(hourglass.base_rotation, 0.25);
+
+    let lens = HourglassSandColorLens {
+        start: Color::BLACK,
+        end: Color::WHITE,
+    };
+    lens.apply(&mut hourglass, 1.0);
+    assert_eq!(hourglass.sand_color, Color::WHITE);
+}
+
+#[test]
+fn seeded_sand_splash_is_reproducible() {
+    let splash_positions = || {
+        let mut app = test_app();
+        spawn(
+            &mut app,
+            default_builder(5.0)
+                .with_sand_splash(SandSplashConfig::default())
+                .with_sand_splash_seed(7),
+        );
+        advance(&mut app, 0.5);
+
+        let world = app.world_mut();
+        let mut positions: Vec<_> = world
+            .query_filtered::<&Transform, With<SandSplashParticle>>()
+            .iter(world)
+            .map(|transform| transform.translation.to_array())
+            .collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        positions
+    };
+
+    let first = splash_positions();
+    assert!(!first.is_empty());
+    assert_eq!(first, splash_positions());
+}
+
+#[test]
+fn splash_lands_in_the_lower_bulb_of_rotated_hourglasses() {
+    let mut app = test_app();
+    app.add_plugins(TransformPlugin);
+    let hourglass = spawn(
+        &mut app,
+        default_builder(5.0).with_sand_splash(SandSplashConfig::default()),
+    );
+    // Turn the hourglass upside down through a parent, as its own rotation follows flips
+    let parent = app
+        .world_mut()
+        .spawn(
+            Transform::from_xyz(100.0, 0.0, 0.0)
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::PI)),
+        )
+        .id();
+    app.world_mut()
+        .entity_mut(hourglass)
+        .insert(ChildOf(parent));
+    advance(&mut app, 0.5);
+
+    // Upside down, the bottom bulb is above the hourglass' center
+    let world = app.world_mut();
+    let positions: Vec<Vec3> = world
+        .query_filtered::<&Transform, With<SandSplashParticle>>()
+        .iter(world)
+        .map(|transform| transform.translation)
+        .collect();
+    assert!(!positions.is_empty());
+    assert!(positions
+        .iter()
+        .all(|position| position.y > 50.0 && (position.x - 100.0).abs() < 20.0));
+}
+
+#[test]
+fn splashes_follow_impact_points_of_any_backend() {
+    let mut app = test_app();
+    // A hourglass drawn by another backend, which places the impact point itself
+    let entity = app
+        .world_mut()
+        .spawn((
+            Hourglass::new(10.0),
+            SandSplash::new(SandSplashConfig::default()),
+            SandImpactPoint(Some(Vec3::new(300.0, -40.0, 0.0))),
+        ))
+        .id();
+    advance(&mut app, 1.0);
+
+    let world = app.world_mut();
+    let positions: Vec<Vec3> = world
+        .query_filtered::<&Transform, With<SandSplashParticle>>()
+        .iter(world)
+        .map(|transform| transform.translation)
+        .collect();
+    assert!(!positions.is_empty());
+    assert!(positions
+        .iter()
+        .all(|position| position.distance(Vec3::new(300.0, -40.0, 0.0)) < 20.0));
+
+    // Without an impact point nothing is falling
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(SandImpactPoint(None));
+    advance(&mut app, 0.5);
+    assert!(!app.world().get::<SandSplash>(entity).unwrap().was_flowing);
+}
+
+#[test]
+fn global_config_themes_new_and_following_hourglasses() {
+    let mut app = test_app();
+    let config = HourglassConfig::new()
+        .with_sand_color(Color::srgb(1.0, 0.0, 0.0))
+        .with_size(Vec2::new(80.0, 120.0));
+    let entity = spawn(
+        &mut app,
+        HourglassMeshBuilder::new(Transform::IDENTITY)
+            .with_global_config(&config)
+            .with_timing(5.0),
+    );
+    app.update();
+
+    let world = app.world();
+    let hourglass = world.get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.sand_color, Color::srgb(1.0, 0.0, 0.0));
+    assert_eq!(hourglass.size.y, 120.0);
+    let body = world.get::<HourglassParts>(entity).unwrap().body.unwrap();
+
+    // Changes to the resource are only applied to following hourglasses while watching
+    let glass = Color::srgb(0.0, 0.0, 1.0);
+    app.world_mut().entity_mut(entity).insert(FollowGlobalTheme);
+    app.insert_resource(config.with_container_color(glass).with_watch(true));
+    app.update();
+
+    let world = app.world();
+    assert_eq!(
+        world.get::<Hourglass>(entity).unwrap().container_color,
+        glass
+    );
+    let material = &world.get::<MeshMaterial2d<ColorMaterial>>(body).unwrap().0;
+    assert_eq!(
+        world
+            .resource::<Assets<ColorMaterial>>()
+            .get(material)
+            .unwrap()
+            .color,
+        glass
+    );
+}
+
+#[test]
+fn smoothed_fill_lags_behind_short_timers() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(1.0).with_sand(HourglassMeshSandConfig {
+            fill_smoothing: 5.0,
+            ..default()
+        }),
+    );
+    advance(&mut app, 0.5);
+
+    // The timer keeps its pace while the shown sand eases after it
+    let world = app.world();
+    let hourglass = world.get::<Hourglass>(entity).unwrap();
+    assert!((hourglass.remaining_time - 0.5).abs() < 1e-4);
+    let fill = world
+        .get::<HourglassMeshSandState>(entity)
+        .unwrap()
+        .fill_percent;
+    assert!(fill > hourglass.upper_chamber + 0.1);
+    assert!(fill < 1.0);
+
+    // Once the timer is done the shown sand catches up
+    advance(&mut app, 2.0);
+    let sand_state = app.world().get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.fill_percent, 0.0);
+}
+
+#[test]
+fn global_config_sets_default_behavior() {
+    let mut app = test_app();
+    let config = HourglassConfig::new()
+        .with_flip_duration(0.25)
+        .with_auto_flip(true)
+        .with_flow_curve(EaseFunction::QuadraticOut)
+        .with_sand_splash(SandSplashConfig::default());
+    let entity = spawn(
+        &mut app,
+        default_builder(4.0)
+            .with_flip_duration(2.0)
+            .with_global_config(&config),
+    );
+    advance(&mut app, 1.0);
+
+    // Builder calls take precedence over the defaults
+    let world = app.world();
+    let hourglass = world.get::<Hourglass>(entity).unwrap();
+    assert_eq!(hourglass.flip_duration, 2.0);
+    assert!(hourglass.auto_flip_when_empty);
+    assert!(world.get::<SandSplash>(entity).is_some());
+
+    // A quarter of the time has passed, but the sand drains quickly at first
+    let expected = 1.0 - EaseFunction::QuadraticOut.sample_clamped(1.0 - hourglass.upper_chamber);
+    let fill = world
+        .get::<HourglassMeshSandState>(entity)
+        .unwrap()
+        .fill_percent;
+    assert!((fill - expected).abs() < 1e-4);
+    assert!(fill < hourglass.upper_chamber - 0.1);
+}
+
+#[test]
+fn splash_particles_are_capped_globally() {
+    let run = |overflow| {
+        let mut app = test_app();
+        app.insert_resource(
+            HourglassConfig::new()
+                .with_max_splash_particles(5)
+                .with_splash_overflow(overflow),
+        );
+        for _ in 0..3 {
+            spawn(
+                &mut app,
+                default_builder(5.0).with_sand_splash(SandSplashConfig::default()),
+            );
+        }
+        advance(&mut app, 0.5);
+
+        let world = app.world_mut();
+        let particles = world
+            .query_filtered::<(), With<SandSplashParticle>>()
+            .iter(world)
+            .count();
+        (particles, world.resource::<HourglassDiagnostics>().clone())
+    };
+
+    let (particles, diagnostics) = run(SplashOverflowPolicy::DropNew);
+    assert_eq!(particles, 5);
+    assert_eq!(diagnostics.splash_particles, 5);
+    assert!(diagnostics.dropped_splash_particles > 0);
+
+    let (particles, diagnostics) = run(SplashOverflowPolicy::RecycleOldest);
+    assert_eq!(particles, 5);
+    assert_eq!(diagnostics.dropped_splash_particles, 0);
+}
+
+#[test]
+fn neck_sparkles_share_the_splash_particle_cap() {
+    let sparkle = || {
+        default_builder(5.0).with_neck_sparkle(NeckSparkleConfig {
+            particle_count: 4,
+            ..default()
+        })
+    };
+    let mut app = test_app();
+    app.insert_resource(HourglassConfig::new().with_max_splash_particles(5));
+    for _ in 0..3 {
+        spawn(&mut app, sparkle());
+    }
+    advance(&mut app, 0.3);
+
+    let world = app.world_mut();
+    let particles = world
+        .query_filtered::<(), With<NeckSparkleParticle>>()
+        .iter(world)
+        .count();
+    let diagnostics = world.resource::<HourglassDiagnostics>();
+    assert_eq!(particles, 5);
+    assert_eq!(diagnostics.splash_particles, 5);
+    assert!(diagnostics.dropped_splash_particles > 0);
+
+    // Power saving and culling stop sparkles like splashes
+    let mut app = test_app();
+    app.insert_resource(HourglassPowerSaving::new(true));
+    spawn(&mut app, sparkle());
+    advance(&mut app, 0.3);
+    let world = app.world_mut();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<NeckSparkleParticle>>()
+            .iter(world)
+            .count(),
+        0
+    );
+
+    let mut app = test_app();
+    app.init_resource::<bevy::render::view::PreviousVisibleEntities>();
+    let entity = spawn(&mut app, sparkle());
+    advance(&mut app, 0.3);
+    assert!(app.world().get::<HourglassCulled>(entity).is_some());
+    let world = app.world_mut();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<NeckSparkleParticle>>()
+            .iter(world)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn label_stays_upright_above_flipping_hourglass() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0)
+            .with_flip_duration(1.0)
+            .with_label("Boss Enrage", LabelConfig::default()),
+    );
+    let label = app
+        .world()
+        .get::<Children>(entity)
+        .unwrap()
+        .iter()
+        .find(|child| app.world().get::<HourglassLabel>(*child).is_some())
+        .unwrap();
+    assert_eq!(
+        app.world().get::<Text2d>(label).map(|text| text.0.as_str()),
+        Some("Boss Enrage")
+    );
+
+    app.world_mut().get_mut::<Hourglass>(entity).unwrap().flip();
+    advance(&mut app, 0.5);
+
+    // Combining the hourglass and label transforms gives the label's placement in the world
+    let world = app.world();
+    let hourglass_transform = world.get::<Transform>(entity).unwrap();
+    assert!(hourglass_transform.rotation.angle_between(Quat::IDENTITY) > 1.0);
+    let label_transform =
+        hourglass_transform.mul_transform(*world.get::<Transform>(label).unwrap());
+    assert!(label_transform.translation.y > 100.0);
+    assert!(label_transform.translation.x.abs() < 1e-3);
+    assert!(label_transform.rotation.angle_between(Quat::IDENTITY) < 1e-3);
+}
+
+#[test]
+fn bottom_anchor_stands_hourglass_on_its_transform() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_anchor(bevy::sprite::Anchor::BottomCenter),
+    );
+    app.update();
+
+    let world = app.world();
+    let size = world.get::<Hourglass>(entity).unwrap().size;
+    let sand_state = world.get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.center_offset, Vec2::new(0.0, size.y / 2.0));
+
+    for child in world.get::<Children>(entity).unwrap().iter() {
+        let translation = world.get::<Transform>(child).unwrap().translation;
+        if world.get::<HourglassMeshBody>(child).is_some() {
+            assert_eq!(translation.y, size.y / 2.0);
+        }
+        if let Some(HourglassMeshPlate::Bottom) = world.get::<HourglassMeshPlate>(child) {
+            let plate_height = HourglassMeshPlatesConfig::default().height;
+            assert!((translation.y - plate_height / 2.0).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn resize_rebuilds_parts_at_the_new_size() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(5.0));
+    app.update();
+    let size = app.world().get::<Hourglass>(entity).unwrap().size;
+
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            resize_hourglass(&mut commands, entity, 100.0);
+        })
+        .unwrap();
+    app.update();
+
+    let world = app.world();
+    assert!(world.get::<HourglassResize>(entity).is_none());
+    assert_eq!(world.get::<Hourglass>(entity).unwrap().size, size / 2.0);
+    let sand_state = world.get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.body_config.total_height, 100.0);
+    assert_eq!(
+        sand_state.sand_config.wall_offset,
+        HourglassMeshSandConfig::default().wall_offset / 2.0
+    );
+
+    for child in world.get::<Children>(entity).unwrap().iter() {
+        if let Some(HourglassMeshPlate::Top) = world.get::<HourglassMeshPlate>(child) {
+            let translation = world.get::<Transform>(child).unwrap().translation;
+            assert!((translation.y - (size.y / 4.0 - 2.5)).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn resize_rebuilds_the_collider() {
+    #[derive(Component)]
+    struct TestCollider(Vec<Vec<Vec2>>);
+
+    struct TestBackend;
+
+    impl ColliderBackend for TestBackend {
+        fn insert_collider(
+            &self,
+            entity: &mut bevy::ecs::system::EntityCommands,
+            collider: &HourglassCollider,
+        ) {
+            entity.insert(TestCollider(collider.parts.clone()));
+        }
+    }
+
+    fn extents(parts: &[Vec<Vec2>]) -> Rect {
+        let mut rect = Rect::EMPTY;
+        for point in parts.iter().flatten() {
+            rect = rect.union_point(*point);
+        }
+        rect
+    }
+
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0)
+            .with_anchor(bevy::sprite::Anchor::BottomCenter)
+            .with_collider(TestBackend),
+    );
+    app.update();
+    let before = extents(&app.world().get::<HourglassCollider>(entity).unwrap().parts);
+
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            resize_hourglass(&mut commands, entity, 100.0);
+        })
+        .unwrap();
+    app.update();
+
+    // The collider halves with the hourglass, still standing on its anchor
+    let world = app.world();
+    let after = extents(&world.get::<HourglassCollider>(entity).unwrap().parts);
+    assert!((after.size() - before.size() / 2.0).length() < 1e-3);
+    assert!((after.min - before.min / 2.0).length() < 1e-3);
+    assert!(after.min.y.abs() < 1e-3);
+    assert_eq!(
+        extents(&world.get::<TestCollider>(entity).unwrap().0),
+        after
+    );
+}
+
+#[test]
+fn parts_give_direct_access_to_children() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(5.0));
+
+    let world = app.world();
+    let parts = *world.get::<HourglassParts>(entity).unwrap();
+    assert!(world
+        .get::<HourglassMeshBody>(parts.body.unwrap())
+        .is_some());
+    assert!(matches!(
+        world.get::<HourglassMeshPlate>(parts.top_plate.unwrap()),
+        Some(HourglassMeshPlate::Top)
+    ));
+    assert!(matches!(
+        world.get::<HourglassMeshSand>(parts.sand(SandBulb::Bottom).unwrap()),
+        Some(HourglassMeshSand::BottomBulb)
+    ));
+    assert!(world
+        .get::<HourglassMeshSandStream>(parts.stream.unwrap())
+        .is_some());
+    assert_eq!(parts.dust_motes, None);
+    assert_eq!(parts.label, None);
+}
+
+#[test]
+fn liquid_contents_drip_through_the_neck() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_sand(HourglassMeshSandConfig {
+            content_style: ContentStyle::liquid(),
+            ..default()
+        }),
+    );
+    advance(&mut app, 1.0);
+
+    let world = app.world();
+    let sand_state = world.get::<HourglassMeshSandState>(entity).unwrap();
+    assert!(sand_state.surface_phase > 0.0);
+    assert!(sand_state
+        .sand_outline_params(SandBulb::Top)
+        .surface_wave
+        .is_some());
+
+    let children = world.get::<Children>(entity).unwrap();
+    let stream = children
+        .iter()
+        .find(|child| world.get::<HourglassMeshSandStream>(*child).is_some())
+        .unwrap();
+    let mesh = world
+        .resource::<Assets<Mesh>>()
+        .get(&world.get::<Mesh2d>(stream).unwrap().0)
+        .unwrap();
+    assert!(mesh.count_vertices() > 0);
+}
+
+#[test]
+fn liquid_waves_rebuild_the_sand_in_steps() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_sand(HourglassMeshSandConfig {
+            content_style: ContentStyle::Liquid {
+                wave_amplitude: 1.5,
+                wavelength: 24.0,
+                wave_speed: 1.0,
+                droplet_spacing: 18.0,
+                droplet_speed: 160.0,
+            },
+            ..default()
+        }),
+    );
+    // Half full, so the surface is inside the bulb
+    advance(&mut app, 2.5);
+    app.world_mut()
+        .resource_mut::<HourglassGlobalState>()
+        .paused = true;
+    app.update();
+
+    let top_sand = app
+        .world()
+        .get::<HourglassParts>(entity)
+        .unwrap()
+        .top_sand
+        .unwrap();
+    let positions = |app: &App| {
+        let world = app.world();
+        let mesh = world
+            .resource::<Assets<Mesh>>()
+            .get(&world.get::<Mesh2d>(top_sand).unwrap().0)
+            .unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh has positions");
+        };
+        positions.clone()
+    };
+
+    // Waves moving a tenth of a radian per frame only move the surface every other frame
+    let mut previous = positions(&app);
+    let mut rebuilds = 0;
+    for _ in 0..10 {
+        app.update();
+        let current = positions(&app);
+        if current != previous {
+            rebuilds += 1;
+        }
+        previous = current;
+    }
+    assert!((4..=6).contains(&rebuilds), "{rebuilds} rebuilds");
+}
+
+#[test]
+fn sand_overflow_piles_up_below_an_empty_hourglass() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(1.0).with_sand_overflow(SandOverflowConfig::default()),
+    );
+    advance(&mut app, 0.5);
+
+    let overflow = app
+        .world()
+        .get::<HourglassParts>(entity)
+        .unwrap()
+        .overflow
+        .unwrap();
+    assert_eq!(
+        app.world().get::<Visibility>(overflow),
+        Some(&Visibility::Hidden)
+    );
+
+    // Empty after one second, fully grown three seconds later
+    advance(&mut app, 4.5);
+    let world = app.world();
+    assert_eq!(world.get::<SandOverflow>(overflow).unwrap().growth, 1.0);
+    assert_eq!(
+        world.get::<Visibility>(overflow),
+        Some(&Visibility::Inherited)
+    );
+    let transform = world.get::<Transform>(overflow).unwrap();
+    let plates = HourglassMeshPlatesConfig::default();
+    assert!((transform.scale.x - plates.width * 1.2).abs() < 1e-3);
+    // Resting on the underside of the bottom plate
+    assert!(transform.translation.y <= -100.0 - plates.height);
+}
+
+#[test]
+fn clicks_are_matched_to_hourglass_parts() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(5.0).with_interaction());
+    app.update();
+
+    let world = app.world();
+    assert!(world.get::<HourglassInteractive>(entity).is_some());
+    let shape = world.get::<HourglassMeshShape>(entity).unwrap();
+    let part_at = |body: Vec2| {
+        // Plates sit just above and below the glass
+        let plate_height = HourglassMeshPlatesConfig::default().height;
+        let plate_y = 100.0 + plate_height / 2.0;
+        shape.part_at(
+            body,
+            Some(body - Vec2::Y * plate_y),
+            Some(body + Vec2::Y * plate_y),
+        )
+    };
+    assert_eq!(part_at(Vec2::new(0.0, 50.0)), Some(HourglassPart::TopBulb));
+    assert_eq!(
+        part_at(Vec2::new(0.0, -50.0)),
+        Some(HourglassPart::BottomBulb)
+    );
+    assert_eq!(
+        part_at(Vec2::new(60.0, 104.0)),
+        Some(HourglassPart::TopPlate)
+    );
+    assert_eq!(
+        part_at(Vec2::new(0.0, -104.0)),
+        Some(HourglassPart::BottomPlate)
+    );
+    assert_eq!(part_at(Vec2::new(300.0, 0.0)), None);
+}
+
+#[test]
+fn badges_show_numbers_in_a_corner() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_badge(HourglassBadgeConfig {
+            prefix: "x".to_string(),
+            ..default()
+        }),
+    );
+    app.update();
+    assert_eq!(
+        app.world().get::<HourglassParts>(entity).unwrap().badge,
+        None
+    );
+
+    let mut hourglass = app.world_mut().get_mut::<Hourglass>(entity).unwrap();
+    hourglass.set_badge(Some(3));
+    let size = hourglass.size;
+    app.update();
+    app.update();
+
+    let world = app.world();
+    let badge = world.get::<HourglassParts>(entity).unwrap().badge.unwrap();
+    assert_eq!(world.get::<HourglassBadge>(badge).unwrap().value, 3);
+    let translation = world.get::<Transform>(badge).unwrap().translation;
+    assert_eq!(translation.truncate(), size / 2.0);
+    let text = world.get::<Children>(badge).unwrap()[0];
+    assert_eq!(world.get::<Text2d>(text).unwrap().0, "x3");
+
+    app.world_mut()
+        .get_mut::<Hourglass>(entity)
+        .unwrap()
+        .set_badge(None);
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(badge),
+        Some(&Visibility::Hidden)
+    );
+}
+
+#[test]
+fn dust_motes_drift_in_empty_glass_only() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_dust_motes(DustMotesConfig {
+            density: 5.0,
+            ..default()
+        }),
+    );
+    advance(&mut app, 0.5);
+
+    let world = app.world();
+    let sand_state = world.get::<HourglassMeshSandState>(entity).unwrap();
+    let children = world.get::<Children>(entity).unwrap();
+    let dust_motes = children
+        .iter()
+        .find_map(|child| world.get::<DustMotes>(child))
+        .unwrap();
+    assert!(!dust_motes.motes.is_empty());
+
+    // The top bulb is still almost full, so motes float below the sand in the top bulb (in the
+    // neck or the bottom bulb) or above it
+    let top_surface = sand_fill_line(sand_state.fill_percent, SandBulb::Top, 20.0, -100.0, 100.0);
+    assert!(dust_motes
+        .motes
+        .iter()
+        .all(|mote| mote.position.y < 10.0 || mote.position.y > top_surface));
+
+    app.world_mut()
+        .resource_mut::<HourglassAccessibility>()
+        .reduced_motion = true;
+    app.update();
+    let world = app.world();
+    let children = world.get::<Children>(entity).unwrap();
+    assert!(children
+        .iter()
+        .filter_map(|child| world.get::<DustMotes>(child))
+        .all(|dust_motes| dust_motes.motes.is_empty()));
+}
+
+#[test]
+fn power_saving_batches_sand_updates_and_skips_splashes() {
+    let mut app = test_app();
+    app.insert_resource(HourglassPowerSaving::new(true).with_sand_update_interval(0.5));
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_sand_splash(SandSplashConfig::default()),
+    );
+
+    advance(&mut app, 0.3);
+    let world = app.world_mut();
+    assert!(
+        world
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .needs_update
+    );
+    assert_eq!(world.query::<&SandSplashParticle>().iter(world).count(), 0);
+
+    // Pending changes are applied once the interval has passed
+    advance(&mut app, 0.2);
+    let world = app.world_mut();
+    assert!(
+        !world
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .needs_update
+    );
+    assert_eq!(world.query::<&SandSplashParticle>().iter(world).count(), 0);
+}
+
+#[test]
+fn sand_rebuilds_take_turns_within_the_frame_budget() {
+    let mut app = test_app();
+    app.insert_resource(HourglassConfig::default().with_max_sand_rebuilds_per_frame(1));
+    let hourglasses: Vec<Entity> = (0..3)
+        .map(|_| spawn(&mut app, default_builder(5.0)))
+        .collect();
+
+    // Every running hourglass needs a rebuild every frame, and one of them gets it
+    let mut rebuilt = Vec::new();
+    for _ in 0..3 {
+        app.update();
+        let world = app.world();
+        let fresh: Vec<Entity> = hourglasses
+            .iter()
+            .copied()
+            .filter(|&entity| {
+                !world
+                    .get::<HourglassMeshSandState>(entity)
+                    .unwrap()
+                    .needs_update
+            })
+            .collect();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(
+            world
+                .resource::<HourglassDiagnostics>()
+                .deferred_sand_rebuilds,
+            2
+        );
+        rebuilt.extend(fresh);
+    }
+    rebuilt.sort();
+    assert_eq!(rebuilt, hourglasses);
+}
+
+#[test]
+fn sand_of_many_hourglasses_is_generated_in_parallel() {
+    let mut app = test_app();
+    let hourglasses: Vec<Entity> = (0..12)
+        .map(|i| spawn(&mut app, default_builder(2.0 + (i % 3) as f32)))
+        .collect();
+    advance(&mut app, 1.0);
+
+    // Hourglasses running for the same time get the same sand, whichever thread generated it
+    let world = app.world();
+    let meshes = world.resource::<Assets<Mesh>>();
+    let top_sand: Vec<Vec<[f32; 3]>> = hourglasses
+        .iter()
+        .map(|&entity| {
+            let top_sand = world
+                .get::<HourglassParts>(entity)
+                .unwrap()
+                .top_sand
+                .unwrap();
+            let mesh = meshes
+                .get(&world.get::<Mesh2d>(top_sand).unwrap().0)
+                .unwrap();
+            let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("mesh has positions");
+            };
+            positions.clone()
+        })
+        .collect();
+    for (i, positions) in top_sand.iter().enumerate() {
+        assert!(
+            !world
+                .get::<HourglassMeshSandState>(hourglasses[i])
+                .unwrap()
+                .needs_update
+        );
+        assert_eq!(positions, &top_sand[i % 3]);
+    }
+    assert_ne!(top_sand[0], top_sand[1]);
+}
+
+#[test]
+fn invalid_configs_are_clamped_with_a_warning() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(-5.0).with_sand(HourglassMeshSandConfig {
+            fill_percent: 1.5,
+            ..default()
+        }),
+    );
+
+    let world = app.world();
+    assert_eq!(world.get::<Hourglass>(entity).unwrap().total_time, 0.0);
+    assert_eq!(
+        world
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .fill_percent,
+        1.0
+    );
+    assert_eq!(
+        HourglassWarning::ClampedConfig {
+            field: "sand fill_percent",
+            value: 1.5,
+            clamped: 1.0,
+        }
+        .to_string(),
+        "Hourglass sand fill_percent of 1.5 is invalid, using 1"
+    );
+}
+
+#[test]
+fn styled_builder_applies_the_whole_look_with_overrides() {
+    let mut app = test_app();
+    let config = HourglassVisualConfig {
+        plates: None,
+        sand_splash: Some(SandSplashConfig::default()),
+        ..default()
+    };
+    let entity = spawn(
+        &mut app,
+        HourglassMeshBuilder::styled(config)
+            .with_sand(HourglassMeshSandConfig {
+                color: Color::BLACK,
+                ..default()
+            })
+            .with_timing(5.0),
+    );
+
+    let world = app.world();
+    let parts = world.get::<HourglassParts>(entity).unwrap();
+    assert!(parts.body.is_some() && parts.top_sand.is_some());
+    assert!(parts.top_plate.is_none());
+    assert!(world.get::<SandSplash>(entity).is_some());
+    assert_eq!(
+        world
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .sand_config
+            .color,
+        Color::BLACK
+    );
+}
+
+#[test]
+fn configs_blend_numbers_and_switch_discrete_values_halfway() {
+    let a = HourglassVisualConfig::default();
+    let b = HourglassVisualConfig {
+        body: HourglassMeshBodyConfig {
+            total_height: 300.0,
+            ..default()
+        },
+        plates: None,
+        sand: HourglassMeshSandConfig {
+            top_color: Some(Color::srgb(1.0, 0.0, 0.0)),
+            ..default()
+        },
+        render_style: RenderStyle::Minimal { stroke_width: 2.0 },
+        ..default()
+    };
+
+    assert_eq!(lerp_configs(&a, &b, -1.0), a);
+    assert_eq!(lerp_configs(&a, &b, 1.0), b);
+
+    let quarter = lerp_configs(&a, &b, 0.25);
+    assert_eq!(quarter.body.total_height, 225.0);
+    assert!(quarter.plates.is_some());
+    assert_eq!(quarter.render_style, RenderStyle::Filled);
+    // Bulb colors set on one side only blend from the main color
+    assert_eq!(
+        quarter.sand.top_color,
+        Some(a.sand.color.mix(&Color::srgb(1.0, 0.0, 0.0), 0.25))
+    );
+
+    let three_quarters = lerp_configs(&a, &b, 0.75);
+    assert!(three_quarters.plates.is_none());
+    assert_eq!(
+        three_quarters.render_style,
+        RenderStyle::Minimal { stroke_width: 2.0 }
+    );
+}
+
+#[test]
+fn quality_tiers_scale_the_sand_globally_and_per_hourglass() {
+    let mut app = test_app();
+    app.insert_resource(HourglassConfig::default().with_quality(QualityTier::Medium));
+    let medium = spawn(&mut app, default_builder(100.0));
+    let low = spawn(
+        &mut app,
+        default_builder(100.0).with_quality(QualityTier::Low),
+    );
+    let ultra = spawn(
+        &mut app,
+        default_builder(100.0).with_quality(QualityTier::Ultra),
+    );
+    advance(&mut app, 0.5);
+
+    let world = app.world();
+    let vertex_count = |entity: Entity| {
+        let top_sand = world
+            .get::<HourglassParts>(entity)
+            .unwrap()
+            .top_sand
+            .unwrap();
+        let handle = &world.get::<Mesh2d>(top_sand).unwrap().0;
+        world
+            .resource::<Assets<Mesh>>()
+            .get(handle)
+            .unwrap()
+            .count_vertices()
+    };
+    assert!(vertex_count(low) < vertex_count(medium));
+    assert!(vertex_count(medium) < vertex_count(ultra));
+
+    // Low quality waits for the sand to drop by a whole percent
+    let fill = |entity: Entity| {
+        world
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .fill_percent
+    };
+    assert_eq!(fill(low), 1.0);
+    assert!(fill(ultra) < 1.0);
+}
+
+#[test]
+fn colliders_are_convex_parts_inserted_by_the_backend() {
+    #[derive(Component)]
+    struct TestCollider(usize);
+
+    struct TestBackend(ColliderShape);
+
+    impl ColliderBackend for TestBackend {
+        fn shape(&self) -> ColliderShape {
+            self.0
+        }
+
+        fn insert_collider(
+            &self,
+            entity: &mut bevy::ecs::system::EntityCommands,
+            collider: &HourglassCollider,
+        ) {
+            entity.insert(TestCollider(collider.parts.len()));
+        }
+    }
+
+    fn area(polygon: &[Vec2]) -> f32 {
+        (0..polygon.len())
+            .map(|i| polygon[i].perp_dot(polygon[(i + 1) % polygon.len()]))
+            .sum::<f32>()
+            / 2.0
+    }
+
+    let mut app = test_app();
+    let compound = spawn(
+        &mut app,
+        default_builder(5.0).with_collider(TestBackend(ColliderShape::Compound)),
+    );
+    let decomposed = spawn(
+        &mut app,
+        default_builder(5.0).with_collider(TestBackend(ColliderShape::ConvexDecomposition)),
+    );
+    app.update();
+
+    let world = app.world();
+    let collider = world.get::<HourglassCollider>(compound).unwrap();
+    // Both bulbs, the neck and the plates
+    assert_eq!(collider.parts.len(), 5);
+    assert_eq!(world.get::<TestCollider>(compound).unwrap().0, 5);
+
+    let collider = world.get::<HourglassCollider>(decomposed).unwrap();
+    for part in &collider.parts {
+        let convex = (0..part.len()).all(|i| {
+            let [a, b, c] = [0, 1, 2].map(|k| part[(i + k) % part.len()]);
+            (b - a).perp_dot(c - b) >= -1e-3
+        });
+        assert!(convex, "part {part:?} is not convex");
+    }
+    let shape = world.get::<HourglassMeshShape>(decomposed).unwrap();
+    let outline = hourglass_geometry::HourglassShapeBuilder {
+        total_height: shape.body_config.total_height,
+        bulb_style: shape.body_config.bulb_style.clone(),
+        neck_style: shape.body_config.neck_style.clone(),
+    }
+    .generate_outline()
+    .into_iter()
+    .map(Vec2::from)
+    .collect::<Vec<_>>();
+    let glass_area: f32 = convex_decomposition(&outline)
+        .iter()
+        .map(|part| area(part))
+        .sum();
+    assert!((glass_area - area(&outline).abs()).abs() < 1.0);
+    assert!(convex_decomposition(&outline).len() < outline.len() - 2);
+}
+
+#[test]
+fn tipped_over_hourglasses_pause_until_standing_again() {
+    #[derive(Resource, Default)]
+    struct Tipped(Vec<bool>);
+
+    let mut app = test_app();
+    app.add_plugins(TransformPlugin)
+        .init_resource::<Tipped>()
+        .add_systems(
+            PostUpdate,
+            |mut tipped: ResMut<Tipped>, mut events: EventReader<HourglassTippedEvent>| {
+                tipped.0.extend(events.read().map(|event| event.tipped));
+            },
+        );
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0).with_tip_detection(HourglassTipDetection::default()),
+    );
+    // Knock the hourglass over through a parent
+    let parent = app
+        .world_mut()
+        .spawn(Transform::from_rotation(Quat::from_rotation_z(
+            std::f32::consts::FRAC_PI_2 - 0.1,
+        )))
+        .id();
+    app.world_mut().entity_mut(entity).insert(ChildOf(parent));
+    advance(&mut app, 1.0);
+
+    let remaining = app.world().get::<Hourglass>(entity).unwrap().remaining_time;
+    assert!(!app.world().get::<Hourglass>(entity).unwrap().running);
+    assert!(
+        app.world()
+            .get::<HourglassTipDetection>(entity)
+            .unwrap()
+            .tipped
+    );
+    advance(&mut app, 1.0);
+    assert_eq!(
+        app.world().get::<Hourglass>(entity).unwrap().remaining_time,
+        remaining
+    );
+
+    // Standing upside down is not lying on its side
+    app.world_mut()
+        .entity_mut(parent)
+        .insert(Transform::from_rotation(Quat::from_rotation_z(
+            std::f32::consts::PI,
+        )));
+    advance(&mut app, 1.0);
+    let hourglass = app.world().get::<Hourglass>(entity).unwrap();
+    assert!(hourglass.running);
+    assert!(hourglass.remaining_time < remaining);
+    assert_eq!(app.world().resource::<Tipped>().0, vec![true, false]);
+}
+
+#[test]
+fn hourglasses_tipped_through_their_own_transform_pause() {
+    let mut app = test_app();
+    app.add_plugins(TransformPlugin);
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0)
+            .with_base_rotation(0.2)
+            .with_tip_detection(HourglassTipDetection::default()),
+    );
+    advance(&mut app, 0.5);
+
+    // Knocked over like a physics engine would, directly on the hourglass
+    let tilt = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2 - 0.2);
+    app.world_mut()
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .rotation *= tilt;
+    advance(&mut app, 1.0);
+    let world = app.world();
+    assert!(world.get::<HourglassTipDetection>(entity).unwrap().tipped);
+    assert!(!world.get::<Hourglass>(entity).unwrap().running);
+    let rotation = world.get::<Transform>(entity).unwrap().rotation;
+    assert!(rotation.angle_between(Quat::from_rotation_z(0.2) * tilt) < 1e-3);
+
+    // Standing it back up resumes the timer, with the base rotation still applied
+    app.world_mut()
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .rotation = Quat::from_rotation_z(0.2);
+    advance(&mut app, 0.5);
+    let world = app.world();
+    assert!(!world.get::<HourglassTipDetection>(entity).unwrap().tipped);
+    assert!(world.get::<Hourglass>(entity).unwrap().running);
+    let rotation = world.get::<Transform>(entity).unwrap().rotation;
+    assert!(rotation.angle_between(Quat::from_rotation_z(0.2)) < 1e-3);
+}
+
+#[test]
+fn swing_mounts_swing_around_the_pivot_with_level_sand() {
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(10.0));
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(SwingMount::new(0.3, 0.25).with_length(100.0));
+    // A quarter of a swing reaches the largest angle
+    advance(&mut app, 1.0);
+
+    let world = app.world();
+    let hourglass = world.get::<Hourglass>(entity).unwrap();
+    assert!((hourglass.base_rotation - 0.3).abs() < 1e-3);
+    let translation = world.get::<Transform>(entity).unwrap().translation;
+    // The pivot stays in place 100 pixels above the rest position
+    let pivot = Vec3::new(0.0, 100.0, 0.0);
+    assert!(((translation - pivot).length() - 100.0).abs() < 1e-2);
+    assert!(translation.x > 0.0);
+    let sand_state = world.get::<HourglassMeshSandState>(entity).unwrap();
+    assert_eq!(sand_state.tilt, hourglass.base_rotation);
+
+    // Half a swing later it hangs on the other side
+    advance(&mut app, 2.0);
+    let world = app.world();
+    assert!((world.get::<Hourglass>(entity).unwrap().base_rotation + 0.3).abs() < 1e-3);
+    assert!(world.get::<Transform>(entity).unwrap().translation.x < 0.0);
+}
+
+#[test]
+fn time_zones_scale_the_speed_of_hourglasses_inside() {
+    let mut app = test_app();
+    app.add_plugins(TransformPlugin);
+    let inside = spawn(&mut app, default_builder(10.0));
+    let outside = spawn(
+        &mut app,
+        HourglassMeshBuilder::new(Transform::from_xyz(200.0, 0.0, 0.0))
+            .with_body(HourglassMeshBodyConfig::default())
+            .with_timing(10.0),
+    );
+    let zone = app
+        .world_mut()
+        .spawn((
+            HourglassTimeZone::rect(Vec2::new(100.0, 100.0), 2.0),
+            Transform::default(),
+        ))
+        .id();
+    app.world_mut().spawn((
+        HourglassTimeZone::circle(50.0, 0.5),
+        Transform::from_xyz(20.0, 0.0, 0.0),
+    ));
+    // Zones are detected once transforms have been propagated
+    app.update();
+    let remaining =
+        |app: &App, entity| app.world().get::<Hourglass>(entity).unwrap().remaining_time;
+    let (inside_before, outside_before) = (remaining(&app, inside), remaining(&app, outside));
+    advance(&mut app, 1.0);
+
+    let world = app.world();
+    // Overlapping zones multiply their speeds
+    assert_eq!(
+        world.get::<HourglassInTimeZone>(inside),
+        Some(&HourglassInTimeZone { speed: 1.0 })
+    );
+    assert!(world.get::<HourglassInTimeZone>(outside).is_none());
+    assert!((inside_before - remaining(&app, inside) - 1.0).abs() < 1e-3);
+    assert!((outside_before - remaining(&app, outside) - 1.0).abs() < 1e-3);
+
+    // Moving the zone away brings the hourglass back to its own speed
+    app.world_mut()
+        .entity_mut(zone)
+        .insert(Transform::from_xyz(200.0, 0.0, 0.0));
+    advance(&mut app, 1.0);
+    let world = app.world();
+    assert_eq!(
+        world.get::<HourglassInTimeZone>(inside),
+        Some(&HourglassInTimeZone { speed: 0.5 })
+    );
+    assert_eq!(
+        world.get::<HourglassInTimeZone>(outside),
+        Some(&HourglassInTimeZone { speed: 2.0 })
+    );
+}
+
+#[test]
+fn shatter_spawns_fragments_and_despawns_hourglass() {
+    #[derive(Resource, Default)]
+    struct Shattered(Vec<Entity>);
+
+    let mut app = test_app();
+    app.init_resource::<Shattered>().add_systems(
+        PostUpdate,
+        |mut shattered: ResMut<Shattered>, mut events: EventReader<HourglassShatteredEvent>| {
+            shattered.0.extend(events.read().map(|event| event.entity));
+        },
+    );
+    let entity = spawn(&mut app, default_builder(5.0));
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            shatter_hourglass(&mut commands, entity, ShatterConfig::default());
+            // Shattering twice is ignored
+            shatter_hourglass(&mut commands, entity, ShatterConfig::default());
+        })
+        .unwrap();
+    advance(&mut app, 0.5);
+
+    let world = app.world();
+    assert_eq!(world.resource::<Shattered>().0, vec![entity]);
+    assert!(!world.get::<Hourglass>(entity).unwrap().running);
+    let children = world.get::<Children>(entity).unwrap();
+    let fragments = children
+        .iter()
+        .filter(|child| world.get::<ShatterFragment>(*child).is_some())
+        .count();
+    // Shards, both plates and the spilled sand
+    assert!(fragments > ShatterConfig::default().sand_particle_count + 2);
+
+    advance(&mut app, 1.5);
+    assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn duration_presets_set_total_time() {
+    assert_eq!(Hourglass::seconds(30).total_time, 30.0);
+    assert_eq!(Hourglass::minutes(5).total_time, 300.0);
+    assert_eq!(Hourglass::pomodoro().total_time, 25.0 * 60.0);
+
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(1.0).with_minutes(2));
+    assert_eq!(
+        app.world().get::<Hourglass>(entity).unwrap().total_time,
+        120.0
+    );
+}
+
+#[test]
+#[should_panic(expected = "must not be zero")]
+fn zero_duration_is_rejected() {
+    Hourglass::seconds(0);
+}
+
+#[test]
+#[should_panic(expected = "overflows u32 seconds")]
+fn overflowing_minutes_are_rejected() {
+    Hourglass::minutes(u32::MAX / 60 + 1);
+}
+
+#[test]
+fn top_bulb_switches_halfway_through_flips() {
+    let mut sim = HourglassSim::new(Hourglass {
+        auto_flip_when_empty: true,
+        flip_duration: 1.0,
+        ..Hourglass::new(1.0)
+    });
+    assert_eq!(sim.hourglass.current_top_bulb(), Bulb::First);
+
+    // Empty, then a quarter into the auto-flip the empty bulb is still on top
+    sim.step(1.0);
+    sim.step(0.25);
+    assert_eq!(sim.hourglass.current_top_bulb(), Bulb::First);
+    assert_eq!(sim.hourglass.visual_fill(SandBulb::Top), 0.0);
+    assert_eq!(sim.hourglass.visual_fill(SandBulb::Bottom), 1.0);
+
+    // Past its side the full bulb is shown on top, before the chambers are swapped
+    sim.step(0.5);
+    assert!(sim.hourglass.flipping);
+    assert_eq!(sim.hourglass.upper_chamber, 0.0);
+    assert_eq!(sim.hourglass.current_top_bulb(), Bulb::Second);
+    assert_eq!(sim.hourglass.visual_fill(SandBulb::Top), 1.0);
+
+    // Once flipped it stays on top, draining again within the same step
+    sim.step(0.25);
+    let hourglass = &sim.hourglass;
+    assert!(!hourglass.flipping);
+    assert!(hourglass.flipped);
+    assert_eq!(hourglass.current_top_bulb(), Bulb::Second);
+    assert_eq!(
+        hourglass.visual_fill(SandBulb::Top),
+        hourglass.upper_chamber
+    );
+    assert!((hourglass.upper_chamber - 0.75).abs() < 1e-4);
+
+    // Sand flowing during a flip keeps draining the chamber shown at the bottom
+    let mut sim = HourglassSim::new(Hourglass {
+        update_during_flip: true,
+        flip_duration: 1.0,
+        ..Hourglass::new(10.0)
+    });
+    sim.step(2.0);
+    sim.hourglass.flip();
+    sim.step(0.75);
+    let hourglass = &sim.hourglass;
+    assert_eq!(hourglass.current_top_bulb(), Bulb::Second);
+    assert_eq!(
+        hourglass.visual_fill(SandBulb::Bottom),
+        hourglass.upper_chamber
+    );
+    assert!((hourglass.visual_fill(SandBulb::Top) - 0.275).abs() < 1e-4);
+    sim.step(0.25);
+    assert_eq!(sim.hourglass.current_top_bulb(), Bulb::Second);
+    assert!((sim.hourglass.visual_fill(SandBulb::Top) - 0.25).abs() < 1e-4);
+}
+
+#[test]
+fn stats_count_flips_empties_and_runtime() {
+    let mut sim = HourglassSim::new(Hourglass {
+        auto_flip_when_empty: true,
+        flip_duration: 0.5,
+        ..Hourglass::new(1.0)
+    });
+    sim.step(1.0);
+    // The flip completes and the sand starts flowing again within the same step
+    sim.step(0.5);
+    sim.step(0.25);
+    assert_eq!(
+        sim.hourglass.stats,
+        HourglassStats {
+            flips: 1,
+            empties: 1,
+            elapsed: 1.75,
+        }
+    );
+
+    // Flips and empties are counted whether they come from the plugin or a simulation
+    let mut app = test_app();
+    let entity = spawn(&mut app, default_builder(1.0).with_auto_flip(true));
+    advance(&mut app, 4.0);
+    let stats = app.world().get::<Hourglass>(entity).unwrap().stats;
+    assert_eq!(
+        stats.empties as usize,
+        app.world().resource::<EventLog>().empties.len()
+    );
+    assert_eq!(
+        stats.flips as usize,
+        app.world().resource::<EventLog>().flip_starts.len()
+    );
+}
+
+#[derive(Resource, Default)]
+struct RenderedFills(Vec<f32>);
+
+struct RecordingRenderer;
+
+impl HourglassRenderer for RecordingRenderer {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RenderedFills>().add_systems(
+            Update,
+            (|hourglasses: Query<&Hourglass>, mut fills: ResMut<RenderedFills>| {
+                fills
+                    .0
+                    .extend(hourglasses.iter().map(|hourglass| hourglass.upper_chamber));
+            })
+            .in_set(HourglassRenderSet),
+        );
+    }
+}
+
+#[test]
+fn custom_renderer_draws_the_current_frame() {
+    let mut app = headless_app();
+    app.add_hourglass_renderer(RecordingRenderer)
+        .add_hourglass_renderer(RecordingRenderer);
+    let renderers = app.world().resource::<HourglassRenderers>();
+    assert!(renderers.contains::<MeshHourglassRenderer>());
+    assert!(renderers.contains::<RecordingRenderer>());
+    assert_eq!(renderers.names().len(), 2);
+    let entity = app.world_mut().spawn(Hourglass::new(1.0)).id();
+
+    advance(&mut app, 0.5);
+    let upper_chamber = app.world().get::<Hourglass>(entity).unwrap().upper_chamber;
+    let fills = &app.world().resource::<RenderedFills>().0;
+    assert_eq!(fills.last(), Some(&upper_chamber));
+    // Registering the backend again didn't add its systems twice
+    assert_eq!(fills.len(), 5);
+}
+
+#[test]
+fn mesh_hourglass_requires_transform_and_visibility() {
+    let mut app = headless_app();
+    let entity = app
+        .world_mut()
+        .spawn((HourglassMesh, Hourglass::new(1.0)))
+        .id();
+    assert!(app.world().get::<Transform>(entity).is_some());
+    assert!(app.world().get::<Visibility>(entity).is_some());
+
+    // Hourglass itself stays render agnostic
+    let entity = app.world_mut().spawn(Hourglass::new(1.0)).id();
+    assert!(app.world().get::<Transform>(entity).is_none());
+}
+
+#[test]
+fn hourglasses_out_of_view_skip_sand_updates() {
+    let mut app = test_app();
+    app.init_resource::<bevy::render::view::PreviousVisibleEntities>();
+    let entity = spawn(&mut app, default_builder(10.0));
+    let warm = spawn(&mut app, default_builder(10.0).with_update_when_hidden());
+
+    advance(&mut app, 0.5);
+    assert!(app.world().get::<HourglassCulled>(entity).is_some());
+    assert!(
+        app.world()
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .needs_update
+    );
+    assert!(app.world().get::<HourglassCulled>(warm).is_none());
+    assert!(
+        !app.world()
+            .get::<HourglassMeshSandState>(warm)
+            .unwrap()
+            .needs_update
+    );
+
+    // Coming back into view regenerates the sand
+    let body = app
+        .world()
+        .get::<HourglassParts>(entity)
+        .unwrap()
+        .body
+        .unwrap();
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(InheritedVisibility::VISIBLE);
+    app.world_mut()
+        .get_mut::<ViewVisibility>(body)
+        .unwrap()
+        .set();
+    app.update();
+    assert!(app.world().get::<HourglassCulled>(entity).is_none());
+    assert!(
+        !app.world()
+            .get::<HourglassMeshSandState>(entity)
+            .unwrap()
+            .needs_update
+    );
+}
+
+#[test]
+fn spawn_animation_grows_in_and_despawn_animation_removes_hourglass() {
+    let mut app = test_app();
+    let anim = SpawnAnim::ScaleIn {
+        duration: 1.0,
+        easing: EaseFunction::Linear,
+    };
+    let entity = spawn(&mut app, default_builder(10.0).with_spawn_animation(anim));
+
+    advance(&mut app, 0.5);
+    let scale = app.world().get::<Transform>(entity).unwrap().scale;
+    assert!((scale.x - 0.5).abs() < 0.01, "{scale}");
+
+    advance(&mut app, 0.6);
+    assert_eq!(
+        app.world().get::<Transform>(entity).unwrap().scale,
+        Vec3::ONE
+    );
+    assert!(app.world().get::<HourglassSpawnAnimation>(entity).is_none());
+
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            despawn_with_animation(&mut commands, entity, SpawnAnim::FadeIn { duration: 0.5 });
+        })
+        .unwrap();
+    advance(&mut app, 0.3);
+    assert!(app.world().get_entity(entity).is_ok());
+    advance(&mut app, 0.3);
+    assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn fade_in_gives_back_shared_materials() {
+    let mut app = test_app();
+    let plain = spawn(&mut app, default_builder(10.0));
+    let faded = spawn(
+        &mut app,
+        default_builder(10.0).with_spawn_animation(SpawnAnim::FadeIn { duration: 0.5 }),
+    );
+    let material = |app: &App, entity: Entity| {
+        let body = app.world().get::<HourglassParts>(entity).unwrap().body;
+        app.world()
+            .get::<MeshMaterial2d<ColorMaterial>>(body.unwrap())
+            .unwrap()
+            .id()
+    };
+
+    // Faded on a copy, and back on the shared glass once fully shown
+    advance(&mut app, 0.2);
+    assert_ne!(material(&app, faded), material(&app, plain));
+    advance(&mut app, 0.4);
+    assert!(app.world().get::<HourglassSpawnAnimation>(faded).is_none());
+    assert_eq!(material(&app, faded), material(&app, plain));
+    let alpha = app
+        .world()
+        .resource::<Assets<ColorMaterial>>()
+        .get(material(&app, plain))
+        .unwrap()
+        .color
+        .alpha();
+    assert_eq!(alpha, HourglassMeshBodyConfig::default().color.alpha());
+}
+
+#[test]
+fn part_offsets_move_plates_sand_and_body() {
+    let mut app = test_app();
+    let plain = spawn(&mut app, default_builder(10.0));
+    let offsets = PartOffsets {
+        plate_offset: -4.0,
+        sand_z: -0.1,
+        body_scale: Vec2::splat(1.1),
+    };
+    let entity = spawn(&mut app, default_builder(10.0).with_part_offsets(offsets));
+    app.update();
+
+    let transform =
+        |app: &App, part: Option<Entity>| *app.world().get::<Transform>(part.unwrap()).unwrap();
+    let plain_parts = *app.world().get::<HourglassParts>(plain).unwrap();
+    let parts = *app.world().get::<HourglassParts>(entity).unwrap();
+
+    let top = transform(&app, parts.top_plate).translation.y;
+    assert_eq!(
+        top,
+        transform(&app, plain_parts.top_plate).translation.y - 4.0
+    );
+    let bottom = transform(&app, parts.bottom_plate).translation.y;
+    assert_eq!(
+        bottom,
+        transform(&app, plain_parts.bottom_plate).translation.y + 4.0
+    );
+    assert_eq!(transform(&app, parts.top_sand).translation.z, -0.1);
+    assert_eq!(transform(&app, parts.stream).translation.z, -0.1);
+    assert_eq!(transform(&app, parts.body).scale, Vec3::new(1.1, 1.1, 1.0));
+}
+
+#[test]
+fn sand_behind_glass_is_drawn_under_the_body() {
+    let mut app = test_app();
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0).with_sand_occlusion(SandOcclusion::BehindGlass),
+    );
+    app.update();
+
+    let parts = *app.world().get::<HourglassParts>(entity).unwrap();
+    let z = |part: Option<Entity>| {
+        app.world()
+            .get::<Transform>(part.unwrap())
+            .unwrap()
+            .translation
+            .z
+    };
+    assert!(z(parts.top_sand) < z(parts.body));
+    assert!(z(parts.bottom_sand) < z(parts.body));
+    assert!(z(parts.stream) < z(parts.body));
+
+    // Sand without a z offset still stays behind rather than z-fighting with the glass
+    let entity = spawn(
+        &mut app,
+        default_builder(10.0)
+            .with_sand_occlusion(SandOcclusion::BehindGlass)
+            .with_part_offsets(PartOffsets {
+                sand_z: 0.0,
+                ..default()
+            }),
+    );
+    let world = app.world();
+    let parts = *world.get::<HourglassParts>(entity).unwrap();
+    let z = |part: Option<Entity>| world.get::<Transform>(part.unwrap()).unwrap().translation.z;
+    assert!(z(parts.top_sand) < z(parts.body));
+    assert!(z(parts.stream) < z(parts.body));
+}
+
+#[test]
+fn custom_materials_are_kept_as_the_sand_refills() {
+    let mut app = test_app();
+    app.init_resource::<Assets<HourglassLitMaterial>>();
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<HourglassLitMaterial>>()
+        .add(HourglassLitMaterial {
+            color: Color::WHITE,
+            lighting: HourglassLit::default(),
+            light: None,
+        });
+    let entity = spawn(
+        &mut app,
+        default_builder(1.0)
+            .with_auto_flip(true)
+            .with_flip_duration(0.5)
+            .with_body_material(material.clone())
+            .with_sand_material(material.clone()),
+    );
+
+    // The top bulb empties, then refills with the flip
+    advance(&mut app, 1.2);
+    let parts = *app.world().get::<HourglassParts>(entity).unwrap();
+    assert!(app.world().get::<Mesh2d>(parts.top_sand.unwrap()).is_none());
+    advance(&mut app, 0.8);
+    assert!(app.world().get::<Mesh2d>(parts.top_sand.unwrap()).is_some());
+
+    let world = app.world();
+    for part in [parts.body, parts.top_sand, parts.bottom_sand, parts.stream] {
+        let part = part.unwrap();
+        assert_eq!(
+            world
+                .get::<MeshMaterial2d<HourglassLitMaterial>>(part)
+                .map(|handle| handle.0.id()),
+            Some(material.id())
+        );
+        assert!(world.get::<MeshMaterial2d<ColorMaterial>>(part).is_none());
+    }
+    // Parts without a custom material keep their colors
+    assert!(world
+        .get::<MeshMaterial2d<ColorMaterial>>(parts.top_plate.unwrap())
+        .is_some());
+}
+
+#[test]
+fn lit_hourglasses_follow_the_light() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HourglassPlugin,
+        HourglassLightingPlugin,
+    ))
+    .init_asset::<Mesh>()
+    .init_asset::<ColorMaterial>();
+    app.update();
+
+    let entity = spawn(
+        &mut app,
+        default_builder(5.0).with_lighting(HourglassLit::default()),
+    );
+    app.update();
+    let body = app
+        .world()
+        .get::<HourglassParts>(entity)
+        .unwrap()
+        .body
+        .unwrap();
+    let material = |app: &App| {
+        let handle = app
+            .world()
+            .get::<MeshMaterial2d<HourglassLitMaterial>>(body)
+            .expect("glass uses the lit material");
+        app.world()
+            .resource::<Assets<HourglassLitMaterial>>()
+            .get(&handle.0)
+            .unwrap()
+            .clone()
+    };
+    assert!(app
+        .world()
+        .get::<MeshMaterial2d<ColorMaterial>>(body)
+        .is_none());
+    assert_eq!(material(&app).light, None);
+    assert_eq!(
+        material(&app).color,
+        HourglassMeshBodyConfig::default().material_color()
+    );
+
+    let light = app
+        .world_mut()
+        .spawn((
+            HourglassLight2d::default(),
+            Transform::from_xyz(50.0, 20.0, 0.0),
+        ))
+        .id();
+    app.update();
+    app.update();
+    let (position, _) = material(&app).light.expect("the light reaches the glass");
+    assert_eq!(position, Vec3::new(50.0, 20.0, 150.0));
+
+    app.world_mut().despawn(light);
+    app.update();
+    assert_eq!(material(&app).light, None);
+}
+
+#[test]
+fn recommended_window_fits_the_canvas_and_leaves_events_to_the_page() {
+    let window = wasm_support::recommended_window_plugin()
+        .primary_window
+        .unwrap();
+    assert_eq!(window.canvas.as_deref(), Some("#bevy"));
+    assert!(window.fit_canvas_to_parent);
+    assert!(!window.prevent_default_event_handling);
+
+    let window = wasm_support::window_plugin_for_canvas("#game")
+        .primary_window
+        .unwrap();
+    assert_eq!(window.canvas.as_deref(), Some("#game"));
+}